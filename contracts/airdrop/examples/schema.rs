@@ -3,10 +3,12 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
+use mars_airdrop::error::ErrorCode;
 use mars_airdrop::state::{Config, State};
 use mars_periphery::airdrop::{
-    ClaimResponse, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse,
-    UserInfoResponse,
+    ClaimResponse, ClaimedBitmapResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+    MerkleRootsResponse, PendingSweepResponse, QueryMsg, StateResponse, UserInfoResponse,
+    UsersResponse,
 };
 
 fn main() {
@@ -23,6 +25,11 @@ fn main() {
     export_schema(&schema_for!(StateResponse), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(ClaimResponse), &out_dir);
+    export_schema(&schema_for!(MerkleRootsResponse), &out_dir);
+    export_schema(&schema_for!(ClaimedBitmapResponse), &out_dir);
+    export_schema(&schema_for!(UsersResponse), &out_dir);
+    export_schema(&schema_for!(PendingSweepResponse), &out_dir);
+    export_schema(&schema_for!(ErrorCode), &out_dir);
 
     export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
@@ -0,0 +1,174 @@
+//! Builds the Merkle Tree of an airdrop and verifies Merkle proofs, with the same hashing rules as the airdrop contract
+//!
+//! USAGE:
+//!     airdrop-merkle-tree build --input <entries.csv|entries.json> --output <claims.json> [OPTIONS]
+//!     airdrop-merkle-tree verify --root <root> --address <address> --amount <amount> --proof <p1,p2,..> [OPTIONS]
+//!
+//! OPTIONS:
+//!     --scheme <legacy|v1>     Hashing scheme the merkle root is added with (default: v1)
+//!     --chain-id <chain_id>    Chain id the airdrop contract is deployed on (required by v1)
+//!     --contract <address>     Address of the airdrop contract (required by v1)
+//!     --root-index <index>     Merkle root identifier written to the claims (default: 0)
+//!     --indexed <true|false>   Leaves carry the position of their entry, requires the v1 scheme (default: false)
+//!     --leaf-index <index>     Index of the leaf to verify (indexed merkle roots only)
+
+use cosmwasm_std::{to_vec, StdError, StdResult, Uint128};
+use mars_airdrop::crypto::{indexed_leaf_hash, leaf_hash, verify_merkle_proof};
+use mars_airdrop::merkle_tree::{parse_csv_entries, parse_json_entries, MerkleTree};
+use mars_periphery::airdrop::HashingScheme;
+use std::collections::HashMap;
+use std::{env, fs, process};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.split_first() {
+        Some((command, options)) if command == "build" => parse_options(options).and_then(build),
+        Some((command, options)) if command == "verify" => parse_options(options).and_then(verify),
+        _ => Err(StdError::generic_err(
+            "Usage: airdrop-merkle-tree <build|verify> [OPTIONS]",
+        )),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+/// @dev Builds the Merkle Tree from the input entries and writes the claims (with Merkle proofs) to the output file
+fn build(options: HashMap<String, String>) -> StdResult<()> {
+    let input = required_option(&options, "input")?;
+    let output = required_option(&options, "output")?;
+    let root_index = match options.get("root-index") {
+        Some(root_index) => root_index
+            .parse::<u32>()
+            .map_err(|_| StdError::generic_err("Invalid root index"))?,
+        None => 0u32,
+    };
+
+    let data = fs::read(input).map_err(|err| StdError::generic_err(err.to_string()))?;
+    let entries = if input.ends_with(".json") {
+        parse_json_entries(&data)?
+    } else {
+        parse_csv_entries(&String::from_utf8_lossy(&data))?
+    };
+
+    let (hashing_scheme, chain_id, contract_address) = tree_options(&options)?;
+    let tree = if is_indexed(&options)? {
+        MerkleTree::new_indexed(chain_id, contract_address, &entries)?
+    } else {
+        MerkleTree::new(hashing_scheme, chain_id, contract_address, &entries)?
+    };
+    let claims = tree.claims(&entries, root_index)?;
+
+    fs::write(output, to_vec(&claims)?).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    println!("Merkle Root : {}", tree.root());
+    println!("Claims : {} written to {}", claims.len(), output);
+    Ok(())
+}
+
+/// @dev Verifies the Merkle proof of an entry against the merkle root
+fn verify(options: HashMap<String, String>) -> StdResult<()> {
+    let merkle_root = required_option(&options, "root")?;
+    let address = required_option(&options, "address")?;
+    let amount = required_option(&options, "amount")?
+        .parse::<u128>()
+        .map_err(|_| StdError::generic_err("Invalid amount"))?;
+    let merkle_proof = required_option(&options, "proof")?
+        .split(',')
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+
+    let (hashing_scheme, chain_id, contract_address) = tree_options(&options)?;
+    let leaf = if is_indexed(&options)? {
+        let leaf_index = required_option(&options, "leaf-index")?
+            .parse::<u64>()
+            .map_err(|_| StdError::generic_err("Invalid leaf index"))?;
+        indexed_leaf_hash(
+            chain_id,
+            contract_address,
+            leaf_index,
+            address,
+            Uint128::new(amount),
+        )
+    } else {
+        leaf_hash(
+            &hashing_scheme,
+            chain_id,
+            contract_address,
+            address,
+            Uint128::new(amount),
+        )
+    };
+    if !verify_merkle_proof(&hashing_scheme, leaf, merkle_proof, merkle_root)
+        .map_err(|err| StdError::generic_err(err.to_string()))?
+    {
+        return Err(StdError::generic_err("Incorrect Merkle Proof"));
+    }
+
+    println!("Valid Merkle Proof");
+    Ok(())
+}
+
+/// @dev Parses `--key value` pairs
+fn parse_options(args: &[String]) -> StdResult<HashMap<String, String>> {
+    let mut options = HashMap::new();
+    for pair in args.chunks(2) {
+        match pair {
+            [key, value] if key.starts_with("--") => {
+                options.insert(key.trim_start_matches("--").to_string(), value.clone());
+            }
+            _ => return Err(StdError::generic_err(format!("Invalid option {}", pair[0]))),
+        }
+    }
+    Ok(options)
+}
+
+/// @dev Returns the hashing scheme, chain id and contract address the Merkle Tree is built with
+fn tree_options(options: &HashMap<String, String>) -> StdResult<(HashingScheme, &str, &str)> {
+    let hashing_scheme = match options.get("scheme").map(String::as_str) {
+        Some("legacy") => HashingScheme::Legacy,
+        None | Some("v1") => HashingScheme::V1,
+        Some(scheme) => {
+            return Err(StdError::generic_err(format!(
+                "Unknown hashing scheme {}",
+                scheme
+            )))
+        }
+    };
+
+    match hashing_scheme {
+        HashingScheme::Legacy => Ok((hashing_scheme, "", "")),
+        HashingScheme::V1 => Ok((
+            hashing_scheme,
+            required_option(options, "chain-id")?,
+            required_option(options, "contract")?,
+        )),
+    }
+}
+
+/// @dev Returns true if the Merkle Tree is indexed. Indexed Merkle Trees are built via the v1 scheme
+fn is_indexed(options: &HashMap<String, String>) -> StdResult<bool> {
+    let is_indexed = match options.get("indexed").map(String::as_str) {
+        None | Some("false") => false,
+        Some("true") => true,
+        Some(_) => return Err(StdError::generic_err("Invalid option --indexed")),
+    };
+
+    if is_indexed && options.get("scheme").map(String::as_str) == Some("legacy") {
+        return Err(StdError::generic_err(
+            "Indexed merkle roots require the V1 hashing scheme",
+        ));
+    }
+    Ok(is_indexed)
+}
+
+fn required_option<'a>(options: &'a HashMap<String, String>, key: &str) -> StdResult<&'a str> {
+    options
+        .get(key)
+        .map(String::as_str)
+        .ok_or_else(|| StdError::generic_err(format!("Missing option --{}", key)))
+}
@@ -1,22 +1,37 @@
-use crate::crypto::verify_claim;
-use crate::state::{Config, State, CONFIG, STATE, USERS};
+use crate::crypto::{
+    evm_signed_msg_hash, indexed_leaf_hash, leaf_hash, recover_evm_address, verify_merkle_proof,
+};
+use crate::error::ContractError;
+use crate::state::{
+    Config, MerkleRoot, PendingSweep, State, UserInfo, CLAIMED_BITMAPS, CLAIMS, CONFIG,
+    DEFAULT_SWEEP_DELAY, EVM_CLAIMS, LEGACY_CONFIG, MERKLE_ROOTS, PENDING_SWEEP, STATE, USERS,
+};
 use cosmwasm_std::{
-    attr, entry_point, from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, Uint128,
+    attr, entry_point, from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::{Bound, U32Key, U64Key};
 use mars_periphery::airdrop::{
-    ClaimResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
-    StateResponse, UserInfoResponse,
+    ClaimResponse, ClaimedBitmapResponse, ClaimedBitmapWordResponse, ConfigResponse, Cw20HookMsg,
+    EarlyExitPenalty, ExecuteMsg, HashingScheme, InstantiateMsg, MerkleRootResponse,
+    MerkleRootsResponse, MigrateMsg, PendingSweepResponse, QueryMsg, StateResponse, SweepRecipient,
+    UserInfoResponse, UserResponse, UsersResponse, VestingSchedule,
 };
 use mars_periphery::auction::Cw20HookMsg::DepositMarsTokens;
+use mars_periphery::auction::ExecuteMsg::ReturnAirdropDelegations;
 use mars_periphery::helpers::{build_send_cw20_token_msg, build_transfer_cw20_token_msg};
+use std::convert::TryInto;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "mars_airdrop";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 //----------------------------------------------------------------------------------------
 // Entry points
 //----------------------------------------------------------------------------------------
@@ -27,16 +42,29 @@ pub fn instantiate(
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     let from_timestamp = msg
         .from_timestamp
         .unwrap_or_else(|| env.block.time.seconds());
 
     if msg.to_timestamp <= from_timestamp {
-        return Err(StdError::generic_err(
-            "Invalid airdrop claim window closure timestamp",
-        ));
+        return Err(ContractError::InvalidClaimWindow {});
+    }
+
+    // CHECK :: vesting schedule needs to be valid
+    if let Some(vesting) = &msg.vesting {
+        validate_vesting_schedule(vesting)?;
+    }
+
+    let early_exit_penalty = msg
+        .early_exit_penalty
+        .map(|early_exit_penalty| validate_early_exit_penalty(deps.api, early_exit_penalty))
+        .transpose()?;
+
+    let sweep_delay = msg.sweep_delay.unwrap_or(DEFAULT_SWEEP_DELAY);
+    if sweep_delay == 0u64 {
+        return Err(ContractError::InvalidSweepDelay {});
     }
 
     let owner = if let Some(owner) = msg.owner {
@@ -48,19 +76,51 @@ pub fn instantiate(
     let config = Config {
         owner,
         mars_token_address: deps.api.addr_validate(&msg.mars_token_address)?,
-        merkle_roots: msg.merkle_roots.unwrap_or_default(),
         from_timestamp,
         to_timestamp: msg.to_timestamp,
         auction_contract_address: None,
         are_claims_enabled: false,
+        claims_enabled_timestamp: 0u64,
+        vesting: msg.vesting,
+        early_exit_penalty,
+        auction_deadline: None,
+        is_auction_failed: false,
+        sweep_delay,
     };
 
-    let state = State {
+    let mut state = State {
         total_airdrop_size: Uint128::zero(),
         total_delegated_amount: Uint128::zero(),
         unclaimed_tokens: Uint128::zero(),
+        merkle_roots_count: 0u32,
+        legacy_merkle_roots_count: 0u32,
+        total_penalty_amount: Uint128::zero(),
+        recycled_penalty_amount: Uint128::zero(),
+        claimants_count: 0u64,
+        withdrawers_count: 0u64,
     };
 
+    // Merkle roots provided at instantiation are only bound by the contract's unclaimed tokens.
+    // They default to the Legacy hashing scheme, as V1 leaves commit to the yet unknown contract address
+    let hashing_scheme = msg.hashing_scheme.unwrap_or(HashingScheme::Legacy);
+    for merkle_root in msg.merkle_roots.unwrap_or_default() {
+        validate_merkle_root(&merkle_root)?;
+        MERKLE_ROOTS.save(
+            deps.storage,
+            U32Key::new(state.merkle_roots_count),
+            &MerkleRoot {
+                merkle_root,
+                budget: None,
+                claimed_amount: Uint128::zero(),
+                expiry_timestamp: None,
+                is_disabled: false,
+                hashing_scheme,
+                is_indexed: false,
+            },
+        )?;
+        state.merkle_roots_count += 1;
+    }
+
     CONFIG.save(deps.storage, &config)?;
     STATE.save(deps.storage, &state)?;
 
@@ -73,38 +133,106 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::UpdateConfig {
             owner,
             auction_contract_address,
-            merkle_roots,
             from_timestamp,
             to_timestamp,
+            early_exit_penalty,
+            auction_deadline,
         } => handle_update_config(
             deps,
             env,
             info,
             owner,
             auction_contract_address,
-            merkle_roots,
             from_timestamp,
             to_timestamp,
+            early_exit_penalty,
+            auction_deadline,
+        ),
+        ExecuteMsg::AddMerkleRoot {
+            merkle_root,
+            budget,
+            expiry_timestamp,
+            hashing_scheme,
+            is_indexed,
+        } => handle_add_merkle_root(
+            deps,
+            env,
+            info,
+            merkle_root,
+            budget,
+            expiry_timestamp,
+            hashing_scheme.unwrap_or_default(),
+            is_indexed.unwrap_or_default(),
         ),
+        ExecuteMsg::DisableMerkleRoot { root_index } => {
+            handle_disable_merkle_root(deps, info, root_index)
+        }
         ExecuteMsg::Claim {
             claim_amount,
             merkle_proof,
             root_index,
-        } => handle_claim(deps, env, info, claim_amount, merkle_proof, root_index),
+            leaf_index,
+        } => handle_claim(
+            deps,
+            env,
+            info,
+            claim_amount,
+            merkle_proof,
+            root_index,
+            leaf_index,
+        ),
+        ExecuteMsg::ClaimAndDelegate {
+            claim_amount,
+            merkle_proof,
+            root_index,
+            leaf_index,
+            delegate_amount,
+        } => handle_claim_and_delegate(
+            deps,
+            env,
+            info,
+            claim_amount,
+            merkle_proof,
+            root_index,
+            leaf_index,
+            delegate_amount,
+        ),
+        ExecuteMsg::EvmClaim {
+            eth_address,
+            claim_amount,
+            merkle_proof,
+            root_index,
+            signature,
+            signed_msg_hash,
+        } => handle_evm_claim(
+            deps,
+            env,
+            info,
+            eth_address,
+            claim_amount,
+            merkle_proof,
+            root_index,
+            signature,
+            signed_msg_hash,
+        ),
         ExecuteMsg::DelegateMarsToBootstrapAuction { amount_to_delegate } => {
             handle_delegate_mars_to_bootstrap_auction(deps, env, info, amount_to_delegate)
         }
-        ExecuteMsg::EnableClaims {} => handle_enable_claims(deps, info),
+        ExecuteMsg::EnableClaims {} => handle_enable_claims(deps, env, info),
+        ExecuteMsg::MarkAuctionFailed {} => handle_mark_auction_failed(deps, env, info),
         ExecuteMsg::WithdrawAirdropReward {} => handle_withdraw_airdrop_rewards(deps, env, info),
-        ExecuteMsg::TransferUnclaimedTokens { recipient, amount } => {
-            handle_transfer_unclaimed_tokens(deps, env, info, recipient, amount)
+        ExecuteMsg::WithdrawVested {} => handle_withdraw_vested(deps, env, info),
+        ExecuteMsg::ProposeSweep { recipients } => {
+            handle_propose_sweep(deps, env, info, recipients)
         }
+        ExecuteMsg::CancelSweep {} => handle_cancel_sweep(deps, info),
+        ExecuteMsg::ExecuteSweep {} => handle_execute_sweep(deps, env),
     }
 }
 
@@ -113,37 +241,99 @@ pub fn receive_cw20(
     _env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     if info.sender != config.mars_token_address {
-        return Err(StdError::generic_err("Only mars tokens are received!"));
+        return Err(ContractError::InvalidToken {});
     }
 
     // CHECK ::: Amount needs to be valid
     if cw20_msg.amount.is_zero() {
-        return Err(StdError::generic_err("Amount must be greater than 0"));
+        return Err(ContractError::ZeroAmount {});
     }
 
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::IncreaseMarsIncentives {} => {
             handle_increase_mars_incentives(deps, cw20_msg.amount)
         }
+        Cw20HookMsg::ReturnDelegatedMars {} => {
+            handle_return_delegated_mars(deps, config, cw20_msg.sender, cw20_msg.amount)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
-        QueryMsg::HasUserClaimed { address } => to_binary(&query_user_claimed(deps, address)?),
-        QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, address)?),
+        QueryMsg::HasUserClaimed {
+            address,
+            root_index,
+            leaf_index,
+        } => to_binary(&query_user_claimed(deps, address, root_index, leaf_index)?),
+        QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, env, address)?),
+        QueryMsg::MerkleRoots { start_after, limit } => {
+            to_binary(&query_merkle_roots(deps, start_after, limit)?)
+        }
+        QueryMsg::ClaimedBitmap {
+            root_index,
+            start_after,
+            limit,
+        } => to_binary(&query_claimed_bitmap(deps, root_index, start_after, limit)?),
+        QueryMsg::AllUsers { start_after, limit } => {
+            to_binary(&query_users(deps, env, start_after, limit, |_, _| true)?)
+        }
+        QueryMsg::Delegators { start_after, limit } => to_binary(&query_users(
+            deps,
+            env,
+            start_after,
+            limit,
+            |_, user_info| !user_info.delegated_amount.is_zero(),
+        )?),
+        QueryMsg::UnwithdrawnUsers { start_after, limit } => to_binary(&query_users(
+            deps,
+            env,
+            start_after,
+            limit,
+            |config, user_info| user_info.withdrawn_amount < withdrawable_amount(config, user_info),
+        )?),
+        QueryMsg::PendingSweep {} => to_binary(&query_pending_sweep(deps)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // MIGRATE :: Merkle roots stored in the config moved to MERKLE_ROOTS, without budget or expiry
+    let legacy_config = LEGACY_CONFIG.load(deps.storage)?;
+    if !legacy_config.merkle_roots.is_empty() {
+        let config = CONFIG.load(deps.storage)?;
+        let mut state = STATE.load(deps.storage)?;
+
+        for merkle_root in legacy_config.merkle_roots {
+            MERKLE_ROOTS.save(
+                deps.storage,
+                U32Key::new(state.merkle_roots_count),
+                &MerkleRoot {
+                    merkle_root,
+                    budget: None,
+                    claimed_amount: Uint128::zero(),
+                    expiry_timestamp: None,
+                    is_disabled: false,
+                    hashing_scheme: HashingScheme::Legacy,
+                    is_indexed: false,
+                },
+            )?;
+            state.merkle_roots_count += 1;
+        }
+        state.legacy_merkle_roots_count = state.merkle_roots_count;
+
+        // Config is saved without the merkle roots
+        CONFIG.save(deps.storage, &config)?;
+        STATE.save(deps.storage, &state)?;
+    }
+
     Ok(Response::default())
 }
 
@@ -159,16 +349,17 @@ pub fn handle_update_config(
     info: MessageInfo,
     owner: Option<String>,
     auction_contract_address: Option<String>,
-    merkle_roots: Option<Vec<String>>,
     from_timestamp: Option<u64>,
     to_timestamp: Option<u64>,
-) -> StdResult<Response> {
+    early_exit_penalty: Option<EarlyExitPenalty>,
+    auction_deadline: Option<u64>,
+) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     let mut attributes = vec![attr("action", "Airdrop::ExecuteMsg::UpdateConfig")];
 
     // CHECK :: ONLY OWNER CAN CALL THIS FUNCTION
     if info.sender != config.owner {
-        return Err(StdError::generic_err("Only owner can update configuration"));
+        return Err(ContractError::Unauthorized {});
     }
 
     if let Some(owner) = owner {
@@ -181,7 +372,7 @@ pub fn handle_update_config(
             Some(_) => {
                 let state = STATE.load(deps.storage)?;
                 if state.total_delegated_amount > Uint128::zero() {
-                    return Err(StdError::generic_err("Auction delegations already live"));
+                    return Err(ContractError::AuctionDelegationsLive {});
                 }
                 config.auction_contract_address =
                     Some(deps.api.addr_validate(&auction_contract_address)?);
@@ -195,15 +386,9 @@ pub fn handle_update_config(
         }
     }
 
-    if let Some(merkle_roots) = merkle_roots {
-        config.merkle_roots = merkle_roots
-    }
-
     if let Some(from_timestamp) = from_timestamp {
         if env.block.time.seconds() >= config.from_timestamp {
-            return Err(StdError::generic_err(
-                "from_timestamp can't be changed after window starts",
-            ));
+            return Err(ContractError::ClaimWindowStarted {});
         }
         config.from_timestamp = from_timestamp;
         attributes.push(attr("new_from_timestamp", from_timestamp.to_string()))
@@ -211,27 +396,156 @@ pub fn handle_update_config(
 
     if let Some(to_timestamp) = to_timestamp {
         if env.block.time.seconds() >= config.from_timestamp && to_timestamp < config.to_timestamp {
-            return Err(StdError::generic_err(
-                "When window starts to_timestamp can only be increased",
-            ));
+            return Err(ContractError::ClaimWindowDecreased {});
         }
         config.to_timestamp = to_timestamp;
         attributes.push(attr("new_to_timestamp", to_timestamp.to_string()))
     }
 
     if config.to_timestamp <= config.from_timestamp {
-        return Err(StdError::generic_err("Invalid airdrop claim window"));
+        return Err(ContractError::InvalidClaimWindow {});
+    }
+
+    if let Some(early_exit_penalty) = early_exit_penalty {
+        if config.are_claims_enabled {
+            return Err(ContractError::EarlyExitPenaltyLocked {});
+        }
+        let early_exit_penalty = validate_early_exit_penalty(deps.api, early_exit_penalty)?;
+        attributes.push(attr(
+            "new_early_exit_penalty_rate",
+            early_exit_penalty.penalty_rate.to_string(),
+        ));
+        config.early_exit_penalty = Some(early_exit_penalty);
+    }
+
+    if let Some(auction_deadline) = auction_deadline {
+        if config.are_claims_enabled || config.is_auction_failed {
+            return Err(ContractError::AuctionConcluded {});
+        }
+        if auction_deadline <= env.block.time.seconds() {
+            return Err(ContractError::InvalidAuctionDeadline {});
+        }
+        // Delegators rely on the deadline once delegations are live, hence it can only be postponed
+        if let Some(current_deadline) = config.auction_deadline {
+            let state = STATE.load(deps.storage)?;
+            if state.total_delegated_amount > Uint128::zero() && auction_deadline < current_deadline
+            {
+                return Err(ContractError::AuctionDeadlineDecreased {});
+            }
+        }
+        config.auction_deadline = Some(auction_deadline);
+        attributes.push(attr("new_auction_deadline", auction_deadline.to_string()))
     }
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// @dev Admin function to add a merkle root. Claims against the merkle root are bound by its own budget and expiry
+/// @param merkle_root : Merkle root used to verify if a user is eligible for the airdrop
+/// @param budget : Max MARS tokens which can be claimed against the merkle root
+/// @param expiry_timestamp : Timestamp post which claims against the merkle root are not allowed
+/// @param hashing_scheme : Hashing scheme used to build the merkle tree
+/// @param is_indexed : Boolean value indicating if the leaves carry an index (V1 hashing scheme only)
+pub fn handle_add_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    budget: Uint128,
+    expiry_timestamp: Option<u64>,
+    hashing_scheme: HashingScheme,
+    is_indexed: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: ONLY OWNER CAN CALL THIS FUNCTION
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_merkle_root(&merkle_root)?;
+
+    // CHECK :: Budget needs to be valid
+    if budget.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    // CHECK :: Expiry needs to be in the future
+    if let Some(expiry_timestamp) = expiry_timestamp {
+        if expiry_timestamp <= env.block.time.seconds() {
+            return Err(ContractError::InvalidMerkleRootExpiry {});
+        }
+    }
+
+    // CHECK :: Leaves carrying an index are only defined for the V1 hashing scheme
+    if is_indexed && hashing_scheme != HashingScheme::V1 {
+        return Err(ContractError::IndexedMerkleRootNotV1 {});
+    }
+
+    let root_index = state.merkle_roots_count;
+    MERKLE_ROOTS.save(
+        deps.storage,
+        U32Key::new(root_index),
+        &MerkleRoot {
+            merkle_root: merkle_root.clone(),
+            budget: Some(budget),
+            claimed_amount: Uint128::zero(),
+            expiry_timestamp,
+            is_disabled: false,
+            hashing_scheme,
+            is_indexed,
+        },
+    )?;
+    state.merkle_roots_count += 1;
+
+    STATE.save(deps.storage, &state)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Airdrop::ExecuteMsg::AddMerkleRoot"),
+        attr("root_index", root_index.to_string()),
+        attr("merkle_root", merkle_root),
+        attr("budget", budget),
+        attr("is_indexed", is_indexed.to_string()),
+    ]))
+}
+
+/// @dev Admin function to disable a merkle root. Claims already made against the merkle root are not affected
+/// @param root_index : Merkle Tree root identifier
+pub fn handle_disable_merkle_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    root_index: u32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: ONLY OWNER CAN CALL THIS FUNCTION
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut merkle_root = MERKLE_ROOTS
+        .may_load(deps.storage, U32Key::new(root_index))?
+        .ok_or_else(|| ContractError::MerkleRootNotFound {})?;
+
+    if merkle_root.is_disabled {
+        return Err(ContractError::MerkleRootAlreadyDisabled {});
+    }
+
+    merkle_root.is_disabled = true;
+
+    MERKLE_ROOTS.save(deps.storage, U32Key::new(root_index), &merkle_root)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Airdrop::ExecuteMsg::DisableMerkleRoot"),
+        attr("root_index", root_index.to_string()),
+    ]))
+}
+
 /// @dev Facilitates increasing MARS airdrop amount
 pub fn handle_increase_mars_incentives(
     deps: DepsMut,
     amount: Uint128,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     let mut state = STATE.load(deps.storage)?;
     state.total_airdrop_size += amount;
     state.unclaimed_tokens += amount;
@@ -243,32 +557,125 @@ pub fn handle_increase_mars_incentives(
 }
 
 /// @dev Function to enable MARS Claims by users. Called along-with Bootstrap Auction contract's LP Pool provide liquidity tx
-pub fn handle_enable_claims(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+pub fn handle_enable_claims(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
 
     if config.auction_contract_address.is_none() {
-        return Err(StdError::generic_err("Auction contract not set"));
+        return Err(ContractError::AuctionNotSet {});
     }
 
     // CHECK :: ONLY AUCTION CONTRACT CAN CALL THIS FUNCTION
     if info.sender != config.auction_contract_address.clone().unwrap() {
-        return Err(StdError::generic_err("Unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     if config.are_claims_enabled {
-        return Err(StdError::generic_err("Claims already enabled"));
+        return Err(ContractError::ClaimsAlreadyEnabled {});
+    }
+
+    if config.is_auction_failed {
+        return Err(ContractError::AuctionFailed {});
     }
 
     config.are_claims_enabled = true;
+    config.claims_enabled_timestamp = env.block.time.seconds();
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "Airdrop::ExecuteMsg::EnableClaims"))
 }
 
+/// @dev Admin function to mark the bootstrap auction as failed if claims haven't been enabled by the auction deadline.
+/// The auction contract is asked to return the delegated MARS, users can withdraw their full airdrop thereafter
+pub fn handle_mark_auction_failed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK :: ONLY OWNER CAN CALL THIS FUNCTION
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let auction_contract_address = config
+        .auction_contract_address
+        .clone()
+        .ok_or_else(|| ContractError::AuctionNotSet {})?;
+
+    if config.are_claims_enabled {
+        return Err(ContractError::AuctionConcluded {});
+    }
+
+    if config.is_auction_failed {
+        return Err(ContractError::AuctionAlreadyFailed {});
+    }
+
+    // CHECK :: AUCTION CAN ONLY BE MARKED AS FAILED ONCE THE DEADLINE HAS PASSED
+    match config.auction_deadline {
+        Some(auction_deadline) if env.block.time.seconds() > auction_deadline => {}
+        Some(_) => return Err(ContractError::AuctionDeadlineNotReached {}),
+        None => return Err(ContractError::AuctionDeadlineNotSet {}),
+    }
+
+    config.is_auction_failed = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    // COSMOS MSG :: ASK THE AUCTION CONTRACT TO RETURN THE DELEGATED MARS
+    let mut messages = vec![];
+    if !state.total_delegated_amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: auction_contract_address.to_string(),
+            msg: to_binary(&ReturnAirdropDelegations {})?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "Airdrop::ExecuteMsg::MarkAuctionFailed"),
+        attr("delegated_amount", state.total_delegated_amount),
+    ]))
+}
+
+/// @dev Accepts the delegated MARS returned by the bootstrap auction contract once the auction is marked as failed
+/// @param sender : Address which sent the MARS tokens
+/// @param amount : Number of MARS tokens returned
+pub fn handle_return_delegated_mars(
+    deps: DepsMut,
+    config: Config,
+    sender: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // CHECK :: ONLY AUCTION CONTRACT CAN RETURN THE DELEGATED MARS
+    if config.auction_contract_address.map(String::from) != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !config.is_auction_failed {
+        return Err(ContractError::AuctionNotFailed {});
+    }
+
+    // CHECK :: ALL THE DELEGATED MARS NEEDS TO BE RETURNED FOR DELEGATORS TO WITHDRAW THEIR FULL AIRDROP
+    let state = STATE.load(deps.storage)?;
+    if amount != state.total_delegated_amount {
+        return Err(ContractError::ReturnedAmountMismatch {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "Airdrop::Cw20HookMsg::ReturnDelegatedMars")
+        .add_attribute("amount", amount))
+}
+
 /// @dev Executes an airdrop claim for a Terra User
 /// @param claim_amount : Airdrop to be claimed by the user
 /// @param merkle_proof : Array of hashes to prove the input is a leaf of the Merkle Tree
 /// @param root_index : Merkle Tree root identifier to be used for verification
+/// @param leaf_index : Index of the leaf, required by indexed merkle roots
 pub fn handle_claim(
     deps: DepsMut,
     env: Env,
@@ -276,7 +683,8 @@ pub fn handle_claim(
     claim_amount: Uint128,
     merkle_proof: Vec<String>,
     root_index: u32,
-) -> Result<Response, StdError> {
+    leaf_index: Option<u64>,
+) -> Result<Response, ContractError> {
     let recipient = info.sender;
 
     let config = CONFIG.load(deps.storage)?;
@@ -284,57 +692,227 @@ pub fn handle_claim(
 
     // CHECK :: IS AIRDROP CLAIM WINDOW OPEN ?
     if config.from_timestamp > env.block.time.seconds() {
-        return Err(StdError::generic_err("Claim not allowed"));
+        return Err(ContractError::ClaimNotAllowed {});
     }
 
     // CHECK :: IS AIRDROP CLAIM WINDOW OPEN ?
     if config.to_timestamp < env.block.time.seconds() {
-        return Err(StdError::generic_err("Claim period has concluded"));
+        return Err(ContractError::ClaimPeriodConcluded {});
     }
 
-    let merkle_root = config.merkle_roots.get(root_index as usize);
-    if merkle_root.is_none() {
-        return Err(StdError::generic_err("Incorrect Merkle Root Index"));
-    }
+    let mut merkle_root =
+        load_merkle_root_for_claim(deps.storage, root_index, env.block.time.seconds())?;
 
-    if !verify_claim(&recipient, claim_amount, merkle_proof, merkle_root.unwrap()) {
-        return Err(StdError::generic_err("Incorrect Merkle Proof"));
+    let leaf = merkle_leaf(
+        &env,
+        &merkle_root,
+        recipient.as_str(),
+        claim_amount,
+        leaf_index,
+    )?;
+    if !verify_merkle_proof(
+        &merkle_root.hashing_scheme,
+        leaf,
+        merkle_proof,
+        &merkle_root.merkle_root,
+    )? {
+        return Err(ContractError::IncorrectMerkleProof {});
     }
 
     let mut user_info = USERS.load(deps.storage, &recipient).unwrap_or_default();
 
-    // Check if addr has already claimed the tokens
-    if !user_info.claimed_amount.is_zero() {
-        return Err(StdError::generic_err("Already claimed"));
+    // Check if the leaf / addr has already claimed the tokens against the merkle root
+    if let Some(leaf_index) = leaf_index {
+        if !set_leaf_claimed(deps.storage, root_index, leaf_index)? {
+            return Err(ContractError::AlreadyClaimed {});
+        }
+    } else {
+        if has_claimed_against_root(deps.storage, &state, &user_info, root_index, &recipient)? {
+            return Err(ContractError::AlreadyClaimed {});
+        }
+        CLAIMS.save(deps.storage, (U32Key::new(root_index), &recipient), &true)?;
     }
 
     let mut messages = vec![];
 
     // check is sufficient MARS available
     if state.unclaimed_tokens < claim_amount {
-        return Err(StdError::generic_err("Insufficient MARS available"));
+        return Err(ContractError::InsufficientMars {});
     }
 
-    // TRANSFER MARS IF CLAIMS ARE ALLOWED (i.e LP bootstrap auction has concluded)
-    if config.are_claims_enabled {
-        messages.push(build_transfer_cw20_token_msg(
+    // CHECK :: CLAIM SHOULD NOT EXCEED THE MERKLE ROOT'S BUDGET
+    claim_against_merkle_root(&mut merkle_root, claim_amount)?;
+
+    state.unclaimed_tokens -= claim_amount;
+
+    // Indexed claims made post the bootstrap auction are transferred at once, without creating the user's position
+    let tokens_to_transfer =
+        if merkle_root.is_indexed && config.are_claims_enabled && config.vesting.is_none() {
+            claim_amount
+        } else {
+            // Update amounts
+            credit_user(&config, &mut state, &mut user_info, claim_amount);
+            if merkle_root.is_indexed {
+                user_info.indexed_claimed_amount += claim_amount;
+            } else {
+                user_info.root_claimed_amount += claim_amount;
+            }
+
+            // TRANSFER MARS IF CLAIMS ARE ALLOWED (i.e LP bootstrap auction has concluded)
+            let tokens_to_transfer = if config.are_claims_enabled {
+                withdraw_vested_tokens(
+                    &config,
+                    &mut state,
+                    &mut user_info,
+                    env.block.time.seconds(),
+                )
+            } else {
+                Uint128::zero()
+            };
+
+            USERS.save(deps.storage, &recipient, &user_info)?;
+            tokens_to_transfer
+        };
+
+    if !tokens_to_transfer.is_zero() {
+        let (withdrawal_msgs, _) = build_withdrawal_msgs(
+            &config,
+            &mut state,
             recipient.clone(),
-            config.mars_token_address.to_string(),
-            claim_amount,
-        )?);
+            tokens_to_transfer,
+            env.block.time.seconds(),
+        )?;
+        messages.extend(withdrawal_msgs);
+    }
 
-        user_info.tokens_withdrawn = true;
+    MERKLE_ROOTS.save(deps.storage, U32Key::new(root_index), &merkle_root)?;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "Airdrop::ExecuteMsg::Claim"),
+        attr("addr", recipient),
+        attr("airdrop", claim_amount),
+    ]))
+}
+
+/// @dev Executes an airdrop claim on behalf of an EVM address. MARS is credited to the terra address signed by the EVM address
+/// @param eth_address : EVM address (lower-case, without the '0x' prefix) eligible for the airdrop
+/// @param claim_amount : Airdrop to be claimed
+/// @param merkle_proof : Array of hashes to prove the input is a leaf of the Merkle Tree
+/// @param root_index : Merkle Tree root identifier to be used for verification
+/// @param signature : Signature of the sender's terra address, generated by the EVM address
+/// @param signed_msg_hash : Hash of the message signed by the EVM address
+pub fn handle_evm_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    eth_address: String,
+    claim_amount: Uint128,
+    merkle_proof: Vec<String>,
+    root_index: u32,
+    signature: String,
+    signed_msg_hash: String,
+) -> Result<Response, ContractError> {
+    let recipient = info.sender;
+    let eth_address = eth_address.trim_start_matches("0x").to_lowercase();
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: IS AIRDROP CLAIM WINDOW OPEN ?
+    if config.from_timestamp > env.block.time.seconds() {
+        return Err(ContractError::ClaimNotAllowed {});
     }
 
+    // CHECK :: IS AIRDROP CLAIM WINDOW OPEN ?
+    if config.to_timestamp < env.block.time.seconds() {
+        return Err(ContractError::ClaimPeriodConcluded {});
+    }
+
+    let mut merkle_root =
+        load_merkle_root_for_claim(deps.storage, root_index, env.block.time.seconds())?;
+
+    let leaf = merkle_leaf(&env, &merkle_root, &eth_address, claim_amount, None)?;
+    if !verify_merkle_proof(
+        &merkle_root.hashing_scheme,
+        leaf,
+        merkle_proof,
+        &merkle_root.merkle_root,
+    )? {
+        return Err(ContractError::IncorrectMerkleProof {});
+    }
+
+    // CHECK :: SIGNED MESSAGE SHOULD BE THE RECIPIENT'S TERRA ADDRESS
+    let msg_hash = hex::decode(signed_msg_hash.trim_start_matches("0x"))
+        .map_err(|_| ContractError::InvalidSignedMsgHash {})?;
+    if msg_hash != evm_signed_msg_hash(recipient.as_str()) {
+        return Err(ContractError::SignedMsgMismatch {});
+    }
+
+    // CHECK :: SIGNATURE SHOULD BE GENERATED BY THE EVM ADDRESS
+    if recover_evm_address(deps.api, &msg_hash, &signature)? != eth_address {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    // Check if the EVM address has already claimed the tokens against the merkle root
+    if EVM_CLAIMS.has(
+        deps.storage,
+        (U32Key::new(root_index), eth_address.as_str()),
+    ) {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    // check is sufficient MARS available
+    if state.unclaimed_tokens < claim_amount {
+        return Err(ContractError::InsufficientMars {});
+    }
+
+    // CHECK :: CLAIM SHOULD NOT EXCEED THE MERKLE ROOT'S BUDGET
+    claim_against_merkle_root(&mut merkle_root, claim_amount)?;
+
+    let mut messages = vec![];
+
+    let mut user_info = USERS
+        .may_load(deps.storage, &recipient)?
+        .unwrap_or_default();
+
     // Update amounts
     state.unclaimed_tokens -= claim_amount;
-    user_info.claimed_amount = claim_amount;
+    credit_user(&config, &mut state, &mut user_info, claim_amount);
+    user_info.evm_claimed_amount += claim_amount;
+
+    // TRANSFER MARS IF CLAIMS ARE ALLOWED (i.e LP bootstrap auction has concluded)
+    if config.are_claims_enabled {
+        let tokens_to_transfer = withdraw_vested_tokens(
+            &config,
+            &mut state,
+            &mut user_info,
+            env.block.time.seconds(),
+        );
+        if !tokens_to_transfer.is_zero() {
+            let (withdrawal_msgs, _) = build_withdrawal_msgs(
+                &config,
+                &mut state,
+                recipient.clone(),
+                tokens_to_transfer,
+                env.block.time.seconds(),
+            )?;
+            messages.extend(withdrawal_msgs);
+        }
+    }
 
     USERS.save(deps.storage, &recipient, &user_info)?;
+    EVM_CLAIMS.save(
+        deps.storage,
+        (U32Key::new(root_index), eth_address.as_str()),
+        &claim_amount,
+    )?;
+    MERKLE_ROOTS.save(deps.storage, U32Key::new(root_index), &merkle_root)?;
     STATE.save(deps.storage, &state)?;
 
     Ok(Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "Airdrop::ExecuteMsg::Claim"),
+        attr("action", "Airdrop::ExecuteMsg::EvmClaim"),
+        attr("eth_address", eth_address),
         attr("addr", recipient),
         attr("airdrop", claim_amount),
     ]))
@@ -347,27 +925,37 @@ pub fn handle_delegate_mars_to_bootstrap_auction(
     _env: Env,
     info: MessageInfo,
     amount_to_delegate: Uint128,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     if config.auction_contract_address.is_none() {
-        return Err(StdError::generic_err("Auction contract not set"));
+        return Err(ContractError::AuctionNotSet {});
     }
 
     // CHECK :: HAS THE BOOTSTRAP AUCTION CONCLUDED ?
     if config.are_claims_enabled {
-        return Err(StdError::generic_err("LP bootstrap auction has concluded"));
+        return Err(ContractError::AuctionConcluded {});
+    }
+    if config.is_auction_failed {
+        return Err(ContractError::AuctionFailed {});
+    }
+
+    // CHECK :: AMOUNT TO DELEGATE NEEDS TO BE VALID
+    if amount_to_delegate.is_zero() {
+        return Err(ContractError::ZeroAmount {});
     }
 
     let mut state = STATE.load(deps.storage)?;
-    let mut user_info = USERS.load(deps.storage, &info.sender)?;
+    let mut user_info = USERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NoAirdropClaimed {})?;
 
     state.total_delegated_amount += amount_to_delegate;
     user_info.delegated_amount += amount_to_delegate;
 
     // CHECK :: TOKENS BEING DELEGATED SHOULD NOT EXCEED USER'S CLAIMABLE AIRDROP AMOUNT
     if user_info.delegated_amount > user_info.claimed_amount {
-        return Err(StdError::generic_err("Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance"));
+        return Err(ContractError::DelegationExceedsBalance {});
     }
 
     // COSMOS MSG :: DELEGATE MARS TOKENS TO LP BOOTSTRAP AUCTION CONTRACT
@@ -398,101 +986,304 @@ pub fn handle_delegate_mars_to_bootstrap_auction(
         ]))
 }
 
-/// @dev Function to allow users to withdraw their undelegated MARS Tokens
+/// @dev Executes an airdrop claim for a Terra User and delegates part of the claimed MARS to the bootstrap auction contract
+/// @param claim_amount : Airdrop to be claimed by the user
+/// @param merkle_proof : Array of hashes to prove the input is a leaf of the Merkle Tree
+/// @param root_index : Merkle Tree root identifier to be used for verification
+/// @param leaf_index : Index of the leaf, required by indexed merkle roots
+/// @param delegate_amount : Number of claimed MARS tokens to be delegated to the bootstrap auction contract
+pub fn handle_claim_and_delegate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claim_amount: Uint128,
+    merkle_proof: Vec<String>,
+    root_index: u32,
+    leaf_index: Option<u64>,
+    delegate_amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: CLAIMED MARS CAN ONLY BE DELEGATED WHILE THE BOOTSTRAP AUCTION IS IN PROGRESS
+    if config.auction_contract_address.is_none() {
+        return Err(ContractError::AuctionNotSet {});
+    }
+    if config.are_claims_enabled {
+        return Err(ContractError::AuctionConcluded {});
+    }
+    if config.is_auction_failed {
+        return Err(ContractError::AuctionFailed {});
+    }
+
+    // CHECK :: TOKENS BEING DELEGATED SHOULD NOT EXCEED THE AIRDROP BEING CLAIMED
+    if delegate_amount > claim_amount {
+        return Err(ContractError::DelegationExceedsClaim {});
+    }
+
+    // Claims are not enabled, hence the claimed MARS is credited to the user's position
+    let claim_response = handle_claim(
+        deps.branch(),
+        env.clone(),
+        info.clone(),
+        claim_amount,
+        merkle_proof,
+        root_index,
+        leaf_index,
+    )?;
+    let delegate_response =
+        handle_delegate_mars_to_bootstrap_auction(deps, env, info.clone(), delegate_amount)?;
+
+    Ok(Response::new()
+        .add_submessages(claim_response.messages)
+        .add_submessages(delegate_response.messages)
+        .add_attributes(vec![
+            attr("action", "Airdrop::ExecuteMsg::ClaimAndDelegate"),
+            attr("addr", info.sender.to_string()),
+            attr("airdrop", claim_amount),
+            attr("amount_delegated", delegate_amount),
+        ])
+        .add_attributes(claim_response.attributes))
+}
+
+/// @dev Function to allow users to withdraw their undelegated MARS Tokens. Delegated MARS is withdrawable as well if
+/// the bootstrap auction has failed
 pub fn handle_withdraw_airdrop_rewards(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut user_info = USERS.load(deps.storage, &info.sender)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NoAirdropClaimed {})?;
 
-    // CHECK :: HAS THE BOOTSTRAP AUCTION CONCLUDED ?
-    if !config.are_claims_enabled {
-        return Err(StdError::generic_err(
-            "LP bootstrap auction in progress. Claims not allowed during this period",
-        ));
+    // CHECK :: HAS THE BOOTSTRAP AUCTION CONCLUDED OR FAILED ?
+    if !config.are_claims_enabled && !config.is_auction_failed {
+        return Err(ContractError::AuctionInProgress {});
+    }
+
+    // CHECK :: VESTED AIRDROPS ARE WITHDRAWN VIA WithdrawVested
+    if config.vesting.is_some() {
+        return Err(ContractError::VestingEnabled {});
     }
 
     // CHECK :: HAS USER ALREADY WITHDRAWN THEIR REWARDS ?
     if user_info.tokens_withdrawn {
-        return Err(StdError::generic_err("Tokens have already been withdrawn"));
+        return Err(ContractError::AlreadyWithdrawn {});
     }
 
     // TRANSFER MARS IF CLAIMS ARE ALLOWED (i.e LP bootstrap auction has concluded)
-    user_info.tokens_withdrawn = true;
-
-    let tokens_to_withdraw = user_info.claimed_amount - user_info.delegated_amount;
+    let tokens_to_withdraw = withdraw_vested_tokens(
+        &config,
+        &mut state,
+        &mut user_info,
+        env.block.time.seconds(),
+    );
     if tokens_to_withdraw.is_zero() {
-        return Err(StdError::generic_err("Nothing to withdraw"));
+        return Err(ContractError::NothingToWithdraw {});
     }
 
-    let transfer_msg = build_transfer_cw20_token_msg(
+    let (withdrawal_msgs, penalty_amount) = build_withdrawal_msgs(
+        &config,
+        &mut state,
         info.sender.clone(),
-        config.mars_token_address.to_string(),
         tokens_to_withdraw,
+        env.block.time.seconds(),
     )?;
 
     USERS.save(deps.storage, &info.sender, &user_info)?;
+    STATE.save(deps.storage, &state)?;
 
     Ok(Response::new()
-        .add_message(transfer_msg)
+        .add_messages(withdrawal_msgs)
         .add_attributes(vec![
             attr("action", "Airdrop::ExecuteMsg::WithdrawAirdropRewards"),
             attr("user", info.sender.to_string()),
             attr("claimed_amount", tokens_to_withdraw),
             attr("total_airdrop", user_info.claimed_amount),
+            attr("penalty_amount", penalty_amount),
         ]))
 }
 
-/// @dev Admin function to transfer MARS Tokens to the recipient address
-/// @param recipient Recipient receiving the MARS tokens
-/// @param amount Amount of MARS to be transferred
-pub fn handle_transfer_unclaimed_tokens(
+/// @dev Function to allow users to withdraw their undelegated MARS Tokens which have vested
+pub fn handle_withdraw_vested(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    recipient: String,
-    amount: Uint128,
-) -> Result<Response, StdError> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // CHECK :: HAS THE BOOTSTRAP AUCTION CONCLUDED OR FAILED ?
+    if !config.are_claims_enabled && !config.is_auction_failed {
+        return Err(ContractError::AuctionInProgress {});
+    }
+
+    let tokens_to_withdraw = withdraw_vested_tokens(
+        &config,
+        &mut state,
+        &mut user_info,
+        env.block.time.seconds(),
+    );
+    if tokens_to_withdraw.is_zero() {
+        return Err(ContractError::NoVestedTokens {});
+    }
+
+    let (withdrawal_msgs, penalty_amount) = build_withdrawal_msgs(
+        &config,
+        &mut state,
+        info.sender.clone(),
+        tokens_to_withdraw,
+        env.block.time.seconds(),
+    )?;
+
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_messages(withdrawal_msgs)
+        .add_attributes(vec![
+            attr("action", "Airdrop::ExecuteMsg::WithdrawVested"),
+            attr("user", info.sender.to_string()),
+            attr("withdrawn_amount", tokens_to_withdraw),
+            attr("total_withdrawn", user_info.withdrawn_amount),
+            attr("penalty_amount", penalty_amount),
+        ]))
+}
+
+/// @dev Admin function to propose a sweep of the unclaimed MARS tokens once the claim period is over. The sweep can
+/// be executed by anyone once the sweep delay has passed
+/// @param recipients : Addresses receiving the unclaimed MARS tokens along-with their amounts
+pub fn handle_propose_sweep(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipients: Vec<SweepRecipient>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
 
     // CHECK :: CAN ONLY BE CALLED BY THE OWNER
     if info.sender != config.owner {
-        return Err(StdError::generic_err("Sender not authorized!"));
+        return Err(ContractError::Unauthorized {});
     }
 
     // CHECK :: CAN ONLY BE CALLED AFTER THE CLAIM PERIOD IS OVER
     if config.to_timestamp > env.block.time.seconds() {
-        return Err(StdError::generic_err(format!(
-            "{} seconds left before unclaimed tokens can be transferred",
-            { config.to_timestamp - env.block.time.seconds() }
-        )));
+        return Err(ContractError::ClaimPeriodNotOver {
+            seconds_left: config.to_timestamp - env.block.time.seconds(),
+        });
+    }
+
+    // CHECK :: PENDING SWEEP IS TO BE EXECUTED OR CANCELLED FIRST
+    if PENDING_SWEEP.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::SweepAlreadyPending {});
     }
 
+    if recipients.is_empty() {
+        return Err(ContractError::NoSweepRecipients {});
+    }
+
+    let recipients = recipients
+        .into_iter()
+        .map(|recipient| {
+            if recipient.amount.is_zero() {
+                return Err(ContractError::ZeroAmount {});
+            }
+            Ok(SweepRecipient {
+                address: deps.api.addr_validate(&recipient.address)?.to_string(),
+                amount: recipient.amount,
+            })
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
     // CHECK :: Amount needs to be less than unclaimed_tokens balance
-    if amount > state.unclaimed_tokens {
-        return Err(StdError::generic_err(
-            "Amount cannot exceed unclaimed token balance",
-        ));
+    let total_amount = sweep_amount(&recipients);
+    if total_amount > state.unclaimed_tokens {
+        return Err(ContractError::SweepExceedsUnclaimed {});
     }
 
-    // COSMOS MSG :: TRANSFER MARS TOKENS
-    state.unclaimed_tokens -= amount;
-    let transfer_msg = build_transfer_cw20_token_msg(
-        deps.api.addr_validate(&recipient)?,
-        config.mars_token_address.to_string(),
-        amount,
-    )?;
+    let pending_sweep = PendingSweep {
+        recipients,
+        proposed_timestamp: env.block.time.seconds(),
+        executable_timestamp: env.block.time.seconds() + config.sweep_delay,
+    };
+    PENDING_SWEEP.save(deps.storage, &pending_sweep)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Airdrop::ExecuteMsg::ProposeSweep"),
+        attr("amount", total_amount),
+        attr(
+            "executable_timestamp",
+            pending_sweep.executable_timestamp.to_string(),
+        ),
+    ]))
+}
+
+/// @dev Admin function to cancel the pending sweep of the unclaimed MARS tokens
+pub fn handle_cancel_sweep(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: CAN ONLY BE CALLED BY THE OWNER
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if PENDING_SWEEP.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingSweep {});
+    }
+    PENDING_SWEEP.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "Airdrop::ExecuteMsg::CancelSweep"))
+}
+
+/// @dev Executes the pending sweep of the unclaimed MARS tokens once the sweep delay has passed. Callable by anyone
+pub fn handle_execute_sweep(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let pending_sweep = PENDING_SWEEP
+        .may_load(deps.storage)?
+        .ok_or_else(|| ContractError::NoPendingSweep {})?;
+
+    // CHECK :: CAN ONLY BE EXECUTED ONCE THE SWEEP DELAY HAS PASSED
+    if pending_sweep.executable_timestamp > env.block.time.seconds() {
+        return Err(ContractError::SweepDelayNotOver {
+            seconds_left: pending_sweep.executable_timestamp - env.block.time.seconds(),
+        });
+    }
 
+    // CHECK :: Amount needs to be less than unclaimed_tokens balance
+    let total_amount = sweep_amount(&pending_sweep.recipients);
+    if total_amount > state.unclaimed_tokens {
+        return Err(ContractError::SweepExceedsUnclaimed {});
+    }
+
+    // COSMOS MSGS :: TRANSFER MARS TOKENS
+    state.unclaimed_tokens -= total_amount;
+    let transfer_msgs = pending_sweep
+        .recipients
+        .iter()
+        .map(|recipient| {
+            build_transfer_cw20_token_msg(
+                // Recipient addresses are validated when the sweep is proposed
+                Addr::unchecked(&recipient.address),
+                config.mars_token_address.to_string(),
+                recipient.amount,
+            )
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    PENDING_SWEEP.remove(deps.storage);
     STATE.save(deps.storage, &state)?;
     Ok(Response::new()
-        .add_message(transfer_msg)
+        .add_messages(transfer_msgs)
         .add_attributes(vec![
-            attr("action", "Airdrop::ExecuteMsg::TransferUnclaimedRewards"),
-            attr("recipient", recipient),
-            attr("amount", amount),
+            attr("action", "Airdrop::ExecuteMsg::ExecuteSweep"),
+            attr("amount", total_amount),
         ]))
 }
 
@@ -506,11 +1297,16 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse {
         mars_token_address: config.mars_token_address.to_string(),
         owner: config.owner.to_string(),
-        merkle_roots: config.merkle_roots,
         from_timestamp: config.from_timestamp,
         to_timestamp: config.to_timestamp,
         auction_contract_address: config.auction_contract_address,
         are_claims_allowed: config.are_claims_enabled,
+        claims_enabled_timestamp: config.claims_enabled_timestamp,
+        vesting: config.vesting,
+        early_exit_penalty: config.early_exit_penalty,
+        auction_deadline: config.auction_deadline,
+        is_auction_failed: config.is_auction_failed,
+        sweep_delay: config.sweep_delay,
     })
 }
 
@@ -521,30 +1317,583 @@ fn query_state(deps: Deps) -> StdResult<StateResponse> {
         total_airdrop_size: state.total_airdrop_size,
         total_delegated_amount: state.total_delegated_amount,
         unclaimed_tokens: state.unclaimed_tokens,
+        total_penalty_amount: state.total_penalty_amount,
+        recycled_penalty_amount: state.recycled_penalty_amount,
+        claimants_count: state.claimants_count,
+        withdrawers_count: state.withdrawers_count,
     })
 }
 
 /// @dev Returns details around user's MARS Airdrop claim
-fn query_user_info(deps: Deps, user_address: String) -> StdResult<UserInfoResponse> {
+fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<UserInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let user_address = deps.api.addr_validate(&user_address)?;
     let user_info = USERS
         .may_load(deps.storage, &user_address)?
         .unwrap_or_default();
-    Ok(UserInfoResponse {
-        airdrop_amount: user_info.claimed_amount,
-        delegated_amount: user_info.delegated_amount,
-        tokens_withdrawn: user_info.tokens_withdrawn,
-    })
+
+    Ok(user_info_response(
+        &config,
+        &user_info,
+        env.block.time.seconds(),
+    ))
+}
+
+/// @dev Returns the pending sweep of the unclaimed MARS tokens, if any
+fn query_pending_sweep(deps: Deps) -> StdResult<Option<PendingSweepResponse>> {
+    Ok(PENDING_SWEEP
+        .may_load(deps.storage)?
+        .map(|pending_sweep| PendingSweepResponse {
+            recipients: pending_sweep.recipients,
+            proposed_timestamp: pending_sweep.proposed_timestamp,
+            executable_timestamp: pending_sweep.executable_timestamp,
+        }))
+}
+
+/// @dev Returns the airdrop positions of the users matching the filter, ordered by their address. At most `limit` users
+/// are scanned, the address of the last one is returned as `start_after` of the next query
+/// @param start_after : Address of the user after which the positions are returned
+/// @param limit : Max number of users to be scanned
+/// @param filter : Returns true if the user's airdrop position is to be returned
+fn query_users(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    filter: impl Fn(&Config, &UserInfo) -> bool,
+) -> StdResult<UsersResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|start_after| deps.api.addr_validate(&start_after))
+        .transpose()?
+        .map(|start_after| Bound::exclusive(start_after.as_bytes()));
+
+    let mut users = vec![];
+    let mut last_scanned = None;
+    let mut scanned_count = 0usize;
+    for item in USERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let (k, user_info) = item?;
+        let address =
+            String::from_utf8(k).map_err(|_| StdError::generic_err("Invalid user address"))?;
+        if filter(&config, &user_info) {
+            users.push(UserResponse {
+                address: address.clone(),
+                user_info: user_info_response(&config, &user_info, env.block.time.seconds()),
+            });
+        }
+        last_scanned = Some(address);
+        scanned_count += 1;
+    }
+
+    // Fewer users than the limit were scanned :: no users left
+    let start_after = if scanned_count == limit {
+        last_scanned
+    } else {
+        None
+    };
+
+    Ok(UsersResponse { users, start_after })
 }
 
-/// @dev Returns true if the user has claimed the airdrop [EVM addresses to be provided in lower-case without the '0x' prefix]
-fn query_user_claimed(deps: Deps, address: String) -> StdResult<ClaimResponse> {
+/// @dev Returns true if the user / leaf has claimed the airdrop [EVM addresses to be provided in lower-case without the '0x' prefix]
+/// @param address : Terra or EVM address of the user
+/// @param root_index : Merkle Tree root identifier. Claims against any merkle root are accounted for if not provided along-with `address`
+/// @param leaf_index : Index of the leaf in the indexed Merkle Tree
+fn query_user_claimed(
+    deps: Deps,
+    address: Option<String>,
+    root_index: Option<u32>,
+    leaf_index: Option<u64>,
+) -> StdResult<ClaimResponse> {
+    let (address, root_index) = match (address, root_index, leaf_index) {
+        (_, Some(root_index), Some(leaf_index)) => {
+            return Ok(ClaimResponse {
+                is_claimed: is_leaf_claimed(deps.storage, root_index, leaf_index)?,
+            });
+        }
+        (Some(address), root_index, None) => (address, root_index),
+        _ => {
+            return Err(StdError::generic_err(
+                "Either address or root_index along-with leaf_index is required",
+            ))
+        }
+    };
+
+    // EVM addresses are 20 bytes long hex strings
+    if address.len() == 40 && hex::decode(&address).is_ok() {
+        let eth_address = address.to_lowercase();
+        let is_claimed = match root_index {
+            Some(root_index) => EVM_CLAIMS.has(
+                deps.storage,
+                (U32Key::new(root_index), eth_address.as_str()),
+            ),
+            None => {
+                let state = STATE.load(deps.storage)?;
+                (0..state.merkle_roots_count).any(|root_index| {
+                    EVM_CLAIMS.has(
+                        deps.storage,
+                        (U32Key::new(root_index), eth_address.as_str()),
+                    )
+                })
+            }
+        };
+        return Ok(ClaimResponse { is_claimed });
+    }
+
     let user_address = deps.api.addr_validate(&address)?;
     let user_info = USERS
         .may_load(deps.storage, &user_address)?
         .unwrap_or_default();
 
-    Ok(ClaimResponse {
-        is_claimed: !user_info.claimed_amount.is_zero(),
+    let is_claimed = match root_index {
+        Some(root_index) => {
+            let state = STATE.load(deps.storage)?;
+            has_claimed_against_root(deps.storage, &state, &user_info, root_index, &user_address)?
+        }
+        None => {
+            user_info.claimed_amount
+                > user_info.evm_claimed_amount + user_info.indexed_claimed_amount
+        }
+    };
+
+    Ok(ClaimResponse { is_claimed })
+}
+
+/// @dev Returns the merkle roots added to the contract, ordered by their index
+/// @param start_after : Index of the merkle root after which the merkle roots are returned
+/// @param limit : Max number of merkle roots to be returned
+fn query_merkle_roots(
+    deps: Deps,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+) -> StdResult<MerkleRootsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let merkle_roots = MERKLE_ROOTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, merkle_root) = item?;
+            let root_index = k
+                .try_into()
+                .map(u32::from_be_bytes)
+                .map_err(|_| StdError::generic_err("Invalid merkle root index"))?;
+            Ok(MerkleRootResponse {
+                root_index,
+                merkle_root: merkle_root.merkle_root,
+                budget: merkle_root.budget,
+                claimed_amount: merkle_root.claimed_amount,
+                expiry_timestamp: merkle_root.expiry_timestamp,
+                is_disabled: merkle_root.is_disabled,
+                hashing_scheme: merkle_root.hashing_scheme,
+                is_indexed: merkle_root.is_indexed,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(MerkleRootsResponse { merkle_roots })
+}
+
+/// @dev Returns the claimed bitmap words of an indexed merkle root, ordered by their index. Words without any claim are omitted
+/// @param root_index : Merkle Tree root identifier
+/// @param start_after : Index of the word after which the words are returned
+/// @param limit : Max number of words to be returned
+fn query_claimed_bitmap(
+    deps: Deps,
+    root_index: u32,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ClaimedBitmapResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive_int);
+
+    let words = CLAIMED_BITMAPS
+        .prefix(U32Key::new(root_index))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, bitmap) = item?;
+            let word_index = k
+                .try_into()
+                .map(u64::from_be_bytes)
+                .map_err(|_| StdError::generic_err("Invalid bitmap word index"))?;
+            Ok(ClaimedBitmapWordResponse { word_index, bitmap })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ClaimedBitmapResponse { root_index, words })
+}
+
+//----------------------------------------------------------------------------------------
+// Helper functions
+//----------------------------------------------------------------------------------------
+
+/// @dev Validates that the merkle root is a hex encoded 32 bytes hash
+/// @param merkle_root : Merkle root to be validated
+fn validate_merkle_root(merkle_root: &str) -> Result<(), ContractError> {
+    let mut root_buf: [u8; 32] = [0; 32];
+    if hex::decode_to_slice(merkle_root, &mut root_buf).is_err() {
+        return Err(ContractError::InvalidMerkleRoot {});
+    }
+    Ok(())
+}
+
+/// @dev Loads the merkle root against which an airdrop is being claimed. Fails if it is disabled or has expired
+/// @param root_index : Merkle Tree root identifier
+/// @param current_timestamp : Current block timestamp
+fn load_merkle_root_for_claim(
+    storage: &dyn Storage,
+    root_index: u32,
+    current_timestamp: u64,
+) -> Result<MerkleRoot, ContractError> {
+    let merkle_root = MERKLE_ROOTS
+        .may_load(storage, U32Key::new(root_index))?
+        .ok_or_else(|| ContractError::MerkleRootNotFound {})?;
+
+    if merkle_root.is_disabled {
+        return Err(ContractError::MerkleRootDisabled {});
+    }
+
+    if let Some(expiry_timestamp) = merkle_root.expiry_timestamp {
+        if expiry_timestamp < current_timestamp {
+            return Err(ContractError::MerkleRootExpired {});
+        }
+    }
+
+    Ok(merkle_root)
+}
+
+/// @dev Accounts for MARS tokens claimed against the merkle root. Fails if the merkle root's budget is exceeded
+/// @param merkle_root : Merkle root against which the airdrop is claimed
+/// @param claim_amount : Airdrop being claimed
+fn claim_against_merkle_root(
+    merkle_root: &mut MerkleRoot,
+    claim_amount: Uint128,
+) -> Result<(), ContractError> {
+    merkle_root.claimed_amount += claim_amount;
+
+    if let Some(budget) = merkle_root.budget {
+        if merkle_root.claimed_amount > budget {
+            return Err(ContractError::MerkleRootBudgetExceeded {});
+        }
+    }
+
+    Ok(())
+}
+
+/// @dev Returns the hash of the leaf being claimed against the merkle root
+/// @param account : Account on behalf of which the airdrop is claimed
+/// @param amount : Airdrop amount being claimed
+/// @param leaf_index : Index of the leaf, required by indexed merkle roots
+fn merkle_leaf(
+    env: &Env,
+    merkle_root: &MerkleRoot,
+    account: &str,
+    amount: Uint128,
+    leaf_index: Option<u64>,
+) -> Result<[u8; 32], ContractError> {
+    match (merkle_root.is_indexed, leaf_index) {
+        (true, Some(leaf_index)) => Ok(indexed_leaf_hash(
+            &env.block.chain_id,
+            env.contract.address.as_str(),
+            leaf_index,
+            account,
+            amount,
+        )),
+        (true, None) => Err(ContractError::LeafIndexRequired {}),
+        (false, None) => Ok(leaf_hash(
+            &merkle_root.hashing_scheme,
+            &env.block.chain_id,
+            env.contract.address.as_str(),
+            account,
+            amount,
+        )),
+        (false, Some(_)) => Err(ContractError::LeafIndexNotSupported {}),
+    }
+}
+
+/// @dev Returns the position (word index, byte and bit mask) of a leaf in the claimed bitmap
+fn bitmap_position(leaf_index: u64) -> (u64, usize, u8) {
+    (
+        leaf_index / 256,
+        ((leaf_index % 256) / 8) as usize,
+        1u8 << (leaf_index % 8),
+    )
+}
+
+/// @dev Returns true if the leaf of the indexed merkle root has been claimed
+fn is_leaf_claimed(storage: &dyn Storage, root_index: u32, leaf_index: u64) -> StdResult<bool> {
+    let (word_index, byte, mask) = bitmap_position(leaf_index);
+    let bitmap =
+        CLAIMED_BITMAPS.may_load(storage, (U32Key::new(root_index), U64Key::new(word_index)))?;
+    Ok(bitmap.map_or(false, |bitmap| bitmap[byte] & mask != 0))
+}
+
+/// @dev Returns true if the user has claimed MARS against the (non-indexed) merkle root. Claims made before the merkle
+/// roots were carried over from the config aren't recorded per merkle root, hence users who claimed then are considered
+/// to have claimed against all of the carried over merkle roots
+/// @param state : State struct
+/// @param user_info : User's airdrop position
+/// @param root_index : Merkle Tree root identifier
+/// @param user_address : Terra address of the user
+fn has_claimed_against_root(
+    storage: &dyn Storage,
+    state: &State,
+    user_info: &UserInfo,
+    root_index: u32,
+    user_address: &Addr,
+) -> StdResult<bool> {
+    if CLAIMS.has(storage, (U32Key::new(root_index), user_address)) {
+        return Ok(true);
+    }
+    let has_legacy_claim = user_info.claimed_amount
+        > user_info.evm_claimed_amount
+            + user_info.indexed_claimed_amount
+            + user_info.root_claimed_amount;
+    Ok(root_index < state.legacy_merkle_roots_count && has_legacy_claim)
+}
+
+/// @dev Marks the leaf of the indexed merkle root as claimed. Returns false if it was already claimed
+fn set_leaf_claimed(
+    storage: &mut dyn Storage,
+    root_index: u32,
+    leaf_index: u64,
+) -> StdResult<bool> {
+    let (word_index, byte, mask) = bitmap_position(leaf_index);
+    let key = (U32Key::new(root_index), U64Key::new(word_index));
+    let mut bitmap = CLAIMED_BITMAPS
+        .may_load(storage, key.clone())?
+        .map_or_else(|| vec![0u8; 32], |bitmap| bitmap.to_vec());
+
+    if bitmap[byte] & mask != 0 {
+        return Ok(false);
+    }
+    bitmap[byte] |= mask;
+
+    CLAIMED_BITMAPS.save(storage, key, &Binary::from(bitmap))?;
+    Ok(true)
+}
+
+/// @dev Returns the total MARS tokens transferred by the sweep
+/// @param recipients : Addresses receiving the unclaimed MARS tokens along-with their amounts
+fn sweep_amount(recipients: &[SweepRecipient]) -> Uint128 {
+    recipients
+        .iter()
+        .fold(Uint128::zero(), |total, recipient| total + recipient.amount)
+}
+
+/// @dev Validates the vesting schedule
+/// @param vesting : Vesting schedule of the MARS airdrop
+fn validate_vesting_schedule(vesting: &VestingSchedule) -> Result<(), ContractError> {
+    if vesting.duration == 0u64 || vesting.cliff > vesting.duration {
+        return Err(ContractError::InvalidVestingSchedule {});
+    }
+    Ok(())
+}
+
+/// @dev Validates the early exit penalty. Returns the penalty with the normalized treasury address
+/// @param early_exit_penalty : Early exit penalty to be validated
+fn validate_early_exit_penalty(
+    api: &dyn Api,
+    early_exit_penalty: EarlyExitPenalty,
+) -> Result<EarlyExitPenalty, ContractError> {
+    if early_exit_penalty.window == 0u64 || early_exit_penalty.penalty_rate > Decimal::one() {
+        return Err(ContractError::InvalidEarlyExitPenalty {});
+    }
+
+    let treasury_address = early_exit_penalty
+        .treasury_address
+        .map(|treasury_address| api.addr_validate(&treasury_address))
+        .transpose()?
+        .map(|treasury_address| treasury_address.to_string());
+
+    Ok(EarlyExitPenalty {
+        treasury_address,
+        ..early_exit_penalty
     })
 }
+
+/// @dev Returns the MARS tokens forfeited by the user if withdrawn within the early exit window
+/// @param config : Configuration struct
+/// @param amount : MARS tokens being withdrawn
+/// @param current_timestamp : Current block timestamp
+fn calculate_early_exit_penalty(
+    config: &Config,
+    amount: Uint128,
+    current_timestamp: u64,
+) -> Uint128 {
+    match &config.early_exit_penalty {
+        Some(early_exit_penalty)
+            if current_timestamp < config.claims_enabled_timestamp + early_exit_penalty.window =>
+        {
+            amount * early_exit_penalty.penalty_rate
+        }
+        _ => Uint128::zero(),
+    }
+}
+
+/// @dev Returns the messages transferring MARS withdrawn by the user along-with the early exit penalty. Forfeited MARS
+/// is transferred to the treasury, or added back to the unclaimed tokens if no treasury is set
+/// @param config : Configuration struct
+/// @param state : State struct
+/// @param recipient : User withdrawing the MARS tokens
+/// @param amount : MARS tokens withdrawn from the user's position
+/// @param current_timestamp : Current block timestamp
+fn build_withdrawal_msgs(
+    config: &Config,
+    state: &mut State,
+    recipient: Addr,
+    amount: Uint128,
+    current_timestamp: u64,
+) -> StdResult<(Vec<CosmosMsg>, Uint128)> {
+    let penalty_amount = calculate_early_exit_penalty(config, amount, current_timestamp);
+    let mut messages = vec![];
+
+    if amount > penalty_amount {
+        messages.push(build_transfer_cw20_token_msg(
+            recipient,
+            config.mars_token_address.to_string(),
+            amount - penalty_amount,
+        )?);
+    }
+
+    if !penalty_amount.is_zero() {
+        state.total_penalty_amount += penalty_amount;
+        match config
+            .early_exit_penalty
+            .as_ref()
+            .and_then(|early_exit_penalty| early_exit_penalty.treasury_address.as_ref())
+        {
+            Some(treasury_address) => messages.push(build_transfer_cw20_token_msg(
+                // Treasury address is validated when the early exit penalty is set
+                Addr::unchecked(treasury_address),
+                config.mars_token_address.to_string(),
+                penalty_amount,
+            )?),
+            None => {
+                state.unclaimed_tokens += penalty_amount;
+                state.recycled_penalty_amount += penalty_amount;
+            }
+        }
+    }
+
+    Ok((messages, penalty_amount))
+}
+
+/// @dev Returns the MARS tokens of the user's airdrop position which are to be withdrawn by the user. Delegated MARS
+/// is returned to the users if the bootstrap auction has failed
+/// @param config : Configuration struct
+/// @param user_info : User's airdrop position
+fn withdrawable_amount(config: &Config, user_info: &UserInfo) -> Uint128 {
+    if config.is_auction_failed {
+        user_info.claimed_amount
+    } else {
+        user_info.claimed_amount - user_info.delegated_amount
+    }
+}
+
+/// @dev Returns the user's airdrop position along-with the MARS tokens vested / locked at the current timestamp
+/// @param config : Configuration struct
+/// @param user_info : User's airdrop position
+/// @param current_timestamp : Current block timestamp
+fn user_info_response(
+    config: &Config,
+    user_info: &UserInfo,
+    current_timestamp: u64,
+) -> UserInfoResponse {
+    let vested_amount = calculate_vested_amount(config, user_info, current_timestamp);
+    let locked_amount = withdrawable_amount(config, user_info) - vested_amount;
+
+    UserInfoResponse {
+        airdrop_amount: user_info.claimed_amount,
+        delegated_amount: user_info.delegated_amount,
+        tokens_withdrawn: user_info.tokens_withdrawn,
+        withdrawn_amount: user_info.withdrawn_amount,
+        vested_amount,
+        locked_amount,
+    }
+}
+
+/// @dev Credits MARS tokens to the user's airdrop position
+/// @param config : Configuration struct
+/// @param state : State struct
+/// @param user_info : User's airdrop position
+/// @param amount : Number of MARS tokens to be credited
+fn credit_user(config: &Config, state: &mut State, user_info: &mut UserInfo, amount: Uint128) {
+    if user_info.claimed_amount.is_zero() {
+        state.claimants_count += 1;
+    }
+
+    // Tokens previously withdrawn remain accounted for once the position is credited with more MARS
+    if user_info.tokens_withdrawn {
+        user_info.withdrawn_amount = withdrawable_amount(config, user_info);
+        user_info.tokens_withdrawn = false;
+    }
+    user_info.claimed_amount += amount;
+}
+
+/// @dev Returns undelegated MARS tokens which have vested for the user. Vesting starts when claims are enabled. The
+/// full airdrop is available at once if the bootstrap auction has failed
+/// @param config : Configuration struct
+/// @param user_info : User's airdrop position
+/// @param current_timestamp : Current block timestamp
+fn calculate_vested_amount(
+    config: &Config,
+    user_info: &UserInfo,
+    current_timestamp: u64,
+) -> Uint128 {
+    if config.is_auction_failed {
+        return withdrawable_amount(config, user_info);
+    }
+    if !config.are_claims_enabled {
+        return Uint128::zero();
+    }
+
+    let total_amount = withdrawable_amount(config, user_info);
+    match &config.vesting {
+        None => total_amount,
+        Some(vesting) => {
+            let time_elapsed = current_timestamp.saturating_sub(config.claims_enabled_timestamp);
+            if time_elapsed < vesting.cliff {
+                Uint128::zero()
+            } else if time_elapsed >= vesting.duration {
+                total_amount
+            } else {
+                total_amount.multiply_ratio(time_elapsed, vesting.duration)
+            }
+        }
+    }
+}
+
+/// @dev Updates user's withdrawn amount. Returns MARS tokens which have vested but are yet to be withdrawn by the user
+/// @param config : Configuration struct
+/// @param state : State struct
+/// @param user_info : User's airdrop position
+/// @param current_timestamp : Current block timestamp
+fn withdraw_vested_tokens(
+    config: &Config,
+    state: &mut State,
+    user_info: &mut UserInfo,
+    current_timestamp: u64,
+) -> Uint128 {
+    if user_info.tokens_withdrawn {
+        return Uint128::zero();
+    }
+
+    let vested_amount = calculate_vested_amount(config, user_info, current_timestamp);
+    let tokens_to_withdraw = vested_amount.saturating_sub(user_info.withdrawn_amount);
+
+    if user_info.withdrawn_amount.is_zero() && !tokens_to_withdraw.is_zero() {
+        state.withdrawers_count += 1;
+    }
+    user_info.withdrawn_amount += tokens_to_withdraw;
+    user_info.tokens_withdrawn =
+        user_info.withdrawn_amount == withdrawable_amount(config, user_info);
+
+    tokens_to_withdraw
+}
@@ -1,45 +1,181 @@
-use cosmwasm_std::{Addr, Uint128};
+use crate::error::ContractError;
+use cosmwasm_std::{Api, Uint128};
+use mars_periphery::airdrop::HashingScheme;
 use sha3::{Digest, Keccak256};
-use std::cmp::Ordering;
 use std::convert::TryInto;
 
+/// Domain separator of the leaves hashed via the V1 hashing scheme
+pub const V1_DOMAIN: &[u8] = b"mars_airdrop_v1";
+/// Domain separator of the leaves carrying an index, hashed via the V1 hashing scheme
+pub const V1_INDEXED_DOMAIN: &[u8] = b"mars_airdrop_v1_indexed";
+/// Prefix of the leaves hashed via the V1 hashing scheme
+const V1_LEAF_PREFIX: u8 = 0x00;
+/// Prefix of the inner nodes hashed via the V1 hashing scheme
+const V1_NODE_PREFIX: u8 = 0x01;
+
 /// @dev Verify whether a claim is valid
+/// @param hashing_scheme Hashing scheme used to build the Merkle Tree
+/// @param chain_id Chain id the airdrop contract is deployed on (only part of V1 leaves)
+/// @param contract_address Address of the airdrop contract (only part of V1 leaves)
 /// @param account Account on behalf of which the airdrop is to be claimed (etherum addresses without `0x` prefix)
 /// @param amount Airdrop amount to be claimed by the user
 /// @param merkle_proof Array of hashes to prove the input is a leaf of the Merkle Tree
 /// @param merkle_root Hash of Merkle tree's root
 pub fn verify_claim(
-    account: &Addr,
+    hashing_scheme: &HashingScheme,
+    chain_id: &str,
+    contract_address: &str,
+    account: &str,
     amount: Uint128,
     merkle_proof: Vec<String>,
     merkle_root: &str,
-) -> bool {
-    let leaf = account.to_string() + &amount.to_string();
-    let mut hash_buf = Keccak256::digest(leaf.as_bytes())
-        .as_slice()
-        .try_into()
-        .expect("Wrong length");
-    let mut hash_str: String;
+) -> Result<bool, ContractError> {
+    let leaf = leaf_hash(hashing_scheme, chain_id, contract_address, account, amount);
+    verify_merkle_proof(hashing_scheme, leaf, merkle_proof, merkle_root)
+}
+
+/// @dev Verify whether the leaf is part of the Merkle Tree
+/// @param hashing_scheme Hashing scheme used to build the Merkle Tree
+/// @param leaf Hash of the leaf
+/// @param merkle_proof Array of hashes to prove the input is a leaf of the Merkle Tree
+/// @param merkle_root Hash of Merkle tree's root
+pub fn verify_merkle_proof(
+    hashing_scheme: &HashingScheme,
+    leaf: [u8; 32],
+    merkle_proof: Vec<String>,
+    merkle_root: &str,
+) -> Result<bool, ContractError> {
+    let mut hash_buf = leaf;
 
     for p in merkle_proof {
         let mut proof_buf: [u8; 32] = [0; 32];
-        hex::decode_to_slice(p, &mut proof_buf).unwrap();
-        let proof_buf_str = hex::encode(proof_buf);
-        hash_str = hex::encode(hash_buf);
-
-        if proof_buf_str.cmp(&hash_str.clone()) == Ordering::Greater {
-            hash_buf = Keccak256::digest(&[hash_buf, proof_buf].concat())
-                .as_slice()
-                .try_into()
-                .expect("Wrong length")
-        } else {
-            hash_buf = Keccak256::digest(&[proof_buf, hash_buf].concat())
-                .as_slice()
-                .try_into()
-                .expect("Wrong length")
-        }
+        hex::decode_to_slice(p, &mut proof_buf)
+            .map_err(|_| ContractError::IncorrectMerkleProof {})?;
+        hash_buf = node_hash(hashing_scheme, &hash_buf, &proof_buf);
+    }
+
+    Ok(merkle_root == hex::encode(hash_buf))
+}
+
+/// @dev Returns the hash of a Merkle Tree leaf
+/// @param hashing_scheme Hashing scheme used to build the Merkle Tree
+/// @param chain_id Chain id the airdrop contract is deployed on (only part of V1 leaves)
+/// @param contract_address Address of the airdrop contract (only part of V1 leaves)
+/// @param account Account eligible for the airdrop
+/// @param amount Airdrop amount of the account
+pub fn leaf_hash(
+    hashing_scheme: &HashingScheme,
+    chain_id: &str,
+    contract_address: &str,
+    account: &str,
+    amount: Uint128,
+) -> [u8; 32] {
+    match hashing_scheme {
+        HashingScheme::Legacy => keccak256(&[account.as_bytes(), amount.to_string().as_bytes()]),
+        HashingScheme::V1 => keccak256(&[
+            &[V1_LEAF_PREFIX],
+            &length_prefixed(V1_DOMAIN),
+            &length_prefixed(chain_id.as_bytes()),
+            &length_prefixed(contract_address.as_bytes()),
+            &length_prefixed(account.as_bytes()),
+            &amount.u128().to_be_bytes(),
+        ]),
+    }
+}
+
+/// @dev Returns the hash of a Merkle Tree leaf carrying its index. Leaves carrying an index are hashed via the V1 hashing scheme
+/// @param chain_id Chain id the airdrop contract is deployed on
+/// @param contract_address Address of the airdrop contract
+/// @param leaf_index Index of the leaf, used to track the claim
+/// @param account Account eligible for the airdrop
+/// @param amount Airdrop amount of the account
+pub fn indexed_leaf_hash(
+    chain_id: &str,
+    contract_address: &str,
+    leaf_index: u64,
+    account: &str,
+    amount: Uint128,
+) -> [u8; 32] {
+    keccak256(&[
+        &[V1_LEAF_PREFIX],
+        &length_prefixed(V1_INDEXED_DOMAIN),
+        &length_prefixed(chain_id.as_bytes()),
+        &length_prefixed(contract_address.as_bytes()),
+        &leaf_index.to_be_bytes(),
+        &length_prefixed(account.as_bytes()),
+        &amount.u128().to_be_bytes(),
+    ])
+}
+
+/// @dev Returns the hash of a Merkle Tree node from its children. Children are sorted before hashing
+/// @param hashing_scheme Hashing scheme used to build the Merkle Tree
+/// @param a Hash of the first child
+/// @param b Hash of the second child
+pub fn node_hash(hashing_scheme: &HashingScheme, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    match hashing_scheme {
+        HashingScheme::Legacy => keccak256(&[first, second]),
+        HashingScheme::V1 => keccak256(&[&[V1_NODE_PREFIX], first, second]),
+    }
+}
+
+/// @dev Returns the keccak256 hash of the concatenated inputs
+fn keccak256(inputs: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    hasher.finalize().into()
+}
+
+/// @dev Prefixes the input with its length (4 bytes, big-endian)
+fn length_prefixed(input: &[u8]) -> Vec<u8> {
+    [&(input.len() as u32).to_be_bytes(), input].concat()
+}
+
+/// @dev Returns the hash signed by an EVM wallet when signing `message` via `personal_sign` (EIP-191)
+/// @param message Message that was signed, i.e. the terra address receiving the airdrop
+pub fn evm_signed_msg_hash(message: &str) -> [u8; 32] {
+    let prefixed_msg = format!("\x19Ethereum Signed Message:\n{}{}", message.len(), message);
+    Keccak256::digest(prefixed_msg.as_bytes())
+        .as_slice()
+        .try_into()
+        .expect("Wrong length")
+}
+
+/// @dev Recovers the EVM address (lower-case, without `0x` prefix) which produced the signature
+/// @param msg_hash Hash of the message which was signed
+/// @param signature 65 bytes signature (r, s, v) encoded as hex, with or without the `0x` prefix
+pub fn recover_evm_address(
+    api: &dyn Api,
+    msg_hash: &[u8],
+    signature: &str,
+) -> Result<String, ContractError> {
+    let signature = hex::decode(signature.trim_start_matches("0x"))
+        .map_err(|_| ContractError::InvalidSignature {})?;
+    if signature.len() != 65 {
+        return Err(ContractError::InvalidSignature {});
     }
 
-    hash_str = hex::encode(hash_buf);
-    merkle_root == hash_str
+    // EVM wallets use 27 / 28 as recovery id, other signers use 0 / 1
+    let recovery_param = match signature[64] {
+        0 | 27 => 0u8,
+        1 | 28 => 1u8,
+        _ => return Err(ContractError::InvalidSignature {}),
+    };
+
+    let pubkey = api
+        .secp256k1_recover_pubkey(msg_hash, &signature[..64], recovery_param)
+        .map_err(|_| ContractError::InvalidSignature {})?;
+    evm_address_from_pubkey(&pubkey)
+}
+
+/// @dev Derives the EVM address (lower-case, without `0x` prefix) from an uncompressed secp256k1 public key
+/// @param pubkey 65 bytes uncompressed public key (0x04 prefix followed by the X and Y coordinates)
+fn evm_address_from_pubkey(pubkey: &[u8]) -> Result<String, ContractError> {
+    if pubkey.len() != 65 || pubkey[0] != 0x04 {
+        return Err(ContractError::InvalidSignature {});
+    }
+    let hash = Keccak256::digest(&pubkey[1..]);
+    Ok(hex::encode(&hash[12..]))
 }
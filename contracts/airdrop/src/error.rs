@@ -0,0 +1,308 @@
+use cosmwasm_std::{to_vec, StdError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+/// Errors returned by the airdrop contract. Messages are prefixed with the stable code of the error (`<code>: <message>`)
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{}: {0}", self.code())]
+    Std(#[from] StdError),
+
+    #[error("{}: Unauthorized", self.code())]
+    Unauthorized {},
+
+    #[error("{}: Only mars tokens are received", self.code())]
+    InvalidToken {},
+
+    #[error("{}: Amount must be greater than 0", self.code())]
+    ZeroAmount {},
+
+    #[error("{}: Invalid airdrop claim window", self.code())]
+    InvalidClaimWindow {},
+
+    #[error("{}: from_timestamp can't be changed after window starts", self.code())]
+    ClaimWindowStarted {},
+
+    #[error("{}: When window starts to_timestamp can only be increased", self.code())]
+    ClaimWindowDecreased {},
+
+    #[error("{}: Invalid vesting schedule", self.code())]
+    InvalidVestingSchedule {},
+
+    #[error("{}: Invalid early exit penalty", self.code())]
+    InvalidEarlyExitPenalty {},
+
+    #[error("{}: Early exit penalty can't be changed once claims are enabled", self.code())]
+    EarlyExitPenaltyLocked {},
+
+    #[error("{}: Invalid sweep delay", self.code())]
+    InvalidSweepDelay {},
+
+    #[error("{}: Invalid merkle root", self.code())]
+    InvalidMerkleRoot {},
+
+    #[error("{}: Invalid merkle root expiry timestamp", self.code())]
+    InvalidMerkleRootExpiry {},
+
+    #[error("{}: Indexed merkle roots require the V1 hashing scheme", self.code())]
+    IndexedMerkleRootNotV1 {},
+
+    #[error("{}: Incorrect Merkle Root Index", self.code())]
+    MerkleRootNotFound {},
+
+    #[error("{}: Merkle root already disabled", self.code())]
+    MerkleRootAlreadyDisabled {},
+
+    #[error("{}: Merkle root is disabled", self.code())]
+    MerkleRootDisabled {},
+
+    #[error("{}: Merkle root has expired", self.code())]
+    MerkleRootExpired {},
+
+    #[error("{}: Merkle root budget exceeded", self.code())]
+    MerkleRootBudgetExceeded {},
+
+    #[error("{}: Leaf index is required by the merkle root", self.code())]
+    LeafIndexRequired {},
+
+    #[error("{}: Merkle root doesn't support leaf indexes", self.code())]
+    LeafIndexNotSupported {},
+
+    #[error("{}: Claim not allowed", self.code())]
+    ClaimNotAllowed {},
+
+    #[error("{}: Claim period has concluded", self.code())]
+    ClaimPeriodConcluded {},
+
+    #[error("{}: Incorrect Merkle Proof", self.code())]
+    IncorrectMerkleProof {},
+
+    #[error("{}: Already claimed", self.code())]
+    AlreadyClaimed {},
+
+    #[error("{}: Insufficient MARS available", self.code())]
+    InsufficientMars {},
+
+    #[error("{}: Invalid signed message hash", self.code())]
+    InvalidSignedMsgHash {},
+
+    #[error("{}: Signed message doesn't match the recipient address", self.code())]
+    SignedMsgMismatch {},
+
+    #[error("{}: Invalid signature", self.code())]
+    InvalidSignature {},
+
+    #[error("{}: Auction contract not set", self.code())]
+    AuctionNotSet {},
+
+    #[error("{}: Auction delegations already live", self.code())]
+    AuctionDelegationsLive {},
+
+    #[error("{}: LP bootstrap auction in progress. Claims not allowed during this period", self.code())]
+    AuctionInProgress {},
+
+    #[error("{}: LP bootstrap auction has concluded", self.code())]
+    AuctionConcluded {},
+
+    #[error("{}: LP bootstrap auction has failed", self.code())]
+    AuctionFailed {},
+
+    #[error("{}: LP bootstrap auction already marked as failed", self.code())]
+    AuctionAlreadyFailed {},
+
+    #[error("{}: LP bootstrap auction has not been marked as failed", self.code())]
+    AuctionNotFailed {},
+
+    #[error("{}: Claims already enabled", self.code())]
+    ClaimsAlreadyEnabled {},
+
+    #[error("{}: Invalid auction deadline", self.code())]
+    InvalidAuctionDeadline {},
+
+    #[error("{}: When auction delegations are live auction_deadline can only be increased", self.code())]
+    AuctionDeadlineDecreased {},
+
+    #[error("{}: Auction deadline not set", self.code())]
+    AuctionDeadlineNotSet {},
+
+    #[error("{}: Auction deadline not reached", self.code())]
+    AuctionDeadlineNotReached {},
+
+    #[error("{}: Returned MARS doesn't match the delegated amount", self.code())]
+    ReturnedAmountMismatch {},
+
+    #[error("{}: No airdrop claimed by the user", self.code())]
+    NoAirdropClaimed {},
+
+    #[error("{}: Amount to delegate cannot exceed the airdrop being claimed", self.code())]
+    DelegationExceedsClaim {},
+
+    #[error("{}: Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance", self.code())]
+    DelegationExceedsBalance {},
+
+    #[error("{}: MARS airdrop is subject to vesting. Use WithdrawVested", self.code())]
+    VestingEnabled {},
+
+    #[error("{}: Tokens have already been withdrawn", self.code())]
+    AlreadyWithdrawn {},
+
+    #[error("{}: Nothing to withdraw", self.code())]
+    NothingToWithdraw {},
+
+    #[error("{}: No vested tokens to withdraw", self.code())]
+    NoVestedTokens {},
+
+    #[error("{}: {seconds_left} seconds left before unclaimed tokens can be transferred", self.code())]
+    ClaimPeriodNotOver { seconds_left: u64 },
+
+    #[error("{}: A sweep is already pending", self.code())]
+    SweepAlreadyPending {},
+
+    #[error("{}: No pending sweep", self.code())]
+    NoPendingSweep {},
+
+    #[error("{}: No sweep recipients", self.code())]
+    NoSweepRecipients {},
+
+    #[error("{}: Amount cannot exceed unclaimed token balance", self.code())]
+    SweepExceedsUnclaimed {},
+
+    #[error("{}: {seconds_left} seconds left before the sweep can be executed", self.code())]
+    SweepDelayNotOver { seconds_left: u64 },
+}
+
+impl ContractError {
+    /// @dev Returns the stable code of the error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ContractError::Std(_) => ErrorCode::Std,
+            ContractError::Unauthorized {} => ErrorCode::Unauthorized,
+            ContractError::InvalidToken {} => ErrorCode::InvalidToken,
+            ContractError::ZeroAmount {} => ErrorCode::ZeroAmount,
+            ContractError::InvalidClaimWindow {} => ErrorCode::InvalidClaimWindow,
+            ContractError::ClaimWindowStarted {} => ErrorCode::ClaimWindowStarted,
+            ContractError::ClaimWindowDecreased {} => ErrorCode::ClaimWindowDecreased,
+            ContractError::InvalidVestingSchedule {} => ErrorCode::InvalidVestingSchedule,
+            ContractError::InvalidEarlyExitPenalty {} => ErrorCode::InvalidEarlyExitPenalty,
+            ContractError::EarlyExitPenaltyLocked {} => ErrorCode::EarlyExitPenaltyLocked,
+            ContractError::InvalidSweepDelay {} => ErrorCode::InvalidSweepDelay,
+            ContractError::InvalidMerkleRoot {} => ErrorCode::InvalidMerkleRoot,
+            ContractError::InvalidMerkleRootExpiry {} => ErrorCode::InvalidMerkleRootExpiry,
+            ContractError::IndexedMerkleRootNotV1 {} => ErrorCode::IndexedMerkleRootNotV1,
+            ContractError::MerkleRootNotFound {} => ErrorCode::MerkleRootNotFound,
+            ContractError::MerkleRootAlreadyDisabled {} => ErrorCode::MerkleRootAlreadyDisabled,
+            ContractError::MerkleRootDisabled {} => ErrorCode::MerkleRootDisabled,
+            ContractError::MerkleRootExpired {} => ErrorCode::MerkleRootExpired,
+            ContractError::MerkleRootBudgetExceeded {} => ErrorCode::MerkleRootBudgetExceeded,
+            ContractError::LeafIndexRequired {} => ErrorCode::LeafIndexRequired,
+            ContractError::LeafIndexNotSupported {} => ErrorCode::LeafIndexNotSupported,
+            ContractError::ClaimNotAllowed {} => ErrorCode::ClaimNotAllowed,
+            ContractError::ClaimPeriodConcluded {} => ErrorCode::ClaimPeriodConcluded,
+            ContractError::IncorrectMerkleProof {} => ErrorCode::IncorrectMerkleProof,
+            ContractError::AlreadyClaimed {} => ErrorCode::AlreadyClaimed,
+            ContractError::InsufficientMars {} => ErrorCode::InsufficientMars,
+            ContractError::InvalidSignedMsgHash {} => ErrorCode::InvalidSignedMsgHash,
+            ContractError::SignedMsgMismatch {} => ErrorCode::SignedMsgMismatch,
+            ContractError::InvalidSignature {} => ErrorCode::InvalidSignature,
+            ContractError::AuctionNotSet {} => ErrorCode::AuctionNotSet,
+            ContractError::AuctionDelegationsLive {} => ErrorCode::AuctionDelegationsLive,
+            ContractError::AuctionInProgress {} => ErrorCode::AuctionInProgress,
+            ContractError::AuctionConcluded {} => ErrorCode::AuctionConcluded,
+            ContractError::AuctionFailed {} => ErrorCode::AuctionFailed,
+            ContractError::AuctionAlreadyFailed {} => ErrorCode::AuctionAlreadyFailed,
+            ContractError::AuctionNotFailed {} => ErrorCode::AuctionNotFailed,
+            ContractError::ClaimsAlreadyEnabled {} => ErrorCode::ClaimsAlreadyEnabled,
+            ContractError::InvalidAuctionDeadline {} => ErrorCode::InvalidAuctionDeadline,
+            ContractError::AuctionDeadlineDecreased {} => ErrorCode::AuctionDeadlineDecreased,
+            ContractError::AuctionDeadlineNotSet {} => ErrorCode::AuctionDeadlineNotSet,
+            ContractError::AuctionDeadlineNotReached {} => ErrorCode::AuctionDeadlineNotReached,
+            ContractError::ReturnedAmountMismatch {} => ErrorCode::ReturnedAmountMismatch,
+            ContractError::NoAirdropClaimed {} => ErrorCode::NoAirdropClaimed,
+            ContractError::DelegationExceedsClaim {} => ErrorCode::DelegationExceedsClaim,
+            ContractError::DelegationExceedsBalance {} => ErrorCode::DelegationExceedsBalance,
+            ContractError::VestingEnabled {} => ErrorCode::VestingEnabled,
+            ContractError::AlreadyWithdrawn {} => ErrorCode::AlreadyWithdrawn,
+            ContractError::NothingToWithdraw {} => ErrorCode::NothingToWithdraw,
+            ContractError::NoVestedTokens {} => ErrorCode::NoVestedTokens,
+            ContractError::ClaimPeriodNotOver { .. } => ErrorCode::ClaimPeriodNotOver,
+            ContractError::SweepAlreadyPending {} => ErrorCode::SweepAlreadyPending,
+            ContractError::NoPendingSweep {} => ErrorCode::NoPendingSweep,
+            ContractError::NoSweepRecipients {} => ErrorCode::NoSweepRecipients,
+            ContractError::SweepExceedsUnclaimed {} => ErrorCode::SweepExceedsUnclaimed,
+            ContractError::SweepDelayNotOver { .. } => ErrorCode::SweepDelayNotOver,
+        }
+    }
+}
+
+/// Stable codes of the airdrop contract errors. Codes are never renamed nor reused, hence clients are to match on the
+/// code rather than on the error message
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Errors raised by cosmwasm-std (storage, serialization, address validation, ..)
+    Std,
+    Unauthorized,
+    InvalidToken,
+    ZeroAmount,
+    InvalidClaimWindow,
+    ClaimWindowStarted,
+    ClaimWindowDecreased,
+    InvalidVestingSchedule,
+    InvalidEarlyExitPenalty,
+    EarlyExitPenaltyLocked,
+    InvalidSweepDelay,
+    InvalidMerkleRoot,
+    InvalidMerkleRootExpiry,
+    IndexedMerkleRootNotV1,
+    MerkleRootNotFound,
+    MerkleRootAlreadyDisabled,
+    MerkleRootDisabled,
+    MerkleRootExpired,
+    MerkleRootBudgetExceeded,
+    LeafIndexRequired,
+    LeafIndexNotSupported,
+    ClaimNotAllowed,
+    ClaimPeriodConcluded,
+    IncorrectMerkleProof,
+    AlreadyClaimed,
+    InsufficientMars,
+    InvalidSignedMsgHash,
+    SignedMsgMismatch,
+    InvalidSignature,
+    AuctionNotSet,
+    AuctionDelegationsLive,
+    AuctionInProgress,
+    AuctionConcluded,
+    AuctionFailed,
+    AuctionAlreadyFailed,
+    AuctionNotFailed,
+    ClaimsAlreadyEnabled,
+    InvalidAuctionDeadline,
+    AuctionDeadlineDecreased,
+    AuctionDeadlineNotSet,
+    AuctionDeadlineNotReached,
+    ReturnedAmountMismatch,
+    NoAirdropClaimed,
+    DelegationExceedsClaim,
+    DelegationExceedsBalance,
+    VestingEnabled,
+    AlreadyWithdrawn,
+    NothingToWithdraw,
+    NoVestedTokens,
+    ClaimPeriodNotOver,
+    SweepAlreadyPending,
+    NoPendingSweep,
+    NoSweepRecipients,
+    SweepExceedsUnclaimed,
+    SweepDelayNotOver,
+}
+
+impl fmt::Display for ErrorCode {
+    /// Writes the code as serialized in the schema, i.e. `claim_not_allowed`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = to_vec(self).map_err(|_| fmt::Error)?;
+        f.write_str(String::from_utf8_lossy(&code).trim_matches('"'))
+    }
+}
@@ -1,3 +1,5 @@
 pub mod contract;
 pub mod crypto;
+pub mod error;
+pub mod merkle_tree;
 pub mod state;
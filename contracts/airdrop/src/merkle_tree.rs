@@ -0,0 +1,249 @@
+use crate::crypto::{indexed_leaf_hash, leaf_hash, node_hash};
+use cosmwasm_std::{from_slice, StdError, StdResult, Uint128};
+use mars_periphery::airdrop::HashingScheme;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AirdropEntry {
+    /// Account eligible for the airdrop (terra address or EVM address without the `0x` prefix)
+    pub address: String,
+    /// Airdrop amount of the account
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AirdropClaim {
+    /// Account eligible for the airdrop
+    pub address: String,
+    /// Airdrop amount of the account
+    pub amount: Uint128,
+    /// Array of hashes to prove the account is a leaf of the Merkle Tree
+    pub merkle_proof: Vec<String>,
+    /// Merkle Tree root identifier to be used for the claim
+    pub index: u32,
+    /// Index of the leaf, only set for indexed Merkle Trees
+    pub leaf_index: Option<u64>,
+}
+
+/// Merkle Tree built with the same rules as `crypto::verify_claim` :: leaves are sorted, pairs are
+/// sorted before hashing and the last node of a layer with an odd number of nodes is carried up
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleTree {
+    hashing_scheme: HashingScheme,
+    chain_id: String,
+    contract_address: String,
+    /// Boolean value indicating if the leaves carry their index in the entries
+    is_indexed: bool,
+    /// Layers of the tree, from the sorted leaves to the root
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// @dev Builds the Merkle Tree of the airdrop
+    /// @param hashing_scheme Hashing scheme the merkle root is added with to the airdrop contract
+    /// @param chain_id Chain id the airdrop contract is deployed on (only part of V1 leaves)
+    /// @param contract_address Address of the airdrop contract (only part of V1 leaves)
+    /// @param entries Accounts eligible for the airdrop along-with their airdrop amount
+    pub fn new(
+        hashing_scheme: HashingScheme,
+        chain_id: &str,
+        contract_address: &str,
+        entries: &[AirdropEntry],
+    ) -> StdResult<Self> {
+        Self::build(hashing_scheme, chain_id, contract_address, false, entries)
+    }
+
+    /// @dev Builds the indexed Merkle Tree of the airdrop. Leaves carry the position of their entry, hence an
+    /// account can be part of multiple entries. Indexed Merkle Trees are built via the V1 hashing scheme
+    /// @param chain_id Chain id the airdrop contract is deployed on
+    /// @param contract_address Address of the airdrop contract
+    /// @param entries Accounts eligible for the airdrop along-with their airdrop amount
+    pub fn new_indexed(
+        chain_id: &str,
+        contract_address: &str,
+        entries: &[AirdropEntry],
+    ) -> StdResult<Self> {
+        Self::build(HashingScheme::V1, chain_id, contract_address, true, entries)
+    }
+
+    fn build(
+        hashing_scheme: HashingScheme,
+        chain_id: &str,
+        contract_address: &str,
+        is_indexed: bool,
+        entries: &[AirdropEntry],
+    ) -> StdResult<Self> {
+        if entries.is_empty() {
+            return Err(StdError::generic_err("No airdrop entries"));
+        }
+
+        let mut addresses = HashSet::new();
+        let mut tree = MerkleTree {
+            hashing_scheme,
+            chain_id: chain_id.to_string(),
+            contract_address: contract_address.to_string(),
+            is_indexed,
+            layers: vec![],
+        };
+
+        let mut leaves = entries
+            .iter()
+            .enumerate()
+            .map(|(leaf_index, entry)| {
+                if !is_indexed && !addresses.insert(entry.address.as_str()) {
+                    return Err(StdError::generic_err(format!(
+                        "Duplicate address {}",
+                        entry.address
+                    )));
+                }
+                Ok(tree.leaf(leaf_index as u64, entry))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        leaves.sort_unstable();
+
+        let mut layers = vec![leaves];
+        while layers[layers.len() - 1].len() > 1 {
+            let next_layer = layers[layers.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => node_hash(&hashing_scheme, a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next_layer);
+        }
+
+        tree.layers = layers;
+        Ok(tree)
+    }
+
+    /// @dev Returns the hash of the leaf of an entry
+    fn leaf(&self, leaf_index: u64, entry: &AirdropEntry) -> [u8; 32] {
+        if self.is_indexed {
+            indexed_leaf_hash(
+                &self.chain_id,
+                &self.contract_address,
+                leaf_index,
+                &entry.address,
+                entry.amount,
+            )
+        } else {
+            leaf_hash(
+                &self.hashing_scheme,
+                &self.chain_id,
+                &self.contract_address,
+                &entry.address,
+                entry.amount,
+            )
+        }
+    }
+
+    /// @dev Returns the hex encoded root of the Merkle Tree
+    pub fn root(&self) -> String {
+        hex::encode(self.layers[self.layers.len() - 1][0])
+    }
+
+    /// @dev Returns the hex encoded Merkle proof of the entry. Returns None if the entry is not a leaf of the tree
+    /// @param entry Account along-with its airdrop amount
+    pub fn proof(&self, entry: &AirdropEntry) -> Option<Vec<String>> {
+        self.indexed_proof(0, entry)
+    }
+
+    /// @dev Returns the hex encoded Merkle proof of the entry at `leaf_index`. The index is ignored by non-indexed trees
+    /// @param leaf_index Position of the entry in the entries the tree is built from
+    /// @param entry Account along-with its airdrop amount
+    pub fn indexed_proof(&self, leaf_index: u64, entry: &AirdropEntry) -> Option<Vec<String>> {
+        let leaf = self.leaf(leaf_index, entry);
+        let mut index = self.layers[0].binary_search(&leaf).ok()?;
+
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(hex::encode(sibling));
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// @dev Returns the claims (entries along-with their Merkle proof) of all the entries
+    /// @param entries Accounts eligible for the airdrop along-with their airdrop amount
+    /// @param root_index Merkle Tree root identifier in the airdrop contract
+    pub fn claims(
+        &self,
+        entries: &[AirdropEntry],
+        root_index: u32,
+    ) -> StdResult<Vec<AirdropClaim>> {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(leaf_index, entry)| {
+                let leaf_index = leaf_index as u64;
+                let merkle_proof = self.indexed_proof(leaf_index, entry).ok_or_else(|| {
+                    StdError::generic_err(format!("{} is not part of the tree", entry.address))
+                })?;
+                Ok(AirdropClaim {
+                    address: entry.address.clone(),
+                    amount: entry.amount,
+                    merkle_proof,
+                    index: root_index,
+                    leaf_index: if self.is_indexed {
+                        Some(leaf_index)
+                    } else {
+                        None
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// @dev Parses airdrop entries from a JSON array of `{ "address": .., "amount": .. }` objects
+/// @param data JSON encoded airdrop entries
+pub fn parse_json_entries(data: &[u8]) -> StdResult<Vec<AirdropEntry>> {
+    from_slice(data)
+}
+
+/// @dev Parses airdrop entries from CSV lines of `address,amount`. A header line is skipped
+/// @param data CSV encoded airdrop entries
+pub fn parse_csv_entries(data: &str) -> StdResult<Vec<AirdropEntry>> {
+    let mut entries = vec![];
+
+    for (line_number, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+        if fields.len() != 2 {
+            return Err(StdError::generic_err(format!(
+                "Invalid entry at line {}",
+                line_number + 1
+            )));
+        }
+
+        let amount = match fields[1].parse::<u128>() {
+            Ok(amount) => Uint128::new(amount),
+            // header line
+            Err(_) if line_number == 0 => continue,
+            Err(_) => {
+                return Err(StdError::generic_err(format!(
+                    "Invalid amount at line {}",
+                    line_number + 1
+                )))
+            }
+        };
+
+        entries.push(AirdropEntry {
+            address: fields[0].to_string(),
+            amount,
+        });
+    }
+
+    Ok(entries)
+}
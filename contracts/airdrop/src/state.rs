@@ -1,11 +1,27 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw_storage_plus::{Item, Map, U32Key, U64Key};
+use mars_periphery::airdrop::{EarlyExitPenalty, HashingScheme, SweepRecipient, VestingSchedule};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub const CONFIG: Item<Config> = Item::new("config");
+pub const CONFIG_KEY: &str = "config";
+pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
 pub const STATE: Item<State> = Item::new("state");
 pub const USERS: Map<&Addr, UserInfo> = Map::new("users");
+/// Claims made against non-indexed merkle roots :: (root_index, address) -> claimed
+pub const CLAIMS: Map<(U32Key, &Addr), bool> = Map::new("claims");
+/// Claims made on behalf of EVM addresses :: (root_index, EVM address) -> MARS claimed
+pub const EVM_CLAIMS: Map<(U32Key, &str), Uint128> = Map::new("evm_claims");
+pub const MERKLE_ROOTS: Map<U32Key, MerkleRoot> = Map::new("merkle_roots");
+/// Claim status of the leaves of indexed merkle roots, packed in 256 bits words keyed by (root_index, leaf_index / 256)
+pub const CLAIMED_BITMAPS: Map<(U32Key, U64Key), Binary> = Map::new("claimed_bitmaps");
+pub const PENDING_SWEEP: Item<PendingSweep> = Item::new("pending_sweep");
+
+/// Merkle roots stored in the config by earlier versions of the contract. Moved to `MERKLE_ROOTS` upon `migrate`
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new(CONFIG_KEY);
+
+/// Default number of seconds after which a proposed sweep can be executed
+pub const DEFAULT_SWEEP_DELAY: u64 = 7 * 86400;
 
 //----------------------------------------------------------------------------------------
 // Storage types
@@ -18,8 +34,6 @@ pub struct Config {
     pub owner: Addr,
     ///  MARS token address
     pub mars_token_address: Addr,
-    /// Merkle roots used to verify is a terra user is eligible for the airdrop
-    pub merkle_roots: Vec<String>,
     /// Timestamp since which MARS airdrops can be delegated to bootstrap auction contract
     pub from_timestamp: u64,
     /// Timestamp till which MARS airdrops can be claimed
@@ -29,6 +43,29 @@ pub struct Config {
     /// Boolean value indicating if the users can withdraw their MARS airdrop tokens or not
     /// This value is updated in the same Tx in which Liquidity is added to the LP Pool
     pub are_claims_enabled: bool,
+    /// Timestamp at which claims were enabled. Vesting of the MARS airdrop starts at this timestamp
+    #[serde(default)]
+    pub claims_enabled_timestamp: u64,
+    /// Vesting schedule of the MARS airdrop. MARS is available for withdrawal at once if not set
+    #[serde(default)]
+    pub vesting: Option<VestingSchedule>,
+    /// Penalty applied to MARS withdrawn shortly after claims are enabled. Not applied if not set
+    #[serde(default)]
+    pub early_exit_penalty: Option<EarlyExitPenalty>,
+    /// Timestamp post which the bootstrap auction can be marked as failed if claims are not enabled
+    #[serde(default)]
+    pub auction_deadline: Option<u64>,
+    /// Boolean value indicating if the bootstrap auction has been marked as failed. Users can withdraw their
+    /// delegated MARS thereafter
+    #[serde(default)]
+    pub is_auction_failed: bool,
+    /// Number of seconds after which a proposed sweep of the unclaimed MARS can be executed
+    #[serde(default = "default_sweep_delay")]
+    pub sweep_delay: u64,
+}
+
+fn default_sweep_delay() -> u64 {
+    DEFAULT_SWEEP_DELAY
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -40,24 +77,101 @@ pub struct State {
     pub total_delegated_amount: Uint128,
     /// Total MARS tokens that are yet to be claimed by the users
     pub unclaimed_tokens: Uint128,
+    /// Number of merkle roots added to the contract. Used as the index of the next merkle root
+    #[serde(default)]
+    pub merkle_roots_count: u32,
+    /// Number of merkle roots carried over from the config upon migration. Claims made before the migration aren't
+    /// recorded per merkle root, hence users who claimed then can't claim against these merkle roots again
+    #[serde(default)]
+    pub legacy_merkle_roots_count: u32,
+    /// Total MARS tokens forfeited by users withdrawing within the early exit window
+    #[serde(default)]
+    pub total_penalty_amount: Uint128,
+    /// MARS tokens forfeited via early exits which have been added back to the unclaimed tokens
+    #[serde(default)]
+    pub recycled_penalty_amount: Uint128,
+    /// Number of users with an airdrop position. MARS claimed via indexed merkle roots once claims are enabled is
+    /// transferred without creating a position, hence not accounted for
+    #[serde(default)]
+    pub claimants_count: u64,
+    /// Number of users who withdrew MARS from their airdrop position
+    #[serde(default)]
+    pub withdrawers_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRoot {
+    /// Merkle root used to verify if a user is eligible for the airdrop
+    pub merkle_root: String,
+    /// Max MARS tokens which can be claimed against the merkle root. Only bound by the contract's unclaimed tokens if not set
+    pub budget: Option<Uint128>,
+    /// MARS tokens claimed against the merkle root
+    pub claimed_amount: Uint128,
+    /// Timestamp post which claims against the merkle root are not allowed
+    pub expiry_timestamp: Option<u64>,
+    /// Boolean value indicating if the merkle root has been disabled by the owner
+    pub is_disabled: bool,
+    /// Hashing scheme used to build the merkle tree. Merkle roots stored before hashing schemes were introduced are Legacy
+    #[serde(default = "legacy_hashing_scheme")]
+    pub hashing_scheme: HashingScheme,
+    /// Boolean value indicating if the leaves carry an index. Claims against indexed merkle roots are tracked in a bitmap
+    #[serde(default)]
+    pub is_indexed: bool,
+}
+
+fn legacy_hashing_scheme() -> HashingScheme {
+    HashingScheme::Legacy
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UserInfo {
     /// Total MARS airdrop tokens claimable by the user
     pub claimed_amount: Uint128,
+    /// MARS airdrop tokens credited to the user via claims made on behalf of EVM addresses
+    #[serde(default)]
+    pub evm_claimed_amount: Uint128,
+    /// MARS airdrop tokens credited to the user via claims against indexed merkle roots
+    #[serde(default)]
+    pub indexed_claimed_amount: Uint128,
+    /// MARS airdrop tokens credited to the user via claims recorded per (non-indexed) merkle root
+    #[serde(default)]
+    pub root_claimed_amount: Uint128,
     /// MARS tokens delegated to the bootstrap auction contract to add to the user's position
     pub delegated_amount: Uint128,
     /// Boolean value indicating if the user has withdrawn the remaining MARS tokens
     pub tokens_withdrawn: bool,
+    /// MARS tokens withdrawn by the user
+    #[serde(default)]
+    pub withdrawn_amount: Uint128,
 }
 
 impl Default for UserInfo {
     fn default() -> Self {
         UserInfo {
             claimed_amount: Uint128::zero(),
+            evm_claimed_amount: Uint128::zero(),
+            indexed_claimed_amount: Uint128::zero(),
+            root_claimed_amount: Uint128::zero(),
             delegated_amount: Uint128::zero(),
             tokens_withdrawn: false,
+            withdrawn_amount: Uint128::zero(),
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSweep {
+    /// Recipients of the unclaimed MARS tokens (validated addresses) along-with their amounts
+    pub recipients: Vec<SweepRecipient>,
+    /// Timestamp at which the sweep was proposed
+    pub proposed_timestamp: u64,
+    /// Timestamp since which the sweep can be executed
+    pub executable_timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    /// Merkle roots used to verify if a terra user is eligible for the airdrop. Not set once migrated
+    #[serde(default)]
+    pub merkle_roots: Vec<String>,
+}
@@ -1,10 +1,13 @@
 use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::{attr, to_binary, Addr, Timestamp, Uint128};
+use cosmwasm_std::{attr, to_binary, Addr, Binary, Decimal, Timestamp, Uint128};
 use cw20::Cw20ExecuteMsg;
+use mars_airdrop::crypto::{indexed_leaf_hash, leaf_hash, node_hash};
+use mars_airdrop::merkle_tree::{AirdropEntry, MerkleTree};
 use mars_periphery::{
     airdrop::{
-        ClaimResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-        StateResponse, UserInfoResponse,
+        ClaimResponse, ClaimedBitmapResponse, ConfigResponse, Cw20HookMsg, EarlyExitPenalty,
+        ExecuteMsg, HashingScheme, InstantiateMsg, MerkleRootsResponse, PendingSweepResponse,
+        QueryMsg, StateResponse, SweepRecipient, UserInfoResponse, UsersResponse, VestingSchedule,
     },
     auction::{ExecuteMsg as AuctionExecuteMsg, UpdateConfigMsg},
 };
@@ -67,9 +70,13 @@ fn init_contracts(app: &mut App) -> (Addr, Addr, InstantiateMsg, u64) {
     let aidrop_instantiate_msg = InstantiateMsg {
         owner: Some(owner.clone().to_string()),
         mars_token_address: mars_token_instance.clone().into_string(),
-        merkle_roots: Some(vec!["merkle_roots".to_string()]),
+        merkle_roots: None,
+        hashing_scheme: None,
         from_timestamp: Some(1571897419),
         to_timestamp: 1581797419,
+        vesting: None,
+        early_exit_penalty: None,
+        sweep_delay: None,
     };
 
     // Init contract
@@ -123,9 +130,10 @@ fn enable_claims(app: &mut App, airdrop_instance: Addr, owner: Addr) {
         &ExecuteMsg::UpdateConfig {
             owner: None,
             auction_contract_address: Some(auction_contract_address.clone()),
-            merkle_roots: None,
             from_timestamp: None,
             to_timestamp: None,
+            early_exit_penalty: None,
+            auction_deadline: None,
         },
         &[],
     )
@@ -146,6 +154,46 @@ fn enable_claims(app: &mut App, airdrop_instance: Addr, owner: Addr) {
     assert_eq!(true, resp.are_claims_allowed);
 }
 
+// Helper function. Instantiates the Bootstrap Auction contract, without setting it in the Airdrop contract
+fn init_auction(
+    app: &mut App,
+    owner: &Addr,
+    mars_instance: &Addr,
+    airdrop_instance: &Addr,
+) -> Addr {
+    let auction_contract = Box::new(ContractWrapper::new(
+        mars_auction::contract::execute,
+        mars_auction::contract::instantiate,
+        mars_auction::contract::query,
+    ));
+    let auction_contract_code_id = app.store_code(auction_contract);
+    let auction_init_msg = mars_periphery::auction::InstantiateMsg {
+        owner: owner.to_string(),
+        mars_token_address: mars_instance.to_string(),
+        astro_token_address: mars_instance.to_string(),
+        airdrop_contract_address: airdrop_instance.to_string(),
+        generator_contract: "generator_contract".to_string(),
+        lockdrop_contract_address: "lockdrop_contract_address".to_string(),
+        lp_tokens_vesting_duration: 2592000u64,
+        init_timestamp: 1571897419u64,
+        ust_deposit_window: 2592000u64,
+        mars_deposit_window: 2592000u64,
+        withdrawal_window: 1592000u64,
+        withdrawal_schedule: None,
+        mars_vesting_duration: 3600u64,
+    };
+
+    app.instantiate_contract(
+        auction_contract_code_id,
+        owner.clone(),
+        &auction_init_msg,
+        &[],
+        String::from("auction"),
+        None,
+    )
+    .unwrap()
+}
+
 #[test]
 fn proper_initialization() {
     let mut app = mock_app();
@@ -160,10 +208,22 @@ fn proper_initialization() {
     assert_eq!(init_msg.mars_token_address, resp.mars_token_address);
     assert_eq!(None, resp.auction_contract_address);
     assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
-    assert_eq!(init_msg.merkle_roots.unwrap(), resp.merkle_roots);
     assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
     assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
 
+    // Check merkle roots
+    let resp: MerkleRootsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert!(resp.merkle_roots.is_empty());
+
     // Check state
     let resp: StateResponse = app
         .wrap()
@@ -220,31 +280,29 @@ fn update_config() {
             &ExecuteMsg::UpdateConfig {
                 owner: None,
                 auction_contract_address: None,
-                merkle_roots: None,
                 from_timestamp: None,
                 to_timestamp: None,
+                early_exit_penalty: None,
+                auction_deadline: None,
             },
             &[],
         )
         .unwrap_err();
 
-    assert_eq!(
-        err.to_string(),
-        "Generic error: Only owner can update configuration"
-    );
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
 
     let new_owner = String::from("new_owner");
     let auction_contract_address = String::from("auction_contract_address");
-    let merkle_roots = vec!["new_merkle_roots".to_string()];
     let from_timestamp = 1571997419;
     let to_timestamp = 1591797419;
 
     let update_msg = ExecuteMsg::UpdateConfig {
         owner: Some(new_owner.clone()),
         auction_contract_address: Some(auction_contract_address.clone()),
-        merkle_roots: Some(merkle_roots.clone()),
         from_timestamp: Some(from_timestamp),
         to_timestamp: Some(to_timestamp),
+        early_exit_penalty: None,
+        auction_deadline: None,
     };
 
     // should be a success
@@ -267,151 +325,329 @@ fn update_config() {
         auction_contract_address,
         resp.auction_contract_address.unwrap().to_string()
     );
-    assert_eq!(merkle_roots, resp.merkle_roots);
     assert_eq!(from_timestamp, resp.from_timestamp);
     assert_eq!(to_timestamp, resp.to_timestamp);
 }
 
 #[cfg(test)]
 #[test]
-fn test_transfer_unclaimed_tokens() {
+fn test_merkle_root_lifecycle() {
     let mut app = mock_app();
-    let (airdrop_instance, mars_token_instance, init_msg, _) = init_contracts(&mut app);
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
 
-    // mint MARS for to Owner
-    mint_some_mars(
-        &mut app,
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        mars_token_instance.clone(),
-        Uint128::from(100_000_000_000u64),
-        init_msg.owner.clone().unwrap(),
+    // Merkle tree leaves :: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp250000000", ...
+    let merkle_root =
+        "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string();
+    let claim_msg = |root_index: u32| ExecuteMsg::Claim {
+        claim_amount: Uint128::from(250000000u64),
+        merkle_proof: vec![
+            "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+            "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+        ],
+        root_index,
+        leaf_index: None,
+    };
+
+    // Only owner can add merkle roots
+    let err = app
+        .execute_contract(
+            Addr::unchecked("wrong_owner"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::from(250000000u64),
+                expiry_timestamp: None,
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
+
+    // Invalid merkle root :: should fail
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: "merkle_root".to_string(),
+                budget: Uint128::from(250000000u64),
+                expiry_timestamp: None,
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid_merkle_root: Invalid merkle root");
+
+    // Zero budget :: should fail
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::zero(),
+                expiry_timestamp: None,
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "zero_amount: Amount must be greater than 0"
     );
 
-    // Set MARS airdrop incentives
-    app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        mars_token_instance.clone(),
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::from(100_000_000_000u64),
-            contract: airdrop_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
+    // Expiry in the past :: should fail
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::from(250000000u64),
+                expiry_timestamp: Some(1571797419u64),
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid_merkle_root_expiry: Invalid merkle root expiry timestamp"
+    );
 
-    // Check Airdrop Contract balance
-    let bal_resp: cw20::BalanceResponse = app
+    // Root 0 :: budget lower than the claim, Root 1 :: to be disabled,
+    // Root 2 :: expires before the claim, Root 3 / 4 :: valid
+    for (budget, expiry_timestamp) in vec![
+        (200000000u64, None),
+        (250000000u64, None),
+        (250000000u64, Some(1571897500u64)),
+        (250000000u64, None),
+        (250000000u64, None),
+    ] {
+        app.execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::from(budget),
+                expiry_timestamp,
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Check :: Merkle roots
+    let resp: MerkleRootsResponse = app
         .wrap()
         .query_wasm_smart(
-            &mars_token_instance.clone(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: airdrop_instance.clone().to_string(),
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: None,
+                limit: None,
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(100_000_000_000u64), bal_resp.balance);
+    assert_eq!(5, resp.merkle_roots.len());
+    assert_eq!(merkle_root, resp.merkle_roots[0].merkle_root);
+    assert_eq!(
+        Some(Uint128::from(200000000u64)),
+        resp.merkle_roots[0].budget
+    );
+    assert_eq!(Some(1571897500u64), resp.merkle_roots[2].expiry_timestamp);
 
-    // Can only be called by the owner
+    // Check :: Pagination
+    let resp: MerkleRootsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: Some(1u32),
+                limit: Some(1u32),
+            },
+        )
+        .unwrap();
+    assert_eq!(1, resp.merkle_roots.len());
+    assert_eq!(2u32, resp.merkle_roots[0].root_index);
+
+    // Only owner can disable merkle roots
     let err = app
         .execute_contract(
             Addr::unchecked("wrong_owner"),
             airdrop_instance.clone(),
-            &ExecuteMsg::TransferUnclaimedTokens {
-                recipient: "recipient".to_string(),
-                amount: Uint128::from(1000000 as u64),
-            },
+            &ExecuteMsg::DisableMerkleRoot { root_index: 1 },
             &[],
         )
         .unwrap_err();
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
 
-    assert_eq!(err.to_string(), "Generic error: Sender not authorized!");
-
-    // claim period is not over
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(1571897419)
-    });
-
-    // Can only be called after the claim period is over
+    // Invalid root index :: should fail
     let err = app
         .execute_contract(
-            Addr::unchecked(init_msg.owner.clone().unwrap()),
+            owner.clone(),
             airdrop_instance.clone(),
-            &ExecuteMsg::TransferUnclaimedTokens {
-                recipient: "recipient".to_string(),
-                amount: Uint128::from(1000000 as u64),
-            },
+            &ExecuteMsg::DisableMerkleRoot { root_index: 5 },
             &[],
         )
         .unwrap_err();
-
     assert_eq!(
         err.to_string(),
-        "Generic error: 9900000 seconds left before unclaimed tokens can be transferred"
+        "merkle_root_not_found: Incorrect Merkle Root Index"
     );
 
-    // claim period is over
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(1581797419)
-    });
+    // Disable Root 1 :: should be a success
+    let success_ = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::DisableMerkleRoot { root_index: 1 },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::DisableMerkleRoot")
+    );
+    assert_eq!(success_.events[1].attributes[2], attr("root_index", "1"));
 
-    // Amount needs to be less than unclaimed_tokens balance
+    // Already disabled :: should fail
     let err = app
         .execute_contract(
-            Addr::unchecked(init_msg.owner.clone().unwrap()),
+            owner.clone(),
             airdrop_instance.clone(),
-            &ExecuteMsg::TransferUnclaimedTokens {
-                recipient: "recipient".to_string(),
-                amount: Uint128::from(100_000_000_0000 as u64),
-            },
+            &ExecuteMsg::DisableMerkleRoot { root_index: 1 },
             &[],
         )
         .unwrap_err();
-
     assert_eq!(
         err.to_string(),
-        "Generic error: Amount cannot exceed unclaimed token balance"
+        "merkle_root_already_disabled: Merkle root already disabled"
     );
 
-    // Should successfully transfer and update state
+    // Set MARS airdrop incentives
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        owner.to_string(),
+    );
     app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        airdrop_instance.clone(),
-        &ExecuteMsg::TransferUnclaimedTokens {
-            recipient: "recipient".to_string(),
-            amount: Uint128::from(100_000_00 as u64),
+        owner.clone(),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
         },
         &[],
     )
     .unwrap();
 
-    let state_resp: StateResponse = app
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897501)
+    });
+
+    // Root 0 :: budget exceeded
+    let err = app
+        .execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(0), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "merkle_root_budget_exceeded: Merkle root budget exceeded"
+    );
+
+    // Root 1 :: disabled
+    let err = app
+        .execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(1), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "merkle_root_disabled: Merkle root is disabled"
+    );
+
+    // Root 2 :: expired
+    let err = app
+        .execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(2), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "merkle_root_expired: Merkle root has expired"
+    );
+
+    // Root 3 :: should be a success
+    app.execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(3), &[])
+        .unwrap();
+
+    // Root 3 :: already claimed
+    let err = app
+        .execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(3), &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "already_claimed: Already claimed");
+
+    // Root 4 :: claims are tracked per merkle root, should be a success
+    let resp: ClaimResponse = app
         .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some(user.to_string()),
+                root_index: Some(4),
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(false, resp.is_claimed);
+
+    app.execute_contract(user.clone(), airdrop_instance.clone(), &claim_msg(4), &[])
         .unwrap();
 
-    // Check config and make sure all fields are updated
+    // Check :: Merkle roots
+    let resp: MerkleRootsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), resp.merkle_roots[0].claimed_amount);
+    assert_eq!(true, resp.merkle_roots[1].is_disabled);
     assert_eq!(
-        Uint128::from(100_000_000_000u64),
-        state_resp.total_airdrop_size
+        Uint128::from(250000000u64),
+        resp.merkle_roots[3].claimed_amount
+    );
+    assert_eq!(
+        Uint128::from(250000000u64),
+        resp.merkle_roots[4].claimed_amount
     );
-    assert_eq!(Uint128::from(0u32), state_resp.total_delegated_amount);
-    assert_eq!(Uint128::from(99990000000u64), state_resp.unclaimed_tokens);
 }
 
 #[cfg(test)]
 #[test]
-fn test_claim_by_terra_user() {
+fn test_sweep_unclaimed_tokens() {
     let mut app = mock_app();
-    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let (airdrop_instance, mars_token_instance, init_msg, _) = init_contracts(&mut app);
 
     // mint MARS for to Owner
     mint_some_mars(
         &mut app,
         Addr::unchecked(init_msg.owner.clone().unwrap()),
-        mars_instance.clone(),
+        mars_token_instance.clone(),
         Uint128::from(100_000_000_000u64),
         init_msg.owner.clone().unwrap(),
     );
@@ -419,7 +655,7 @@ fn test_claim_by_terra_user() {
     // Set MARS airdrop incentives
     app.execute_contract(
         Addr::unchecked(init_msg.owner.clone().unwrap()),
-        mars_instance.clone(),
+        mars_token_instance.clone(),
         &Cw20ExecuteMsg::Send {
             amount: Uint128::from(100_000_000_000u64),
             contract: airdrop_instance.to_string(),
@@ -433,7 +669,7 @@ fn test_claim_by_terra_user() {
     let bal_resp: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &mars_instance,
+            &mars_token_instance.clone(),
             &cw20::Cw20QueryMsg::Balance {
                 address: airdrop_instance.clone().to_string(),
             },
@@ -441,33 +677,339 @@ fn test_claim_by_terra_user() {
         .unwrap();
     assert_eq!(Uint128::from(100_000_000_000u64), bal_resp.balance);
 
-    let merkle_roots =
-        vec!["cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string()];
-    let update_msg = ExecuteMsg::UpdateConfig {
-        owner: None,
-        auction_contract_address: None,
-        merkle_roots: Some(merkle_roots.clone()),
-        from_timestamp: None,
-        to_timestamp: None,
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let sweep_recipients = |treasury_amount: u64, community_pool_amount: u64| {
+        vec![
+            SweepRecipient {
+                address: "treasury".to_string(),
+                amount: Uint128::from(treasury_amount),
+            },
+            SweepRecipient {
+                address: "community_pool".to_string(),
+                amount: Uint128::from(community_pool_amount),
+            },
+        ]
     };
 
-    // Update Config :: should be a success
-    app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        airdrop_instance.clone(),
-        &update_msg,
-        &[],
-    )
-    .unwrap();
-
-    let resp: ConfigResponse = app
-        .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
-        .unwrap();
-
-    // Check config and make sure all fields are updated
+    // Can only be called by the owner
+    let err = app
+        .execute_contract(
+            Addr::unchecked("wrong_owner"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(600000, 400000),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
+
+    // claim period is not over
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897419)
+    });
+
+    // Can only be called after the claim period is over
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(600000, 400000),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "claim_period_not_over: 9900000 seconds left before unclaimed tokens can be transferred"
+    );
+
+    // claim period is over
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1581797419)
+    });
+
+    // Amount needs to be less than unclaimed_tokens balance
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(60_000_000_000, 40_000_000_001),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "sweep_exceeds_unclaimed: Amount cannot exceed unclaimed token balance"
+    );
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep { recipients: vec![] },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "no_sweep_recipients: No sweep recipients");
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(600000, 0),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "zero_amount: Amount must be greater than 0"
+    );
+
+    // Should successfully propose the sweep
+    let success_ = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(6_000_000, 4_000_000),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::ProposeSweep")
+    );
+    assert_eq!(success_.events[1].attributes[2], attr("amount", "10000000"));
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("executable_timestamp", "1582402219")
+    );
+
+    let pending_sweep: Option<PendingSweepResponse> = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::PendingSweep {})
+        .unwrap();
+    assert_eq!(
+        pending_sweep,
+        Some(PendingSweepResponse {
+            recipients: sweep_recipients(6_000_000, 4_000_000),
+            proposed_timestamp: 1581797419,
+            executable_timestamp: 1582402219,
+        })
+    );
+
+    // Only a single sweep can be pending
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ProposeSweep {
+                recipients: sweep_recipients(600000, 400000),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "sweep_already_pending: A sweep is already pending"
+    );
+
+    // Sweep can only be executed once the sweep delay has passed
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ExecuteSweep {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "sweep_delay_not_over: 604800 seconds left before the sweep can be executed"
+    );
+
+    // Only owner can cancel the pending sweep
+    let err = app
+        .execute_contract(
+            Addr::unchecked("wrong_owner"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::CancelSweep {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
+
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::CancelSweep {},
+        &[],
+    )
+    .unwrap();
+
+    let pending_sweep: Option<PendingSweepResponse> = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::PendingSweep {})
+        .unwrap();
+    assert_eq!(pending_sweep, None);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ExecuteSweep {},
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "no_pending_sweep: No pending sweep");
+
+    // Should successfully propose the sweep again and execute it once the sweep delay has passed
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::ProposeSweep {
+            recipients: sweep_recipients(6_000_000, 4_000_000),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1582402219)
+    });
+
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            airdrop_instance.clone(),
+            &ExecuteMsg::ExecuteSweep {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::ExecuteSweep")
+    );
+    assert_eq!(success_.events[1].attributes[2], attr("amount", "10000000"));
+
+    for (recipient, amount) in &[("treasury", 6_000_000u64), ("community_pool", 4_000_000u64)] {
+        let bal_resp: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &mars_token_instance,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: recipient.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(Uint128::from(*amount), bal_resp.balance);
+    }
+
+    let pending_sweep: Option<PendingSweepResponse> = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::PendingSweep {})
+        .unwrap();
+    assert_eq!(pending_sweep, None);
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+
+    // Check config and make sure all fields are updated
+    assert_eq!(
+        Uint128::from(100_000_000_000u64),
+        state_resp.total_airdrop_size
+    );
+    assert_eq!(Uint128::from(0u32), state_resp.total_delegated_amount);
+    assert_eq!(Uint128::from(99990000000u64), state_resp.unclaimed_tokens);
+}
+
+#[cfg(test)]
+#[test]
+fn test_claim_by_terra_user() {
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+
+    // mint MARS for to Owner
+    mint_some_mars(
+        &mut app,
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        init_msg.owner.clone().unwrap(),
+    );
+
+    // Set MARS airdrop incentives
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check Airdrop Contract balance
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: airdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(100_000_000_000u64), bal_resp.balance);
+
+    let merkle_root =
+        "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string();
+    let update_msg = ExecuteMsg::AddMerkleRoot {
+        merkle_root: merkle_root.clone(),
+        budget: Uint128::from(100_000_000_000u64),
+        expiry_timestamp: None,
+        hashing_scheme: Some(HashingScheme::Legacy),
+        is_indexed: None,
+    };
+
+    // Add Merkle Root :: should be a success
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        airdrop_instance.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+
+    // Check config and make sure all fields are updated
     assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
-    assert_eq!(merkle_roots, resp.merkle_roots);
     assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
     assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
 
@@ -484,6 +1026,8 @@ fn test_claim_by_terra_user() {
             "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
+        leaf_index: None,
     };
     let mut claim_msg_wrong_amount = ExecuteMsg::Claim {
         claim_amount: Uint128::from(210000000 as u64),
@@ -492,6 +1036,7 @@ fn test_claim_by_terra_user() {
             "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
     };
     let mut claim_msg_incorrect_proof = ExecuteMsg::Claim {
         claim_amount: Uint128::from(250000000 as u64),
@@ -500,6 +1045,7 @@ fn test_claim_by_terra_user() {
             "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
     };
 
     // ################################
@@ -515,7 +1061,7 @@ fn test_claim_by_terra_user() {
             &[],
         )
         .unwrap_err();
-    assert_eq!(claim_f.to_string(), "Generic error: Claim not allowed");
+    assert_eq!(claim_f.to_string(), "claim_not_allowed: Claim not allowed");
 
     // Update Block to test successful claim
     app.update_block(|b| {
@@ -535,6 +1081,7 @@ fn test_claim_by_terra_user() {
                     "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
                 ],
                 root_index: 5,
+                leaf_index: None,
             },
             &[],
         )
@@ -542,7 +1089,7 @@ fn test_claim_by_terra_user() {
 
     assert_eq!(
         claim_f.to_string(),
-        "Generic error: Incorrect Merkle Root Index"
+        "merkle_root_not_found: Incorrect Merkle Root Index"
     );
 
     // **** "Incorrect Merkle Proof" Error should be returned ****
@@ -555,7 +1102,10 @@ fn test_claim_by_terra_user() {
         )
         .unwrap_err();
 
-    assert_eq!(claim_f.to_string(), "Generic error: Incorrect Merkle Proof");
+    assert_eq!(
+        claim_f.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
 
     // **** "Incorrect Merkle Proof" Error should be returned ****
     claim_f = app
@@ -567,7 +1117,10 @@ fn test_claim_by_terra_user() {
         )
         .unwrap_err();
 
-    assert_eq!(claim_f.to_string(), "Generic error: Incorrect Merkle Proof");
+    assert_eq!(
+        claim_f.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
 
     // **** User should successfully claim the Airdrop ****
 
@@ -577,7 +1130,9 @@ fn test_claim_by_terra_user() {
         .query_wasm_smart(
             &airdrop_instance,
             &QueryMsg::HasUserClaimed {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+                address: Some("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+                root_index: None,
+                leaf_index: None,
             },
         )
         .unwrap();
@@ -612,7 +1167,9 @@ fn test_claim_by_terra_user() {
         .query_wasm_smart(
             &airdrop_instance,
             &QueryMsg::HasUserClaimed {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+                address: Some("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+                root_index: None,
+                leaf_index: None,
             },
         )
         .unwrap();
@@ -660,7 +1217,7 @@ fn test_claim_by_terra_user() {
             &[],
         )
         .unwrap_err();
-    assert_eq!(claim_f.to_string(), "Generic error: Already claimed");
+    assert_eq!(claim_f.to_string(), "already_claimed: Already claimed");
 
     // Enable MARS Withdrawals
     enable_claims(
@@ -680,6 +1237,7 @@ fn test_claim_by_terra_user() {
             "4fab6b0ef8d988835ad968d03d61de408772d033e9ce734394bb623309c5d7fc".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
     };
     claim_msg_wrong_amount = ExecuteMsg::Claim {
         claim_amount: Uint128::from(2 as u64),
@@ -688,6 +1246,7 @@ fn test_claim_by_terra_user() {
             "4fab6b0ef8d988835ad968d03d61de408772d033e9ce734394bb623309c5d7fc".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
     };
     claim_msg_incorrect_proof = ExecuteMsg::Claim {
         claim_amount: Uint128::from(1 as u64),
@@ -696,6 +1255,7 @@ fn test_claim_by_terra_user() {
             "4fab6b0ef8d988835ad968d03d61de408772d033e9ce734394bb623309c5d7fc".to_string(),
         ],
         root_index: 0,
+        leaf_index: None,
     };
 
     // **** "Incorrect Merkle Root Index" Error should be returned ****
@@ -710,6 +1270,7 @@ fn test_claim_by_terra_user() {
                     "4fab6b0ef8d988835ad968d03d61de408772d033e9ce734394bb623309c5d7fc".to_string(),
                 ],
                 root_index: 5,
+                leaf_index: None,
             },
             &[],
         )
@@ -717,7 +1278,7 @@ fn test_claim_by_terra_user() {
 
     assert_eq!(
         claim_f.to_string(),
-        "Generic error: Incorrect Merkle Root Index"
+        "merkle_root_not_found: Incorrect Merkle Root Index"
     );
 
     // **** "Incorrect Merkle Proof" Error should be returned ****
@@ -730,7 +1291,10 @@ fn test_claim_by_terra_user() {
         )
         .unwrap_err();
 
-    assert_eq!(claim_f.to_string(), "Generic error: Incorrect Merkle Proof");
+    assert_eq!(
+        claim_f.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
 
     // **** "Incorrect Merkle Proof" Error should be returned ****
     claim_f = app
@@ -742,7 +1306,10 @@ fn test_claim_by_terra_user() {
         )
         .unwrap_err();
 
-    assert_eq!(claim_f.to_string(), "Generic error: Incorrect Merkle Proof");
+    assert_eq!(
+        claim_f.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
 
     // **** User should successfully claim the Airdrop ****
 
@@ -752,7 +1319,9 @@ fn test_claim_by_terra_user() {
         .query_wasm_smart(
             &airdrop_instance,
             &QueryMsg::HasUserClaimed {
-                address: "terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string(),
+                address: Some("terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string()),
+                root_index: None,
+                leaf_index: None,
             },
         )
         .unwrap();
@@ -796,7 +1365,9 @@ fn test_claim_by_terra_user() {
         .query_wasm_smart(
             &airdrop_instance,
             &QueryMsg::HasUserClaimed {
-                address: "terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string(),
+                address: Some("terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string()),
+                root_index: None,
+                leaf_index: None,
             },
         )
         .unwrap();
@@ -841,7 +1412,7 @@ fn test_claim_by_terra_user() {
             &[],
         )
         .unwrap_err();
-    assert_eq!(claim_f.to_string(), "Generic error: Already claimed");
+    assert_eq!(claim_f.to_string(), "already_claimed: Already claimed");
 
     // Claim period has concluded
     app.update_block(|b| {
@@ -861,318 +1432,2325 @@ fn test_claim_by_terra_user() {
         .unwrap_err();
     assert_eq!(
         claim_f.to_string(),
-        "Generic error: Claim period has concluded"
+        "claim_period_concluded: Claim period has concluded"
     );
 }
 
 #[cfg(test)]
 #[test]
-fn test_enable_claims() {
+fn test_claim_with_v1_hashing_scheme() {
     let mut app = mock_app();
-    let (airdrop_instance, _, init_msg, _) = init_contracts(&mut app);
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
+    let chain_id = app.block_info().chain_id;
+
+    // Merkle tree with 2 leaves built via the V1 hashing scheme. Leaves are bound to the airdrop contract
+    let leaf = |account: &str, amount: u64| {
+        leaf_hash(
+            &HashingScheme::V1,
+            &chain_id,
+            airdrop_instance.as_str(),
+            account,
+            Uint128::from(amount),
+        )
+    };
+    let user_leaf = leaf(user.as_str(), 250000000u64);
+    let other_leaf = leaf("terra1qz2ty5l3sc5hfkq3zxqssjmzkwl2qt0c7hvs25", 50000000u64);
+    let merkle_root = hex::encode(node_hash(&HashingScheme::V1, &user_leaf, &other_leaf));
+    let merkle_proof = vec![hex::encode(other_leaf)];
+
+    // Root 0 :: Legacy hashing scheme, Root 1 :: V1 hashing scheme (default)
+    for hashing_scheme in vec![Some(HashingScheme::Legacy), None] {
+        app.execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::from(300000000u64),
+                expiry_timestamp: None,
+                hashing_scheme,
+                is_indexed: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Set MARS airdrop incentives
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        owner.to_string(),
+    );
+    app.execute_contract(
+        owner.clone(),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    // Proof isn't valid against the legacy hashing scheme
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::Claim {
+                claim_amount: Uint128::from(250000000u64),
+                merkle_proof: merkle_proof.clone(),
+                root_index: 0,
+                leaf_index: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
+
+    // Malformed proof :: should return an error
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::Claim {
+                claim_amount: Uint128::from(250000000u64),
+                merkle_proof: vec!["not_a_hash".to_string()],
+                root_index: 1,
+                leaf_index: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
+
+    // Incorrect amount :: should fail
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::Claim {
+                claim_amount: Uint128::from(25000000u64),
+                merkle_proof: merkle_proof.clone(),
+                root_index: 1,
+                leaf_index: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
+
+    // Should be a success
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::Claim {
+                claim_amount: Uint128::from(250000000u64),
+                merkle_proof,
+                root_index: 1,
+                leaf_index: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::Claim")
+    );
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("airdrop", "250000000")
+    );
+
+    // Check :: Merkle roots
+    let resp: MerkleRootsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(HashingScheme::Legacy, resp.merkle_roots[0].hashing_scheme);
+    assert_eq!(HashingScheme::V1, resp.merkle_roots[1].hashing_scheme);
+    assert_eq!(
+        Uint128::from(250000000u64),
+        resp.merkle_roots[1].claimed_amount
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_indexed_claims() {
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
+    let chain_id = app.block_info().chain_id;
+
+    // Indexed Merkle tree with 2 leaves (index 0 and 300) which allocate the airdrop to the same user
+    let leaf = |leaf_index: u64, amount: u64| {
+        indexed_leaf_hash(
+            &chain_id,
+            airdrop_instance.as_str(),
+            leaf_index,
+            user.as_str(),
+            Uint128::from(amount),
+        )
+    };
+    let first_leaf = leaf(0, 250000000u64);
+    let second_leaf = leaf(300, 50000000u64);
+    let merkle_root = hex::encode(node_hash(&HashingScheme::V1, &first_leaf, &second_leaf));
+
+    // Indexed merkle roots require the V1 hashing scheme
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::AddMerkleRoot {
+                merkle_root: merkle_root.clone(),
+                budget: Uint128::from(300000000u64),
+                expiry_timestamp: None,
+                hashing_scheme: Some(HashingScheme::Legacy),
+                is_indexed: Some(true),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "indexed_merkle_root_not_v_1: Indexed merkle roots require the V1 hashing scheme"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root,
+            budget: Uint128::from(300000000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::V1),
+            is_indexed: Some(true),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Set MARS airdrop incentives
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        owner.to_string(),
+    );
+    app.execute_contract(
+        owner.clone(),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    let claim_msg =
+        |claim_amount: u64, merkle_proof: [u8; 32], leaf_index: Option<u64>| ExecuteMsg::Claim {
+            claim_amount: Uint128::from(claim_amount),
+            merkle_proof: vec![hex::encode(merkle_proof)],
+            root_index: 0,
+            leaf_index,
+        };
+
+    // Leaf index not provided :: should return an error
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_msg(250000000u64, second_leaf, None),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "leaf_index_required: Leaf index is required by the merkle root"
+    );
+
+    // Incorrect leaf index :: should return an error
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_msg(250000000u64, second_leaf, Some(1)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
+
+    // Should be a success
+    app.execute_contract(
+        user.clone(),
+        airdrop_instance.clone(),
+        &claim_msg(250000000u64, second_leaf, Some(0)),
+        &[],
+    )
+    .unwrap();
+
+    // Leaf already claimed :: should return an error
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_msg(250000000u64, second_leaf, Some(0)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "already_claimed: Already claimed");
+
+    // Second allocation of the user :: should be a success
+    app.execute_contract(
+        user.clone(),
+        airdrop_instance.clone(),
+        &claim_msg(50000000u64, first_leaf, Some(300)),
+        &[],
+    )
+    .unwrap();
+
+    // Check :: Claim status of the leaves
+    for (leaf_index, expected) in vec![(0u64, true), (1u64, false), (300u64, true)] {
+        let resp: ClaimResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &airdrop_instance,
+                &QueryMsg::HasUserClaimed {
+                    address: None,
+                    root_index: Some(0),
+                    leaf_index: Some(leaf_index),
+                },
+            )
+            .unwrap();
+        assert_eq!(expected, resp.is_claimed);
+    }
+
+    // Either address or root_index along-with leaf_index is required
+    app.wrap()
+        .query_wasm_smart::<ClaimResponse, _, _>(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: None,
+                root_index: Some(0),
+                leaf_index: None,
+            },
+        )
+        .unwrap_err();
+
+    // Check :: Claimed bitmap words (leaf 300 is the bit 4 of the byte 5 of the word 1)
+    let resp: ClaimedBitmapResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::ClaimedBitmap {
+                root_index: 0,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(2, resp.words.len());
+    let mut first_word = vec![0u8; 32];
+    first_word[0] = 0x01;
+    let mut second_word = vec![0u8; 32];
+    second_word[5] = 0x10;
+    assert_eq!(0u64, resp.words[0].word_index);
+    assert_eq!(Binary::from(first_word), resp.words[0].bitmap);
+    assert_eq!(1u64, resp.words[1].word_index);
+    assert_eq!(Binary::from(second_word), resp.words[1].bitmap);
+
+    let resp: ClaimedBitmapResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::ClaimedBitmap {
+                root_index: 0,
+                start_after: Some(0),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(1, resp.words.len());
+    assert_eq!(1u64, resp.words[0].word_index);
+
+    // Check :: Both allocations are credited to the user (claims are not enabled yet)
+    let resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(300000000u64), resp.airdrop_amount);
+
+    // Check :: Indexed claims don't count as the user's legacy claim
+    let resp: ClaimResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some(user.to_string()),
+                root_index: None,
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(false, resp.is_claimed);
+
+    // Check :: Merkle roots
+    let resp: MerkleRootsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::MerkleRoots {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(true, resp.merkle_roots[0].is_indexed);
+    assert_eq!(
+        Uint128::from(300000000u64),
+        resp.merkle_roots[0].claimed_amount
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_evm_claim() {
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+
+    // mint MARS for to Owner
+    mint_some_mars(
+        &mut app,
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        init_msg.owner.clone().unwrap(),
+    );
+
+    // Set MARS airdrop incentives
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Merkle tree leaves :: "2c7536e3605d9c16a7a3d7b1898e529396a65c2350000000", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp250000000"
+    let merkle_root =
+        "763d5752e7cc2eb568ec689d0251bf4c7cfe90e4d76c3ee2f9dc709f100627d0".to_string();
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root,
+            budget: Uint128::from(300_000_000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::Legacy),
+            is_indexed: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    let eth_address = "2c7536e3605d9c16a7a3d7b1898e529396a65c23".to_string();
+    let recipient = "terra1qz2ty5l3sc5hfkq3zxqssjmzkwl2qt0c7hvs25".to_string();
+    // Signature of the recipient address generated by the EVM address (`personal_sign`)
+    let signature = "52c4492331c289a6902db3b025a593f120bf7dccbe32c29db480dda2a16ddd22499414c93dd3d5e2a3f83aee5157b8ee7ab7ce4f824fa007b9b67d1524374d141c".to_string();
+    let signed_msg_hash =
+        "c4d94d873a3b7d98b1a5cb4376e77603c42c7f58af189de83f9ea01a40cdad23".to_string();
+
+    let evm_claim_msg = ExecuteMsg::EvmClaim {
+        eth_address: eth_address.clone(),
+        claim_amount: Uint128::from(50000000u64),
+        merkle_proof: vec![
+            "6c20b319fb8cd5acd2e9c3a6da639cba7b9dde02361aad41f5c74c357d885b07".to_string(),
+        ],
+        root_index: 0,
+        signature: signature.clone(),
+        signed_msg_hash: signed_msg_hash.clone(),
+    };
+
+    // **** "Incorrect Merkle Proof" Error should be returned ****
+    let mut claim_f = app
+        .execute_contract(
+            Addr::unchecked(recipient.clone()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::EvmClaim {
+                eth_address: eth_address.clone(),
+                claim_amount: Uint128::from(60000000u64),
+                merkle_proof: vec![
+                    "6c20b319fb8cd5acd2e9c3a6da639cba7b9dde02361aad41f5c74c357d885b07".to_string(),
+                ],
+                root_index: 0,
+                signature: signature.clone(),
+                signed_msg_hash: signed_msg_hash.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        claim_f.to_string(),
+        "incorrect_merkle_proof: Incorrect Merkle Proof"
+    );
+
+    // **** "Signed message doesn't match the recipient address" Error should be returned ****
+    claim_f = app
+        .execute_contract(
+            Addr::unchecked("terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string()),
+            airdrop_instance.clone(),
+            &evm_claim_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        claim_f.to_string(),
+        "signed_msg_mismatch: Signed message doesn't match the recipient address"
+    );
+
+    // **** "Invalid signature" Error should be returned ****
+    claim_f = app
+        .execute_contract(
+            Addr::unchecked(recipient.clone()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::EvmClaim {
+                eth_address: eth_address.clone(),
+                claim_amount: Uint128::from(50000000u64),
+                merkle_proof: vec![
+                    "6c20b319fb8cd5acd2e9c3a6da639cba7b9dde02361aad41f5c74c357d885b07".to_string(),
+                ],
+                root_index: 0,
+                signature: signature[..128].to_string() + "1b",
+                signed_msg_hash: signed_msg_hash.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(claim_f.to_string(), "invalid_signature: Invalid signature");
+
+    // **** Recipient should successfully claim the Airdrop of the EVM address ****
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked(recipient.clone()),
+            airdrop_instance.clone(),
+            &evm_claim_msg,
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::EvmClaim")
+    );
+    assert_eq!(
+        success_.events[1].attributes[2],
+        attr("eth_address", eth_address.clone())
+    );
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("addr", recipient.clone())
+    );
+    assert_eq!(
+        success_.events[1].attributes[4],
+        attr("airdrop", "50000000")
+    );
+
+    // Check :: EVM address successfully claimed the airdrop
+    let claim_query_resp: ClaimResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some(eth_address.clone()),
+                root_index: None,
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(true, claim_query_resp.is_claimed);
+
+    // Check :: Recipient's own airdrop is not marked as claimed
+    let claim_query_resp: ClaimResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some(recipient.clone()),
+                root_index: None,
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(false, claim_query_resp.is_claimed);
+
+    // Check :: Recipient state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: recipient.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(50000000u64),
+        user_info_query_resp.airdrop_amount
+    );
+    assert_eq!(Uint128::from(0u64), user_info_query_resp.delegated_amount);
+    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+
+    // **** "Already claimed" Error should be returned ****
+    claim_f = app
+        .execute_contract(
+            Addr::unchecked(recipient.clone()),
+            airdrop_instance.clone(),
+            &evm_claim_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(claim_f.to_string(), "already_claimed: Already claimed");
+
+    // Enable MARS Withdrawals
+    enable_claims(
+        &mut app,
+        Addr::unchecked(airdrop_instance.clone()),
+        Addr::unchecked(init_msg.owner.unwrap()),
+    );
+
+    // Recipient withdraws MARS credited via the EVM claim
+    app.execute_contract(
+        Addr::unchecked(recipient.clone()),
+        airdrop_instance.clone(),
+        &ExecuteMsg::WithdrawAirdropReward {},
+        &[],
+    )
+    .unwrap();
+
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: recipient.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(50000000u64), bal_resp.balance);
+
+    // Check :: Contract state
+    let state_query_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(99950000000u64),
+        state_query_resp.unclaimed_tokens
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_enable_claims() {
+    let mut app = mock_app();
+    let (airdrop_instance, _, init_msg, _) = init_contracts(&mut app);
     let auction_contract_address = String::from("auction_contract_address");
 
-    // should successfully set auction contract address
+    // should successfully set auction contract address
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.unwrap()),
+        airdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            auction_contract_address: Some(auction_contract_address.clone()),
+            from_timestamp: None,
+            to_timestamp: None,
+            early_exit_penalty: None,
+            auction_deadline: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::EnableClaims {};
+
+    // ###### Should give "Unauthorized" Error ######
+
+    let mut resp_f = app
+        .execute_contract(
+            Addr::unchecked("not_bootstrap_auction_contract".to_string()),
+            airdrop_instance.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(resp_f.to_string(), "unauthorized: Unauthorized");
+
+    // ###### Should successfully enable claims ######
+
+    app.execute_contract(
+        Addr::unchecked(auction_contract_address.clone()),
+        airdrop_instance.clone(),
+        &msg,
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(true, resp.are_claims_allowed);
+
+    // ###### Should give "Claims already enabled" Error ######
+
+    resp_f = app
+        .execute_contract(
+            Addr::unchecked(auction_contract_address.clone()),
+            airdrop_instance.clone(),
+            &msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        resp_f.to_string(),
+        "claims_already_enabled: Claims already enabled"
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_withdraw_airdrop_rewards() {
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+
+    // mint MARS for to Owner
+    mint_some_mars(
+        &mut app,
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        init_msg.owner.clone().unwrap(),
+    );
+
+    // Set MARS airdrop incentives
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check Airdrop Contract balance
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: airdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(100_000_000_000u64), bal_resp.balance);
+
+    let merkle_root =
+        "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string();
+
+    let update_msg = ExecuteMsg::AddMerkleRoot {
+        merkle_root: merkle_root.clone(),
+        budget: Uint128::from(100_000_000_000u64),
+        expiry_timestamp: None,
+        hashing_scheme: Some(HashingScheme::Legacy),
+        is_indexed: None,
+    };
+
+    // Add Merkle Root :: should be a success
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        airdrop_instance.clone(),
+        &update_msg,
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+
+    // Check config and make sure all fields are updated
+    assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
+    assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
+    assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
+
+    // Update Block to test successful claim
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    // ################################
+    // TERRA USER :: Claims not allowed. MARS Rewards will Not be transferred to the user
+    // ################################
+
+    let claim_msg = ExecuteMsg::Claim {
+        claim_amount: Uint128::from(250000000 as u64),
+        merkle_proof: vec![
+            "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+            "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+        ],
+        root_index: 0,
+        leaf_index: None,
+    };
+
+    // **** User should successfully claim the Airdrop ****
+
+    // Check :: User hasn't yet claimed the airdrop
+    let resp: ClaimResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+                root_index: None,
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(false, resp.is_claimed);
+
+    // Should be a success
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            airdrop_instance.clone(),
+            &claim_msg,
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::Claim")
+    );
+    assert_eq!(
+        success_.events[1].attributes[2],
+        attr("addr", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
+    );
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("airdrop", "250000000")
+    );
+
+    // Check :: Terra User successfully claimed the airdrop
+    let claim_query_resp: ClaimResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::HasUserClaimed {
+                address: Some("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+                root_index: None,
+                leaf_index: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(true, claim_query_resp.is_claimed);
+
+    // Check :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(250000000u64),
+        user_info_query_resp.airdrop_amount
+    );
+    assert_eq!(Uint128::from(0u64), user_info_query_resp.delegated_amount);
+    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+
+    // Check :: Contract state
+    let state_query_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(100_000_000_000u64),
+        state_query_resp.total_airdrop_size
+    );
+    assert_eq!(Uint128::from(0u64), state_query_resp.total_delegated_amount);
+    assert_eq!(
+        Uint128::from(99750000000u64),
+        state_query_resp.unclaimed_tokens
+    );
+
+    // **** "Already claimed" Error should be returned ****
+
+    let claim_f = app
+        .execute_contract(
+            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            airdrop_instance.clone(),
+            &claim_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(claim_f.to_string(), "already_claimed: Already claimed");
+
+    // #################
+    // ENABLE CLAIMS ::
+    // #################
+
+    // Enable MARS Withdrawals
+    enable_claims(
+        &mut app,
+        Addr::unchecked(airdrop_instance.clone()),
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+    );
+
+    // **** "No airdrop claimed by the user" Error should be returned ****
+
+    let withdraw_f = app
+        .execute_contract(
+            Addr::unchecked("not_a_claimant".to_string()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawAirdropReward {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        withdraw_f.to_string(),
+        "no_airdrop_claimed: No airdrop claimed by the user"
+    );
+
+    // Should be a success
+    app.execute_contract(
+        Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+        airdrop_instance.clone(),
+        &ExecuteMsg::WithdrawAirdropReward {},
+        &[],
+    )
+    .unwrap();
+
+    // Check :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(250000000u64),
+        user_info_query_resp.airdrop_amount
+    );
+    assert_eq!(Uint128::from(0u64), user_info_query_resp.delegated_amount);
+    assert_eq!(true, user_info_query_resp.tokens_withdrawn);
+}
+
+#[cfg(test)]
+#[test]
+fn test_withdraw_vested() {
+    let mut app = mock_app();
+    let (_, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
+
+    // Instantiate Airdrop Contract with a vesting schedule
+    let airdrop_contract = Box::new(ContractWrapper::new(
+        mars_airdrop::contract::execute,
+        mars_airdrop::contract::instantiate,
+        mars_airdrop::contract::query,
+    ));
+    let airdrop_code_id = app.store_code(airdrop_contract);
+
+    let vesting = VestingSchedule {
+        cliff: 100u64,
+        duration: 1000u64,
+    };
+
+    // Invalid vesting schedule :: should fail
+    let err = app
+        .instantiate_contract(
+            airdrop_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                merkle_roots: Some(vec![
+                    "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string(),
+                ]),
+                vesting: Some(VestingSchedule {
+                    cliff: 1001u64,
+                    duration: 1000u64,
+                }),
+                early_exit_penalty: None,
+                ..init_msg.clone()
+            },
+            &[],
+            "airdrop",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid_vesting_schedule: Invalid vesting schedule"
+    );
+
+    let airdrop_instance = app
+        .instantiate_contract(
+            airdrop_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                merkle_roots: Some(vec![
+                    "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string(),
+                ]),
+                vesting: Some(vesting.clone()),
+                early_exit_penalty: None,
+                ..init_msg.clone()
+            },
+            &[],
+            "airdrop",
+            None,
+        )
+        .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(vesting), resp.vesting);
+    assert_eq!(0u64, resp.claims_enabled_timestamp);
+
+    // mint MARS for to Owner and set MARS airdrop incentives
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        owner.to_string(),
+    );
+    app.execute_contract(
+        owner.clone(),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    // User claims the airdrop while claims are not enabled
+    app.execute_contract(
+        user.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::Claim {
+            claim_amount: Uint128::from(250000000u64),
+            merkle_proof: vec![
+                "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+                "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+            ],
+            root_index: 0,
+            leaf_index: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Claims not enabled :: should fail
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "auction_in_progress: LP bootstrap auction in progress. Claims not allowed during this period"
+    );
+
+    // Enable MARS Withdrawals. Vesting starts now
+    enable_claims(&mut app, airdrop_instance.clone(), owner.clone());
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(1571897424u64, resp.claims_enabled_timestamp);
+
+    // Vested airdrop can't be withdrawn via WithdrawAirdropReward :: should fail
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawAirdropReward {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "vesting_enabled: MARS airdrop is subject to vesting. Use WithdrawVested"
+    );
+
+    // Cliff not reached :: should fail
+    app.update_block(|b| b.time = Timestamp::from_seconds(1571897474));
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no_vested_tokens: No vested tokens to withdraw"
+    );
+
+    // 25% of the vesting duration has passed :: should be a success
+    app.update_block(|b| b.time = Timestamp::from_seconds(1571897674));
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::WithdrawVested")
+    );
+    assert_eq!(success_.events[1].attributes[2], attr("user", user.clone()));
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("withdrawn_amount", "62500000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[4],
+        attr("total_withdrawn", "62500000")
+    );
+
+    // Check :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(62500000u64),
+        user_info_query_resp.withdrawn_amount
+    );
+    assert_eq!(
+        Uint128::from(62500000u64),
+        user_info_query_resp.vested_amount
+    );
+    assert_eq!(
+        Uint128::from(187500000u64),
+        user_info_query_resp.locked_amount
+    );
+    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+
+    // Vesting duration has passed :: should be a success
+    app.update_block(|b| b.time = Timestamp::from_seconds(1571898424));
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("withdrawn_amount", "187500000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[4],
+        attr("total_withdrawn", "250000000")
+    );
+
+    // Check :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(250000000u64),
+        user_info_query_resp.withdrawn_amount
+    );
+    assert_eq!(Uint128::zero(), user_info_query_resp.locked_amount);
+    assert_eq!(true, user_info_query_resp.tokens_withdrawn);
+
+    // Check :: User's MARS balance
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(250000000u64), bal_resp.balance);
+
+    // Nothing left to withdraw :: should fail
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no_vested_tokens: No vested tokens to withdraw"
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_early_exit_penalty() {
+    let mut app = mock_app();
+    let (_, mars_instance, init_msg, _) = init_contracts(&mut app);
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
+    let treasury = Addr::unchecked("treasury");
+
+    let airdrop_contract = Box::new(ContractWrapper::new(
+        mars_airdrop::contract::execute,
+        mars_airdrop::contract::instantiate,
+        mars_airdrop::contract::query,
+    ));
+    let airdrop_code_id = app.store_code(airdrop_contract);
+
+    let instantiate_airdrop = |app: &mut App, early_exit_penalty: Option<EarlyExitPenalty>| {
+        app.instantiate_contract(
+            airdrop_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                merkle_roots: Some(vec![
+                    "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string(),
+                ]),
+                early_exit_penalty,
+                ..init_msg.clone()
+            },
+            &[],
+            "airdrop",
+            None,
+        )
+    };
+
+    let mars_balance = |app: &App, address: &Addr| {
+        let bal_resp: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &mars_instance,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        bal_resp.balance
+    };
+
+    // Invalid penalty rate :: should fail
+    let err = instantiate_airdrop(
+        &mut app,
+        Some(EarlyExitPenalty {
+            window: 1000u64,
+            penalty_rate: Decimal::percent(150),
+            treasury_address: None,
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid_early_exit_penalty: Invalid early exit penalty"
+    );
+
+    // Airdrop 1 :: forfeited MARS is transferred to the treasury
+    let treasury_airdrop = instantiate_airdrop(
+        &mut app,
+        Some(EarlyExitPenalty {
+            window: 1000u64,
+            penalty_rate: Decimal::percent(10),
+            treasury_address: Some(treasury.to_string()),
+        }),
+    )
+    .unwrap();
+
+    // Airdrop 2 :: forfeited MARS is added back to the unclaimed tokens. Penalty is set via UpdateConfig
+    let recycling_airdrop = instantiate_airdrop(&mut app, None).unwrap();
+    app.execute_contract(
+        owner.clone(),
+        recycling_airdrop.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            auction_contract_address: None,
+            from_timestamp: None,
+            to_timestamp: None,
+            early_exit_penalty: Some(EarlyExitPenalty {
+                window: 1000u64,
+                penalty_rate: Decimal::percent(20),
+                treasury_address: None,
+            }),
+            auction_deadline: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    for airdrop_instance in vec![treasury_airdrop.clone(), recycling_airdrop.clone()] {
+        // Set MARS airdrop incentives
+        mint_some_mars(
+            &mut app,
+            owner.clone(),
+            mars_instance.clone(),
+            Uint128::from(100_000_000_000u64),
+            owner.to_string(),
+        );
+        app.execute_contract(
+            owner.clone(),
+            mars_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                amount: Uint128::from(100_000_000_000u64),
+                contract: airdrop_instance.to_string(),
+                msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::Claim {
+                claim_amount: Uint128::from(250000000u64),
+                merkle_proof: vec![
+                    "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+                    "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+                ],
+                root_index: 0,
+                leaf_index: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        enable_claims(&mut app, airdrop_instance, owner.clone());
+    }
+
+    // Penalty can't be changed once claims are enabled :: should fail
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            treasury_airdrop.clone(),
+            &ExecuteMsg::UpdateConfig {
+                owner: None,
+                auction_contract_address: None,
+                from_timestamp: None,
+                to_timestamp: None,
+                early_exit_penalty: Some(EarlyExitPenalty {
+                    window: 1u64,
+                    penalty_rate: Decimal::zero(),
+                    treasury_address: None,
+                }),
+                auction_deadline: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "early_exit_penalty_locked: Early exit penalty can't be changed once claims are enabled"
+    );
+
+    // Withdrawals within the early exit window are penalized
+    app.update_block(|b| b.time = Timestamp::from_seconds(1571897924));
+
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            treasury_airdrop.clone(),
+            &ExecuteMsg::WithdrawAirdropReward {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("claimed_amount", "250000000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[5],
+        attr("penalty_amount", "25000000")
+    );
+    assert_eq!(Uint128::from(225000000u64), mars_balance(&app, &user));
+    assert_eq!(Uint128::from(25000000u64), mars_balance(&app, &treasury));
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&treasury_airdrop, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(25000000u64), state_resp.total_penalty_amount);
+    assert_eq!(Uint128::zero(), state_resp.recycled_penalty_amount);
+    assert_eq!(Uint128::from(99750000000u64), state_resp.unclaimed_tokens);
+
+    app.execute_contract(
+        user.clone(),
+        recycling_airdrop.clone(),
+        &ExecuteMsg::WithdrawAirdropReward {},
+        &[],
+    )
+    .unwrap();
+    assert_eq!(Uint128::from(425000000u64), mars_balance(&app, &user));
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&recycling_airdrop, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(50000000u64), state_resp.total_penalty_amount);
+    assert_eq!(
+        Uint128::from(50000000u64),
+        state_resp.recycled_penalty_amount
+    );
+    assert_eq!(Uint128::from(99800000000u64), state_resp.unclaimed_tokens);
+}
+
+#[cfg(test)]
+#[test]
+fn test_delegate_mars_to_bootstrap_auction() {
+    use astroport::asset::AssetInfo;
+
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, token_code_id) = init_contracts(&mut app);
+
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+
+    // mint MARS for to Owner
+    mint_some_mars(
+        &mut app,
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        init_msg.owner.clone().unwrap(),
+    );
+
+    // Set MARS airdrop incentives
+    app.execute_contract(
+        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pair_contract = Box::new(
+        ContractWrapper::new(
+            astroport_pair::contract::execute,
+            astroport_pair::contract::instantiate,
+            astroport_pair::contract::query,
+        )
+        .with_reply(astroport_pair::contract::reply),
+    );
+
+    let pair_code_id = app.store_code(pair_contract);
+
+    let init_pair_msg = astroport::pair::InstantiateMsg {
+        asset_infos: [
+            AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            AssetInfo::Token {
+                contract_addr: mars_instance.clone(),
+            },
+        ],
+        factory_addr: Addr::unchecked("factory_addr"),
+        init_params: None,
+        token_code_id,
+    };
+    let pair_instance = app
+        .instantiate_contract(
+            pair_code_id,
+            owner.clone(),
+            &init_pair_msg,
+            &[],
+            String::from("pair"),
+            None,
+        )
+        .unwrap();
+
+    // Initialize Bootstrap Auction contract
+    let auction_contract = Box::new(ContractWrapper::new(
+        mars_auction::contract::execute,
+        mars_auction::contract::instantiate,
+        mars_auction::contract::query,
+    ));
+    let auction_contract_code_id = app.store_code(auction_contract);
+    let auction_init_msg = mars_periphery::auction::InstantiateMsg {
+        owner: init_msg.owner.clone().unwrap(),
+        mars_token_address: mars_instance.clone().to_string(),
+        astro_token_address: mars_instance.clone().to_string(),
+        airdrop_contract_address: airdrop_instance.clone().to_string(),
+        generator_contract: "generator_contract".to_string(),
+        lockdrop_contract_address: "lockdrop_contract_address".to_string(),
+        lp_tokens_vesting_duration: 2592000u64,
+        init_timestamp: 1571897419u64,
+        ust_deposit_window: 2592000u64,
+        mars_deposit_window: 2592000u64,
+        withdrawal_window: 1592000u64,
+        withdrawal_schedule: None,
+        mars_vesting_duration: 3600u64,
+    };
+
+    let auction_contract_instance = app
+        .instantiate_contract(
+            auction_contract_code_id,
+            Addr::unchecked(init_msg.owner.clone().unwrap()),
+            &auction_init_msg,
+            &[],
+            String::from("MARS"),
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        auction_contract_instance.clone(),
+        &AuctionExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                astroport_lp_pool: Some(pair_instance.to_string()),
+                owner: None,
+                generator_contract: None,
+                mars_lp_staking_contract: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let merkle_root =
+        "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string();
+
+    let update_msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        auction_contract_address: Some(auction_contract_instance.to_string()),
+        from_timestamp: None,
+        to_timestamp: None,
+        early_exit_penalty: None,
+        auction_deadline: None,
+    };
+
+    // Update Config :: should be a success
+    app.execute_contract(owner.clone(), airdrop_instance.clone(), &update_msg, &[])
+        .unwrap();
+
+    // Add Merkle Root :: should be a success
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root,
+            budget: Uint128::from(100_000_000_000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::Legacy),
+            is_indexed: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+
+    // Check config and make sure all fields are updated
+    assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
+    assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
+    assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
+
+    // Update Block to test successful claim
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    // ################################
+    // TERRA USER :: Claims not allowed. MARS Rewards will Not be transferred to the user
+    // ################################
+
+    let claim_msg = ExecuteMsg::Claim {
+        claim_amount: Uint128::from(250000000 as u64),
+        merkle_proof: vec![
+            "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+            "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+        ],
+        root_index: 0,
+        leaf_index: None,
+    };
+
+    // **** User should successfully claim the Airdrop ****
+
+    // Should be a success
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            airdrop_instance.clone(),
+            &claim_msg,
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::Claim")
+    );
+    assert_eq!(
+        success_.events[1].attributes[2],
+        attr("addr", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
+    );
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("airdrop", "250000000")
+    );
+
+    // **** "Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance" Error should be returned ****
+
+    let claim_f = app
+        .execute_contract(
+            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::DelegateMarsToBootstrapAuction {
+                amount_to_delegate: Uint128::from(250000001u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        claim_f.to_string(),
+        "delegation_exceeds_balance: Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance"
+    );
+
+    // **** Should successfully delegate MARS ****
+
+    let delegation_res = app
+        .execute_contract(
+            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::DelegateMarsToBootstrapAuction {
+                amount_to_delegate: Uint128::from(250000000u64),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        delegation_res.events[1].attributes[1],
+        attr(
+            "action",
+            "Airdrop::ExecuteMsg::DelegateMarsToBootstrapAuction"
+        )
+    );
+    assert_eq!(
+        delegation_res.events[1].attributes[2],
+        attr("user", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
+    );
+    assert_eq!(
+        delegation_res.events[1].attributes[3],
+        attr("amount_delegated", "250000000")
+    );
+
+    // Check :: Airdrop :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(250000000u64),
+        user_info_query_resp.airdrop_amount
+    );
+    assert_eq!(
+        Uint128::from(250000000u64),
+        user_info_query_resp.delegated_amount
+    );
+    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+
+    // Check :: Airdrop :: Contract state
+    let state_query_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(100_000_000_000u64),
+        state_query_resp.total_airdrop_size
+    );
+    assert_eq!(
+        Uint128::from(250000000u64),
+        state_query_resp.total_delegated_amount
+    );
+    assert_eq!(
+        Uint128::from(99750000000u64),
+        state_query_resp.unclaimed_tokens
+    );
+
+    // **** "No airdrop claimed by the user" Error should be returned ****
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95".to_string()),
+            airdrop_instance.clone(),
+            &ExecuteMsg::DelegateMarsToBootstrapAuction {
+                amount_to_delegate: Uint128::from(1u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no_airdrop_claimed: No airdrop claimed by the user"
+    );
+
+    // ################################
+    // USER #2 :: Claims the airdrop and delegates part of it in a single tx
+    // ################################
+
+    let user = Addr::unchecked("terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95");
+    let chain_id = app.block_info().chain_id;
+    let leaf = |account: &str, amount: u64| {
+        leaf_hash(
+            &HashingScheme::V1,
+            &chain_id,
+            airdrop_instance.as_str(),
+            account,
+            Uint128::from(amount),
+        )
+    };
+    let user_leaf = leaf(user.as_str(), 100000000u64);
+    let other_leaf = leaf("terra1qz2ty5l3sc5hfkq3zxqssjmzkwl2qt0c7hvs25", 50000000u64);
+
     app.execute_contract(
-        Addr::unchecked(init_msg.owner.unwrap()),
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root: hex::encode(node_hash(&HashingScheme::V1, &user_leaf, &other_leaf)),
+            budget: Uint128::from(150000000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::V1),
+            is_indexed: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let claim_and_delegate_msg = |delegate_amount: u64| ExecuteMsg::ClaimAndDelegate {
+        claim_amount: Uint128::from(100000000u64),
+        merkle_proof: vec![hex::encode(other_leaf)],
+        root_index: 1,
+        leaf_index: None,
+        delegate_amount: Uint128::from(delegate_amount),
+    };
+
+    // **** "Amount to delegate cannot exceed the airdrop being claimed" Error should be returned ****
+
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_and_delegate_msg(100000001u64),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "delegation_exceeds_claim: Amount to delegate cannot exceed the airdrop being claimed"
+    );
+
+    // **** Should successfully claim and delegate MARS ****
+
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_and_delegate_msg(40000000u64),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Airdrop::ExecuteMsg::ClaimAndDelegate")
+    );
+    assert_eq!(success_.events[1].attributes[2], attr("addr", user.clone()));
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("airdrop", "100000000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[4],
+        attr("amount_delegated", "40000000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[5],
+        attr("action", "Airdrop::ExecuteMsg::Claim")
+    );
+    assert_eq!(success_.events[1].attributes[6], attr("addr", user.clone()));
+    assert_eq!(
+        success_.events[1].attributes[7],
+        attr("airdrop", "100000000")
+    );
+
+    // **** "Already claimed" Error should be returned ****
+
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &claim_and_delegate_msg(40000000u64),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "already_claimed: Already claimed");
+
+    // Check :: Airdrop :: User state
+    let user_info_query_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(100000000u64),
+        user_info_query_resp.airdrop_amount
+    );
+    assert_eq!(
+        Uint128::from(40000000u64),
+        user_info_query_resp.delegated_amount
+    );
+
+    // Check :: Airdrop :: Contract state
+    let state_query_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(290000000u64),
+        state_query_resp.total_delegated_amount
+    );
+    assert_eq!(
+        Uint128::from(99650000000u64),
+        state_query_resp.unclaimed_tokens
+    );
+}
+
+#[test]
+fn test_auction_failure() {
+    let mut app = mock_app();
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+
+    let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
+    let user = Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp");
+
+    // mint MARS for to Owner
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_instance.clone(),
+        Uint128::from(100_000_000_000u64),
+        owner.to_string(),
+    );
+
+    // Set MARS airdrop incentives
+    app.execute_contract(
+        owner.clone(),
+        mars_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(100_000_000_000u64),
+            contract: airdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Initialize Bootstrap Auction contract. Liquidity is never added to the pool
+    let auction_instance = init_auction(&mut app, &owner, &mars_instance, &airdrop_instance);
+
+    // **** "Auction contract not set" Error should be returned ****
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::MarkAuctionFailed {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "auction_not_set: Auction contract not set");
+
+    // **** "Invalid auction deadline" Error should be returned ****
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                owner: None,
+                auction_contract_address: Some(auction_instance.to_string()),
+                from_timestamp: None,
+                to_timestamp: None,
+                early_exit_penalty: None,
+                auction_deadline: Some(app.block_info().time.seconds()),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid_auction_deadline: Invalid auction deadline"
+    );
+
+    app.execute_contract(
+        owner.clone(),
         airdrop_instance.clone(),
         &ExecuteMsg::UpdateConfig {
             owner: None,
-            auction_contract_address: Some(auction_contract_address.clone()),
-            merkle_roots: None,
+            auction_contract_address: Some(auction_instance.to_string()),
             from_timestamp: None,
             to_timestamp: None,
+            early_exit_penalty: None,
+            auction_deadline: Some(1575000000u64),
         },
         &[],
     )
     .unwrap();
 
-    let msg = ExecuteMsg::EnableClaims {};
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root: "cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e"
+                .to_string(),
+            budget: Uint128::from(100_000_000_000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::Legacy),
+            is_indexed: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    // ###### Should give "Unauthorized" Error ######
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(1575000000u64), resp.auction_deadline);
+    assert_eq!(false, resp.is_auction_failed);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1571897424)
+    });
+
+    // User claims the airdrop and delegates part of it to the bootstrap auction
+    app.execute_contract(
+        user.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::Claim {
+            claim_amount: Uint128::from(250000000u64),
+            merkle_proof: vec![
+                "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
+                "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
+            ],
+            root_index: 0,
+            leaf_index: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::DelegateMarsToBootstrapAuction {
+            amount_to_delegate: Uint128::from(100000000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // **** Auction deadline can only be increased once delegations are live ****
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                owner: None,
+                auction_contract_address: None,
+                from_timestamp: None,
+                to_timestamp: None,
+                early_exit_penalty: None,
+                auction_deadline: Some(1574000000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "auction_deadline_decreased: When auction delegations are live auction_deadline can only be increased"
+    );
+
+    // **** Delegated MARS can't be withdrawn while the auction is in progress ****
 
-    let mut resp_f = app
+    let err = app
         .execute_contract(
-            Addr::unchecked("not_bootstrap_auction_contract".to_string()),
+            user.clone(),
             airdrop_instance.clone(),
-            &msg,
+            &ExecuteMsg::WithdrawAirdropReward {},
             &[],
         )
         .unwrap_err();
-    assert_eq!(resp_f.to_string(), "Generic error: Unauthorized");
-
-    // ###### Should successfully enable claims ######
+    assert_eq!(
+        err.to_string(),
+        "auction_in_progress: LP bootstrap auction in progress. Claims not allowed during this period"
+    );
 
-    app.execute_contract(
-        Addr::unchecked(auction_contract_address.clone()),
-        airdrop_instance.clone(),
-        &msg,
-        &[],
-    )
-    .unwrap();
+    // **** Only owner can mark the auction as failed ****
 
-    let resp: ConfigResponse = app
-        .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
-        .unwrap();
-    assert_eq!(true, resp.are_claims_allowed);
+    let err = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::MarkAuctionFailed {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
 
-    // ###### Should give "Claims already enabled" Error ######
+    // **** "Auction deadline not reached" Error should be returned ****
 
-    resp_f = app
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_contract_address.clone()),
+            owner.clone(),
             airdrop_instance.clone(),
-            &msg,
+            &ExecuteMsg::MarkAuctionFailed {},
             &[],
         )
         .unwrap_err();
-    assert_eq!(resp_f.to_string(), "Generic error: Claims already enabled");
-}
+    assert_eq!(
+        err.to_string(),
+        "auction_deadline_not_reached: Auction deadline not reached"
+    );
 
-#[cfg(test)]
-#[test]
-fn test_withdraw_airdrop_rewards() {
-    let mut app = mock_app();
-    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
+    // **** Delegated MARS can only be returned by the auction contract ****
 
-    // mint MARS for to Owner
     mint_some_mars(
         &mut app,
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        owner.clone(),
         mars_instance.clone(),
-        Uint128::from(100_000_000_000u64),
-        init_msg.owner.clone().unwrap(),
+        Uint128::from(100000000u64),
+        user.to_string(),
     );
-
-    // Set MARS airdrop incentives
-    app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        mars_instance.clone(),
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::from(100_000_000_000u64),
-            contract: airdrop_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Check Airdrop Contract balance
-    let bal_resp: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_instance,
-            &cw20::Cw20QueryMsg::Balance {
-                address: airdrop_instance.clone().to_string(),
+    let err = app
+        .execute_contract(
+            user.clone(),
+            mars_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                amount: Uint128::from(100000000u64),
+                contract: airdrop_instance.to_string(),
+                msg: to_binary(&Cw20HookMsg::ReturnDelegatedMars {}).unwrap(),
             },
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(100_000_000_000u64), bal_resp.balance);
-
-    let merkle_roots =
-        vec!["cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string()];
-
-    let update_msg = ExecuteMsg::UpdateConfig {
-        owner: None,
-        auction_contract_address: None,
-        merkle_roots: Some(merkle_roots.clone()),
-        from_timestamp: None,
-        to_timestamp: None,
-    };
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unauthorized: Unauthorized");
 
-    // Update Config :: should be a success
-    app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
-        airdrop_instance.clone(),
-        &update_msg,
-        &[],
-    )
-    .unwrap();
+    // **** Auction contract only returns delegations to the airdrop contract ****
 
-    let resp: ConfigResponse = app
-        .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
-        .unwrap();
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &AuctionExecuteMsg::ReturnAirdropDelegations {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
 
-    // Check config and make sure all fields are updated
-    assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
-    assert_eq!(merkle_roots, resp.merkle_roots);
-    assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
-    assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
+    // **** Owner marks the auction as failed, delegated MARS is returned by the auction contract ****
 
-    // Update Block to test successful claim
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(1571897424)
+        b.time = Timestamp::from_seconds(1575000001)
     });
 
-    // ################################
-    // TERRA USER :: Claims not allowed. MARS Rewards will Not be transferred to the user
-    // ################################
-
-    let claim_msg = ExecuteMsg::Claim {
-        claim_amount: Uint128::from(250000000 as u64),
-        merkle_proof: vec![
-            "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
-            "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
-        ],
-        root_index: 0,
-    };
-
-    // **** User should successfully claim the Airdrop ****
-
-    // Check :: User hasn't yet claimed the airdrop
-    let resp: ClaimResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &airdrop_instance,
-            &QueryMsg::HasUserClaimed {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(false, resp.is_claimed);
-
-    // Should be a success
     let success_ = app
         .execute_contract(
-            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            owner.clone(),
             airdrop_instance.clone(),
-            &claim_msg,
+            &ExecuteMsg::MarkAuctionFailed {},
             &[],
         )
         .unwrap();
-
     assert_eq!(
         success_.events[1].attributes[1],
-        attr("action", "Airdrop::ExecuteMsg::Claim")
+        attr("action", "Airdrop::ExecuteMsg::MarkAuctionFailed")
     );
     assert_eq!(
         success_.events[1].attributes[2],
-        attr("addr", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
-    );
-    assert_eq!(
-        success_.events[1].attributes[3],
-        attr("airdrop", "250000000")
+        attr("delegated_amount", "100000000")
     );
 
-    // Check :: Terra User successfully claimed the airdrop
-    let claim_query_resp: ClaimResponse = app
+    let auction_state: mars_periphery::auction::StateResponse = app
         .wrap()
         .query_wasm_smart(
-            &airdrop_instance,
-            &QueryMsg::HasUserClaimed {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
-            },
+            &auction_instance,
+            &mars_periphery::auction::QueryMsg::State {},
         )
         .unwrap();
-    assert_eq!(true, claim_query_resp.is_claimed);
+    assert_eq!(Uint128::zero(), auction_state.total_mars_deposited);
+    assert_eq!(Uint128::zero(), auction_state.airdrop_mars_deposited);
+    assert_eq!(true, auction_state.are_airdrop_delegations_returned);
 
-    // Check :: User state
-    let user_info_query_resp: UserInfoResponse = app
+    // Returned MARS no longer counts towards the user's delegation
+    let auction_user: mars_periphery::auction::UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &airdrop_instance,
-            &QueryMsg::UserInfo {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            &auction_instance,
+            &mars_periphery::auction::QueryMsg::UserInfo {
+                address: user.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        Uint128::from(250000000u64),
-        user_info_query_resp.airdrop_amount
-    );
-    assert_eq!(Uint128::from(0u64), user_info_query_resp.delegated_amount);
-    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+    assert_eq!(Uint128::zero(), auction_user.mars_deposited);
 
-    // Check :: Contract state
-    let state_query_resp: StateResponse = app
+    let resp: ConfigResponse = app
         .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
         .unwrap();
+    assert_eq!(true, resp.is_auction_failed);
+    assert_eq!(false, resp.are_claims_allowed);
+
+    // **** Auction can only be marked as failed once ****
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::MarkAuctionFailed {},
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(100_000_000_000u64),
-        state_query_resp.total_airdrop_size
-    );
-    assert_eq!(Uint128::from(0u64), state_query_resp.total_delegated_amount);
-    assert_eq!(
-        Uint128::from(99750000000u64),
-        state_query_resp.unclaimed_tokens
+        err.to_string(),
+        "auction_already_failed: LP bootstrap auction already marked as failed"
     );
 
-    // **** "Already claimed" Error should be returned ****
+    // **** MARS can no longer be delegated, nor claims be enabled ****
 
-    let claim_f = app
+    let err = app
         .execute_contract(
-            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+            user.clone(),
             airdrop_instance.clone(),
-            &claim_msg,
+            &ExecuteMsg::DelegateMarsToBootstrapAuction {
+                amount_to_delegate: Uint128::from(1u64),
+            },
             &[],
         )
         .unwrap_err();
-    assert_eq!(claim_f.to_string(), "Generic error: Already claimed");
-
-    // #################
-    // ENABLE CLAIMS ::
-    // #################
+    assert_eq!(
+        err.to_string(),
+        "auction_failed: LP bootstrap auction has failed"
+    );
 
-    // Enable MARS Withdrawals
-    enable_claims(
-        &mut app,
-        Addr::unchecked(airdrop_instance.clone()),
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
+    let err = app
+        .execute_contract(
+            auction_instance.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::EnableClaims {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "auction_failed: LP bootstrap auction has failed"
     );
 
-    // Should be a success
-    app.execute_contract(
-        Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
-        airdrop_instance.clone(),
-        &ExecuteMsg::WithdrawAirdropReward {},
-        &[],
-    )
-    .unwrap();
+    // **** User withdraws the full airdrop, including the delegated MARS ****
 
-    // Check :: User state
     let user_info_query_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &airdrop_instance,
             &QueryMsg::UserInfo {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+                address: user.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(100000000u64),
+        user_info_query_resp.delegated_amount
+    );
     assert_eq!(
         Uint128::from(250000000u64),
-        user_info_query_resp.airdrop_amount
+        user_info_query_resp.vested_amount
     );
-    assert_eq!(Uint128::from(0u64), user_info_query_resp.delegated_amount);
-    assert_eq!(true, user_info_query_resp.tokens_withdrawn);
+    assert_eq!(Uint128::zero(), user_info_query_resp.locked_amount);
+
+    let success_ = app
+        .execute_contract(
+            user.clone(),
+            airdrop_instance.clone(),
+            &ExecuteMsg::WithdrawAirdropReward {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("claimed_amount", "250000000")
+    );
+    assert_eq!(
+        success_.events[1].attributes[5],
+        attr("penalty_amount", "0")
+    );
+
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(350000000u64), bal_resp.balance);
+
+    let bal_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: airdrop_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(99750000000u64), bal_resp.balance);
 }
 
-#[cfg(test)]
 #[test]
-fn test_delegate_mars_to_bootstrap_auction() {
-    use astroport::asset::AssetInfo;
-
+fn test_users_queries() {
     let mut app = mock_app();
-    let (airdrop_instance, mars_instance, init_msg, token_code_id) = init_contracts(&mut app);
+    let (airdrop_instance, mars_instance, init_msg, _) = init_contracts(&mut app);
 
     let owner = Addr::unchecked(init_msg.owner.clone().unwrap());
 
     // mint MARS for to Owner
     mint_some_mars(
         &mut app,
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        owner.clone(),
         mars_instance.clone(),
         Uint128::from(100_000_000_000u64),
-        init_msg.owner.clone().unwrap(),
+        owner.to_string(),
     );
 
     // Set MARS airdrop incentives
     app.execute_contract(
-        Addr::unchecked(init_msg.owner.clone().unwrap()),
+        owner.clone(),
         mars_instance.clone(),
         &Cw20ExecuteMsg::Send {
             amount: Uint128::from(100_000_000_000u64),
@@ -1183,239 +3761,225 @@ fn test_delegate_mars_to_bootstrap_auction() {
     )
     .unwrap();
 
-    let pair_contract = Box::new(
-        ContractWrapper::new(
-            astroport_pair::contract::execute,
-            astroport_pair::contract::instantiate,
-            astroport_pair::contract::query,
-        )
-        .with_reply(astroport_pair::contract::reply),
-    );
-
-    let pair_code_id = app.store_code(pair_contract);
-
-    let init_pair_msg = astroport::pair::InstantiateMsg {
-        asset_infos: [
-            AssetInfo::NativeToken {
-                denom: "uusd".to_string(),
-            },
-            AssetInfo::Token {
-                contract_addr: mars_instance.clone(),
-            },
-        ],
-        factory_addr: Addr::unchecked("factory_addr"),
-        init_params: None,
-        token_code_id,
-    };
-    let pair_instance = app
-        .instantiate_contract(
-            pair_code_id,
-            owner.clone(),
-            &init_pair_msg,
-            &[],
-            String::from("pair"),
-            None,
-        )
-        .unwrap();
-
-    // Initialize Bootstrap Auction contract
-    let auction_contract = Box::new(ContractWrapper::new(
-        mars_auction::contract::execute,
-        mars_auction::contract::instantiate,
-        mars_auction::contract::query,
-    ));
-    let auction_contract_code_id = app.store_code(auction_contract);
-    let auction_init_msg = mars_periphery::auction::InstantiateMsg {
-        owner: init_msg.owner.clone().unwrap(),
-        mars_token_address: mars_instance.clone().to_string(),
-        astro_token_address: mars_instance.clone().to_string(),
-        airdrop_contract_address: airdrop_instance.clone().to_string(),
-        generator_contract: "generator_contract".to_string(),
-        lockdrop_contract_address: "lockdrop_contract_address".to_string(),
-        lp_tokens_vesting_duration: 2592000u64,
-        init_timestamp: 1571897419u64,
-        ust_deposit_window: 2592000u64,
-        mars_deposit_window: 2592000u64,
-        withdrawal_window: 1592000u64,
-        mars_vesting_duration: 3600u64,
-    };
-
-    let auction_contract_instance = app
-        .instantiate_contract(
-            auction_contract_code_id,
-            Addr::unchecked(init_msg.owner.clone().unwrap()),
-            &auction_init_msg,
-            &[],
-            String::from("MARS"),
-            None,
-        )
-        .unwrap();
-
+    let auction_instance = init_auction(&mut app, &owner, &mars_instance, &airdrop_instance);
     app.execute_contract(
         owner.clone(),
-        auction_contract_instance.clone(),
-        &AuctionExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                astroport_lp_pool: Some(pair_instance.to_string()),
-                owner: None,
-                generator_contract: None,
-                mars_lp_staking_contract: None,
-            },
+        airdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            owner: None,
+            auction_contract_address: Some(auction_instance.to_string()),
+            from_timestamp: None,
+            to_timestamp: None,
+            early_exit_penalty: None,
+            auction_deadline: None,
         },
         &[],
     )
     .unwrap();
 
-    let merkle_roots =
-        vec!["cdcdfad1c342f5f55a2639dcae7321a64cd000807fa24c2c4ddaa944fd52d34e".to_string()];
-
-    let update_msg = ExecuteMsg::UpdateConfig {
-        owner: None,
-        auction_contract_address: Some(auction_contract_instance.to_string()),
-        merkle_roots: Some(merkle_roots.clone()),
-        from_timestamp: None,
-        to_timestamp: None,
-    };
-
-    // Update Config :: should be a success
-    app.execute_contract(owner.clone(), airdrop_instance.clone(), &update_msg, &[])
-        .unwrap();
-
-    let resp: ConfigResponse = app
-        .wrap()
-        .query_wasm_smart(&airdrop_instance, &QueryMsg::Config {})
-        .unwrap();
+    let entries = vec![
+        AirdropEntry {
+            address: "user_c".to_string(),
+            amount: Uint128::from(2000u64),
+        },
+        AirdropEntry {
+            address: "user_a".to_string(),
+            amount: Uint128::from(5000u64),
+        },
+        AirdropEntry {
+            address: "user_b".to_string(),
+            amount: Uint128::from(3000u64),
+        },
+    ];
+    let tree = MerkleTree::new(
+        HashingScheme::V1,
+        &app.block_info().chain_id,
+        airdrop_instance.as_str(),
+        &entries,
+    )
+    .unwrap();
 
-    // Check config and make sure all fields are updated
-    assert_eq!(init_msg.owner.clone().unwrap(), resp.owner);
-    assert_eq!(merkle_roots, resp.merkle_roots);
-    assert_eq!(init_msg.from_timestamp.unwrap(), resp.from_timestamp);
-    assert_eq!(init_msg.to_timestamp, resp.to_timestamp);
+    app.execute_contract(
+        owner.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::AddMerkleRoot {
+            merkle_root: tree.root(),
+            budget: Uint128::from(10000u64),
+            expiry_timestamp: None,
+            hashing_scheme: Some(HashingScheme::V1),
+            is_indexed: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    // Update Block to test successful claim
     app.update_block(|b| {
         b.height += 17280;
         b.time = Timestamp::from_seconds(1571897424)
     });
 
-    // ################################
-    // TERRA USER :: Claims not allowed. MARS Rewards will Not be transferred to the user
-    // ################################
-
-    let claim_msg = ExecuteMsg::Claim {
-        claim_amount: Uint128::from(250000000 as u64),
-        merkle_proof: vec![
-            "7719b79a65e5aa0bbfd144cf5373138402ab1c374d9049e490b5b61c23d90065".to_string(),
-            "60368f2058e0fb961a7721a241f9b973c3dd6c57e10a627071cd81abca6aa490".to_string(),
-        ],
-        root_index: 0,
-    };
-
-    // **** User should successfully claim the Airdrop ****
-
-    // Should be a success
-    let success_ = app
-        .execute_contract(
-            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+    for entry in entries.iter() {
+        app.execute_contract(
+            Addr::unchecked(entry.address.clone()),
             airdrop_instance.clone(),
-            &claim_msg,
+            &ExecuteMsg::Claim {
+                claim_amount: entry.amount,
+                merkle_proof: tree.proof(entry).unwrap(),
+                root_index: 0,
+                leaf_index: None,
+            },
             &[],
         )
         .unwrap();
+    }
 
+    app.execute_contract(
+        Addr::unchecked("user_a"),
+        airdrop_instance.clone(),
+        &ExecuteMsg::DelegateMarsToBootstrapAuction {
+            amount_to_delegate: Uint128::from(1000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let users_query = |app: &App, msg: QueryMsg| -> Vec<(String, Uint128)> {
+        let resp: UsersResponse = app
+            .wrap()
+            .query_wasm_smart(&airdrop_instance, &msg)
+            .unwrap();
+        resp.users
+            .into_iter()
+            .map(|user| (user.address, user.user_info.airdrop_amount))
+            .collect()
+    };
+
+    // Check :: Users are ordered by their address
     assert_eq!(
-        success_.events[1].attributes[1],
-        attr("action", "Airdrop::ExecuteMsg::Claim")
-    );
-    assert_eq!(
-        success_.events[1].attributes[2],
-        attr("addr", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
+        users_query(
+            &app,
+            QueryMsg::AllUsers {
+                start_after: None,
+                limit: Some(2),
+            }
+        ),
+        vec![
+            ("user_a".to_string(), Uint128::from(5000u64)),
+            ("user_b".to_string(), Uint128::from(3000u64)),
+        ]
     );
     assert_eq!(
-        success_.events[1].attributes[3],
-        attr("airdrop", "250000000")
+        users_query(
+            &app,
+            QueryMsg::AllUsers {
+                start_after: Some("user_b".to_string()),
+                limit: None,
+            }
+        ),
+        vec![("user_c".to_string(), Uint128::from(2000u64))]
     );
 
-    // **** "Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance" Error should be returned ****
-
-    let claim_f = app
-        .execute_contract(
-            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
-            airdrop_instance.clone(),
-            &ExecuteMsg::DelegateMarsToBootstrapAuction {
-                amount_to_delegate: Uint128::from(250000001u64),
+    let resp: UsersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::Delegators {
+                start_after: None,
+                limit: None,
             },
-            &[],
         )
-        .unwrap_err();
+        .unwrap();
+    assert_eq!(resp.users.len(), 1);
+    assert_eq!(resp.users[0].address, "user_a");
     assert_eq!(
-        claim_f.to_string(),
-        "Generic error: Total amount being delegated for bootstrap auction cannot exceed your claimable airdrop balance"
+        resp.users[0].user_info.delegated_amount,
+        Uint128::from(1000u64)
     );
+    assert_eq!(resp.start_after, None);
 
-    // **** Should successfully delegate MARS ****
+    let state_query_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(3u64, state_query_resp.claimants_count);
+    assert_eq!(0u64, state_query_resp.withdrawers_count);
 
-    let delegation_res = app
-        .execute_contract(
-            Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
-            airdrop_instance.clone(),
-            &ExecuteMsg::DelegateMarsToBootstrapAuction {
-                amount_to_delegate: Uint128::from(250000000u64),
+    // Claims are enabled and user B withdraws the airdrop
+    app.execute_contract(
+        auction_instance.clone(),
+        airdrop_instance.clone(),
+        &ExecuteMsg::EnableClaims {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("user_b"),
+        airdrop_instance.clone(),
+        &ExecuteMsg::WithdrawAirdropReward {},
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        users_query(
+            &app,
+            QueryMsg::UnwithdrawnUsers {
+                start_after: None,
+                limit: None,
+            }
+        ),
+        vec![
+            ("user_a".to_string(), Uint128::from(5000u64)),
+            ("user_c".to_string(), Uint128::from(2000u64)),
+        ]
+    );
+
+    // Check :: At most `limit` users are scanned. User B is scanned but filtered out
+    let resp: UsersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UnwithdrawnUsers {
+                start_after: Some("user_a".to_string()),
+                limit: Some(1),
             },
-            &[],
         )
         .unwrap();
-    assert_eq!(
-        delegation_res.events[1].attributes[1],
-        attr(
-            "action",
-            "Airdrop::ExecuteMsg::DelegateMarsToBootstrapAuction"
+    assert!(resp.users.is_empty());
+    assert_eq!(resp.start_after, Some("user_b".to_string()));
+
+    let resp: UsersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UnwithdrawnUsers {
+                start_after: resp.start_after,
+                limit: Some(1),
+            },
         )
-    );
-    assert_eq!(
-        delegation_res.events[1].attributes[2],
-        attr("user", "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp")
-    );
-    assert_eq!(
-        delegation_res.events[1].attributes[3],
-        attr("amount_delegated", "250000000")
-    );
+        .unwrap();
+    assert_eq!(resp.users.len(), 1);
+    assert_eq!(resp.users[0].address, "user_c");
+    assert_eq!(resp.start_after, Some("user_c".to_string()));
 
-    // Check :: Airdrop :: User state
-    let user_info_query_resp: UserInfoResponse = app
+    let resp: UsersResponse = app
         .wrap()
         .query_wasm_smart(
             &airdrop_instance,
-            &QueryMsg::UserInfo {
-                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            &QueryMsg::UnwithdrawnUsers {
+                start_after: resp.start_after,
+                limit: Some(1),
             },
         )
         .unwrap();
-    assert_eq!(
-        Uint128::from(250000000u64),
-        user_info_query_resp.airdrop_amount
-    );
-    assert_eq!(
-        Uint128::from(250000000u64),
-        user_info_query_resp.delegated_amount
-    );
-    assert_eq!(false, user_info_query_resp.tokens_withdrawn);
+    assert!(resp.users.is_empty());
+    assert_eq!(resp.start_after, None);
 
-    // Check :: Airdrop :: Contract state
     let state_query_resp: StateResponse = app
         .wrap()
         .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
         .unwrap();
-    assert_eq!(
-        Uint128::from(100_000_000_000u64),
-        state_query_resp.total_airdrop_size
-    );
-    assert_eq!(
-        Uint128::from(250000000u64),
-        state_query_resp.total_delegated_amount
-    );
-    assert_eq!(
-        Uint128::from(99750000000u64),
-        state_query_resp.unclaimed_tokens
-    );
+    assert_eq!(3u64, state_query_resp.claimants_count);
+    assert_eq!(1u64, state_query_resp.withdrawers_count);
 }
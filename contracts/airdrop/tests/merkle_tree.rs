@@ -0,0 +1,224 @@
+use cosmwasm_std::Uint128;
+use mars_airdrop::crypto::{indexed_leaf_hash, verify_claim, verify_merkle_proof};
+use mars_airdrop::merkle_tree::{parse_csv_entries, parse_json_entries, AirdropEntry, MerkleTree};
+use mars_periphery::airdrop::HashingScheme;
+use proptest::collection::btree_map;
+use proptest::prelude::*;
+
+const CHAIN_ID: &str = "columbus-5";
+const CONTRACT_ADDRESS: &str = "terra1dpe2aqykm2vnakcz4vgpha0agxnlkjvgfahhk7";
+
+fn entry(address: &str, amount: u128) -> AirdropEntry {
+    AirdropEntry {
+        address: address.to_string(),
+        amount: Uint128::new(amount),
+    }
+}
+
+fn hashing_scheme() -> impl Strategy<Value = HashingScheme> {
+    prop_oneof![Just(HashingScheme::Legacy), Just(HashingScheme::V1)]
+}
+
+fn entries() -> impl Strategy<Value = Vec<AirdropEntry>> {
+    btree_map("terra1[a-z0-9]{38}", 1u128..u128::MAX, 1..64).prop_map(|entries| {
+        entries
+            .into_iter()
+            .map(|(address, amount)| entry(&address, amount))
+            .collect()
+    })
+}
+
+proptest! {
+    #[test]
+    fn proofs_of_all_entries_are_verified(
+        hashing_scheme in hashing_scheme(),
+        entries in entries(),
+    ) {
+        let tree = MerkleTree::new(hashing_scheme, CHAIN_ID, CONTRACT_ADDRESS, &entries).unwrap();
+        let root = tree.root();
+
+        for claim in tree.claims(&entries, 0).unwrap() {
+            prop_assert!(verify_claim(
+                &hashing_scheme,
+                CHAIN_ID,
+                CONTRACT_ADDRESS,
+                &claim.address,
+                claim.amount,
+                claim.merkle_proof,
+                &root,
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn proofs_are_rejected_for_other_amounts(
+        hashing_scheme in hashing_scheme(),
+        entries in entries(),
+    ) {
+        let tree = MerkleTree::new(hashing_scheme, CHAIN_ID, CONTRACT_ADDRESS, &entries).unwrap();
+        let root = tree.root();
+
+        for claim in tree.claims(&entries, 0).unwrap() {
+            prop_assert!(!verify_claim(
+                &hashing_scheme,
+                CHAIN_ID,
+                CONTRACT_ADDRESS,
+                &claim.address,
+                claim.amount - Uint128::new(1),
+                claim.merkle_proof,
+                &root,
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn v1_proofs_are_bound_to_the_contract(entries in entries()) {
+        let tree = MerkleTree::new(HashingScheme::V1, CHAIN_ID, CONTRACT_ADDRESS, &entries).unwrap();
+        let root = tree.root();
+
+        for claim in tree.claims(&entries, 0).unwrap() {
+            prop_assert!(!verify_claim(
+                &HashingScheme::V1,
+                CHAIN_ID,
+                "terra1other",
+                &claim.address,
+                claim.amount,
+                claim.merkle_proof,
+                &root,
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn indexed_proofs_are_bound_to_the_leaf_index(entries in entries()) {
+        // The same account is part of the tree twice
+        let entries = [entries.clone(), entries].concat();
+        let tree = MerkleTree::new_indexed(CHAIN_ID, CONTRACT_ADDRESS, &entries).unwrap();
+        let root = tree.root();
+
+        for (leaf_index, claim) in tree.claims(&entries, 0).unwrap().into_iter().enumerate() {
+            prop_assert_eq!(claim.leaf_index, Some(leaf_index as u64));
+
+            let leaf = |leaf_index: u64| {
+                indexed_leaf_hash(CHAIN_ID, CONTRACT_ADDRESS, leaf_index, &claim.address, claim.amount)
+            };
+            prop_assert!(verify_merkle_proof(
+                &HashingScheme::V1,
+                leaf(leaf_index as u64),
+                claim.merkle_proof.clone(),
+                &root,
+            )
+            .unwrap());
+            prop_assert!(!verify_merkle_proof(
+                &HashingScheme::V1,
+                leaf(entries.len() as u64 + leaf_index as u64),
+                claim.merkle_proof,
+                &root,
+            )
+            .unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_legacy_tree_matches_existing_roots() {
+    // Merkle root used by the EVM claim integration test
+    let tree = MerkleTree::new(
+        HashingScheme::Legacy,
+        "",
+        "",
+        &[
+            entry("2c7536e3605d9c16a7a3d7b1898e529396a65c23", 50000000),
+            entry("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp", 250000000),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        tree.root(),
+        "763d5752e7cc2eb568ec689d0251bf4c7cfe90e4d76c3ee2f9dc709f100627d0"
+    );
+
+    // Merkle proofs generated by scripts/create_airdrop_json.ts
+    let entries = vec![
+        entry("terra17mpuq65hw5kt7d44kpw4nk7x339xhznaa0duzv", 100249808700),
+        entry("terra1lv845g7szf9m3082qn3eehv9ewkjjr2kdyz0t6", 100249873003),
+        entry("terra1mthww38ea56sjmwtswlvy0g4zzjspjwaw3e6t8", 100250347000),
+        entry("terra1jdd392vxlx5u23yvekvvjzuna0gszttgtngfnt", 100250367000),
+        entry("terra1yskm9s4r0h0egg3lxe5wmmppr9s6lfau4j8yhc", 100250516700),
+        entry("terra19x45fmcfmrnhe35r38xwtutft248ylj8p9mh5j", 100053467000),
+        entry("terra1elk8fwaqv2hg9hpuh6wclvur0mj408d7zrly49", 100003467000),
+        entry("terra1suygcd3r636scvclqm08euwaemdrmrwxl06z0v", 100503467000),
+    ];
+    let tree = MerkleTree::new(HashingScheme::Legacy, "", "", &entries).unwrap();
+    assert_eq!(
+        tree.proof(&entries[0]).unwrap(),
+        vec![
+            "3bdf1c708eac5d0b077ea8d4789ce96b89622c3e6510ee8cc91fc8a82c38f2fe",
+            "0d65b0cab2eb02020e089396d15422d1e5540dd501c0503d4fa2d79cb36ecaab",
+            "05d6a1cd0744a6e28c242cebdc297ed817953b532aefdac120c7d5449a255b9e",
+        ]
+    );
+}
+
+#[test]
+fn test_tree_with_odd_number_of_leaves() {
+    let entries = vec![
+        entry("terra1a", 1),
+        entry("terra1b", 2),
+        entry("terra1c", 3),
+    ];
+    let tree = MerkleTree::new(HashingScheme::V1, CHAIN_ID, CONTRACT_ADDRESS, &entries).unwrap();
+
+    for claim in tree.claims(&entries, 2).unwrap() {
+        assert_eq!(claim.index, 2);
+        assert!(verify_claim(
+            &HashingScheme::V1,
+            CHAIN_ID,
+            CONTRACT_ADDRESS,
+            &claim.address,
+            claim.amount,
+            claim.merkle_proof,
+            &tree.root(),
+        )
+        .unwrap());
+    }
+
+    // Entries which are not part of the tree have no proof
+    assert_eq!(tree.proof(&entry("terra1d", 4)), None);
+}
+
+#[test]
+fn test_invalid_entries() {
+    let err = MerkleTree::new(HashingScheme::Legacy, "", "", &[]).unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: No airdrop entries");
+
+    let err = MerkleTree::new(
+        HashingScheme::Legacy,
+        "",
+        "",
+        &[entry("terra1a", 1), entry("terra1a", 2)],
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Duplicate address terra1a");
+}
+
+#[test]
+fn test_parse_entries() {
+    let csv = "address,amount\nterra1a,100\n\nterra1b, 200\n";
+    assert_eq!(
+        parse_csv_entries(csv).unwrap(),
+        vec![entry("terra1a", 100), entry("terra1b", 200)]
+    );
+
+    let err = parse_csv_entries("terra1a,100\nterra1b,abc").unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Invalid amount at line 2");
+
+    let json = br#"[{"address":"terra1a","amount":"100"},{"address":"terra1b","amount":"200"}]"#;
+    assert_eq!(
+        parse_json_entries(json).unwrap(),
+        vec![entry("terra1a", 100), entry("terra1b", 200)]
+    );
+}
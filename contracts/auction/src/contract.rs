@@ -4,11 +4,16 @@ use std::ops::Div;
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, QuerierWrapper, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg,
-    WasmQuery,
+    MessageInfo, Order, QuerierWrapper, QueryRequest, Response, StdError, StdResult, Uint128,
+    WasmMsg, WasmQuery,
 };
 
+use mars_periphery::airdrop::Cw20HookMsg::ReturnDelegatedMars as AirdropReturnDelegatedMars;
 use mars_periphery::airdrop::ExecuteMsg::EnableClaims as AirdropEnableClaims;
+use mars_periphery::airdrop::{
+    QueryMsg as AirdropQueryMsg, StateResponse as AirdropStateResponse,
+    UserInfoResponse as AirdropUserInfoResponse,
+};
 use mars_periphery::auction::{
     CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
     StateResponse, UpdateConfigMsg, UserInfoResponse,
@@ -65,6 +70,9 @@ pub fn instantiate(
         return Err(StdError::generic_err("mars_vesting_duration cannot be 0"));
     }
 
+    let withdrawal_schedule = msg.withdrawal_schedule.unwrap_or_default();
+    withdrawal_schedule.validate()?;
+
     let config = Config {
         owner: deps.api.addr_validate(&msg.owner)?,
         mars_token_address: deps.api.addr_validate(&msg.mars_token_address)?,
@@ -82,6 +90,7 @@ pub fn instantiate(
         mars_deposit_window: msg.mars_deposit_window,
         ust_deposit_window: msg.ust_deposit_window,
         withdrawal_window: msg.withdrawal_window,
+        withdrawal_schedule,
     };
 
     let state = STATE.load(deps.storage).unwrap_or_default();
@@ -131,6 +140,8 @@ pub fn execute(
             withdraw_unlocked_shares,
         ),
 
+        ExecuteMsg::ReturnAirdropDelegations {} => handle_return_airdrop_delegations(deps, info),
+
         ExecuteMsg::Callback(msg) => _handle_callback(deps, env, info, msg),
     }
 }
@@ -162,7 +173,15 @@ pub fn receive_cw20(
                 return Err(StdError::generic_err("Unauthorized"));
             }
 
-            handle_deposit_mars_tokens(deps, env, info, user_address, cw20_msg.amount)
+            let is_airdrop_delegation = config.airdrop_contract_address == cw20_msg.sender;
+            handle_deposit_mars_tokens(
+                deps,
+                env,
+                info,
+                user_address,
+                cw20_msg.amount,
+                is_airdrop_delegation,
+            )
         }
         Cw20HookMsg::IncreaseMarsIncentives {} => {
             handle_increasing_mars_incentives(deps, cw20_msg.amount)
@@ -223,8 +242,55 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // MIGRATE :: MARS delegated via the airdrop contract before it was tracked, as per the airdrop contract's positions.
+    // The delegated MARS is to be returned in full if the auction is marked as failed
+    let mut users_migrated = 0u64;
+    if !state.are_airdrop_delegations_returned {
+        let airdrop_state: AirdropStateResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: config.airdrop_contract_address.to_string(),
+                msg: to_binary(&AirdropQueryMsg::State {})?,
+            }))?;
+        state.airdrop_mars_deposited = airdrop_state.total_delegated_amount;
+        STATE.save(deps.storage, &state)?;
+
+        let users = USERS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| match item {
+                Ok((_, user_info)) => !user_info.mars_deposited.is_zero(),
+                Err(_) => true,
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (user_bytes, mut user_info) in users {
+            let user_address = Addr::unchecked(String::from_utf8(user_bytes)?);
+            let airdrop_user_info: AirdropUserInfoResponse =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: config.airdrop_contract_address.to_string(),
+                    msg: to_binary(&AirdropQueryMsg::UserInfo {
+                        address: user_address.to_string(),
+                    })?,
+                }))?;
+            let airdrop_mars_deposited = airdrop_user_info
+                .delegated_amount
+                .min(user_info.mars_deposited);
+            if airdrop_mars_deposited != user_info.airdrop_mars_deposited {
+                user_info.airdrop_mars_deposited = airdrop_mars_deposited;
+                USERS.save(deps.storage, &user_address, &user_info)?;
+                users_migrated += 1;
+            }
+        }
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Auction::MigrateMsg"),
+        attr("airdrop_mars_deposited", state.airdrop_mars_deposited),
+        attr("users_migrated", users_migrated.to_string()),
+    ]))
 }
 
 //----------------------------------------------------------------------------------------
@@ -302,12 +368,14 @@ pub fn handle_update_config(
 /// @dev Accepts MARS tokens to be used for the LP Bootstrapping via auction. Callable only by Airdrop / Lockdrop contracts
 /// @param user_address : User address who is delegating the MARS tokens for LP Pool bootstrap via auction
 /// @param amount : Number of MARS Tokens being deposited
+/// @param is_airdrop_delegation : Boolean value indicating if the MARS tokens are delegated via the airdrop contract
 pub fn handle_deposit_mars_tokens(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     user_address: Addr,
     amount: Uint128,
+    is_airdrop_delegation: bool,
 ) -> Result<Response, StdError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -327,6 +395,10 @@ pub fn handle_deposit_mars_tokens(
     // UPDATE STATE
     state.total_mars_deposited += amount;
     user_info.mars_deposited += amount;
+    if is_airdrop_delegation {
+        state.airdrop_mars_deposited += amount;
+        user_info.airdrop_mars_deposited += amount;
+    }
 
     // SAVE UPDATED STATE
     STATE.save(deps.storage, &state)?;
@@ -428,8 +500,8 @@ pub fn handle_withdraw_ust(
         )));
     }
 
-    // After UST deposit window is closed, we allow to withdraw only once
-    if env.block.time.seconds() > config.init_timestamp + config.ust_deposit_window {
+    // Once the withdrawal window opens, we allow to withdraw only once
+    if env.block.time.seconds() >= config.init_timestamp + config.ust_deposit_window {
         user_info.ust_withdrawn_flag = true;
     }
 
@@ -480,6 +552,13 @@ pub fn handle_init_pool(
         return Err(StdError::generic_err("Liquidity already provided to pool"));
     }
 
+    // CHECK :: Bootstrap auction has failed if airdrop delegations have been returned
+    if state.are_airdrop_delegations_returned {
+        return Err(StdError::generic_err(
+            "Airdrop delegations have been returned",
+        ));
+    }
+
     // CHECK :: Deposit / withdrawal windows need to be over
     if !are_windows_closed(env.block.time.seconds(), &config) {
         return Err(StdError::generic_err(
@@ -532,6 +611,54 @@ pub fn handle_init_pool(
     Ok(response)
 }
 
+/// @dev Returns the MARS tokens delegated by airdrop recipients to the airdrop contract. Called by the airdrop contract
+/// when the bootstrap auction is marked as failed, i.e liquidity was never added to the pool
+pub fn handle_return_airdrop_delegations(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, StdError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: Only airdrop contract can call this function
+    if info.sender != config.airdrop_contract_address {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Delegated MARS can't be returned once liquidity is provided to the pool
+    if !state.lp_shares_minted.is_zero() {
+        return Err(StdError::generic_err("Liquidity already provided to pool"));
+    }
+
+    if state.are_airdrop_delegations_returned {
+        return Err(StdError::generic_err(
+            "Airdrop delegations have already been returned",
+        ));
+    }
+
+    // Delegators' airdrop delegations are excluded from their deposits via `user_mars_deposited`
+    let amount = state.airdrop_mars_deposited;
+    state.total_mars_deposited -= amount;
+    state.airdrop_mars_deposited = Uint128::zero();
+    state.are_airdrop_delegations_returned = true;
+
+    let mut response = Response::new();
+    if !amount.is_zero() {
+        response = response.add_message(build_send_cw20_token_msg(
+            config.airdrop_contract_address.to_string(),
+            config.mars_token_address.to_string(),
+            amount,
+            to_binary(&AirdropReturnDelegatedMars {})?,
+        )?);
+    }
+
+    STATE.save(deps.storage, &state)?;
+    Ok(response.add_attributes(vec![
+        attr("action", "Auction::ExecuteMsg::ReturnAirdropDelegations"),
+        attr("mars_returned", amount),
+    ]))
+}
+
 /// @dev Admin function to stake Astroport LP tokens with the generator contract
 /// @params single_incentive_staking : Boolean value indicating if LP Tokens are to be staked with MARS LP Contract or not
 /// @params dual_incentives_staking : Boolean value indicating if LP Tokens are to be staked with Astroport Generator Contract or not
@@ -710,7 +837,7 @@ pub fn handle_claim_rewards_and_unlock(
     }
 
     // CHECK :: Does user have valid MARS / UST deposit balances
-    if user_info.mars_deposited == Uint128::zero() && user_info.ust_deposited == Uint128::zero() {
+    if user_mars_deposited(&state, &user_info).is_zero() && user_info.ust_deposited.is_zero() {
         return Err(StdError::generic_err("Invalid request"));
     }
 
@@ -1081,6 +1208,7 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         mars_deposit_window: config.mars_deposit_window,
         ust_deposit_window: config.ust_deposit_window,
         withdrawal_window: config.withdrawal_window,
+        withdrawal_schedule: config.withdrawal_schedule,
     })
 }
 
@@ -1097,6 +1225,8 @@ fn query_state(deps: Deps) -> StdResult<StateResponse> {
         pool_init_timestamp: state.pool_init_timestamp,
         global_mars_reward_index: state.global_mars_reward_index,
         global_astro_reward_index: state.global_astro_reward_index,
+        airdrop_mars_deposited: state.airdrop_mars_deposited,
+        are_airdrop_delegations_returned: state.are_airdrop_delegations_returned,
     })
 }
 
@@ -1162,7 +1292,7 @@ fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<User
     }
 
     Ok(UserInfoResponse {
-        mars_deposited: user_info.mars_deposited,
+        mars_deposited: user_mars_deposited(&state, &user_info),
         ust_deposited: user_info.ust_deposited,
         ust_withdrawn_flag: user_info.ust_withdrawn_flag,
         lp_shares: user_info.lp_shares,
@@ -1184,6 +1314,18 @@ fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<User
 // HELPERS :: LP & REWARD CALCULATIONS
 //----------------------------------------------------------------------------------------
 
+/// @dev Returns the MARS tokens delegated by the user which count towards the LP Bootstrapping. MARS delegated via
+/// the airdrop contract is excluded once it has been returned to the airdrop contract
+/// @param state : Contract State
+/// @param user_info : User Info State
+fn user_mars_deposited(state: &State, user_info: &UserInfo) -> Uint128 {
+    if state.are_airdrop_delegations_returned {
+        user_info.mars_deposited - user_info.airdrop_mars_deposited
+    } else {
+        user_info.mars_deposited
+    }
+}
+
 /// @dev Calculates user's MARS-UST LP Shares
 /// Formula -
 /// user's MARS share %  = user's MARS deposits / Total MARS deposited
@@ -1196,8 +1338,10 @@ fn calculate_user_lp_share(state: &State, user_info: &UserInfo) -> Uint128 {
     {
         return user_info.lp_shares;
     }
-    let user_mars_shares_percent =
-        Decimal::from_ratio(user_info.mars_deposited, state.total_mars_deposited);
+    let user_mars_shares_percent = Decimal::from_ratio(
+        user_mars_deposited(state, user_info),
+        state.total_mars_deposited,
+    );
     let user_ust_shares_percent =
         Decimal::from_ratio(user_info.ust_deposited, state.total_ust_deposited);
     let user_total_share_percent = user_mars_shares_percent + user_ust_shares_percent;
@@ -1219,9 +1363,9 @@ fn calculate_auction_reward_for_user(
     let mut user_mars_shares_percent = Decimal::zero();
     let mut user_ust_shares_percent = Decimal::zero();
 
-    if user_info.mars_deposited > Uint128::zero() {
-        user_mars_shares_percent =
-            Decimal::from_ratio(user_info.mars_deposited, state.total_mars_deposited);
+    let mars_deposited = user_mars_deposited(state, user_info);
+    if mars_deposited > Uint128::zero() {
+        user_mars_shares_percent = Decimal::from_ratio(mars_deposited, state.total_mars_deposited);
     }
     if user_info.ust_deposited > Uint128::zero() {
         user_ust_shares_percent =
@@ -1348,33 +1492,11 @@ fn are_windows_closed(current_timestamp: u64, config: &Config) -> bool {
 /// @params current_timestamp : Current block timestamp
 /// @params config : Contract configuration
 fn allowed_withdrawal_percent(current_timestamp: u64, config: &Config) -> Decimal {
-    let ust_withdrawal_cutoff_init_point = config.init_timestamp + config.ust_deposit_window;
-
-    // Deposit window :: 100% withdrawals allowed
-    if current_timestamp <= ust_withdrawal_cutoff_init_point {
-        return Decimal::from_ratio(100u32, 100u32);
-    }
-
-    let ust_withdrawal_cutoff_second_point =
-        ust_withdrawal_cutoff_init_point + (config.withdrawal_window / 2u64);
-    // Deposit window closed, 1st half of withdrawal window :: 50% withdrawals allowed
-    if current_timestamp <= ust_withdrawal_cutoff_second_point {
-        return Decimal::from_ratio(50u32, 100u32);
-    }
-    let ust_withdrawal_cutoff_final =
-        ust_withdrawal_cutoff_second_point + (config.withdrawal_window / 2u64);
-    //  Deposit window closed, 2nd half of withdrawal window :: max withdrawal allowed decreases linearly from 50% to 0% vs time elapsed
-    if current_timestamp < ust_withdrawal_cutoff_final {
-        let time_left = ust_withdrawal_cutoff_final - current_timestamp;
-        Decimal::from_ratio(
-            50u64 * time_left,
-            100u64 * (ust_withdrawal_cutoff_final - ust_withdrawal_cutoff_second_point),
-        )
-    }
-    // Withdrawals not allowed
-    else {
-        Decimal::from_ratio(0u32, 100u32)
-    }
+    config.withdrawal_schedule.max_withdrawal_percent(
+        config.init_timestamp + config.ust_deposit_window,
+        config.withdrawal_window,
+        current_timestamp,
+    )
 }
 
 //----------------------------------------------------------------------------------------
@@ -1,5 +1,6 @@
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw_storage_plus::{Item, Map};
+use mars_periphery::withdrawal_schedule::WithdrawalSchedule;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -46,6 +47,9 @@ pub struct Config {
     pub mars_deposit_window: u64,
     /// Number of seconds post ust_deposit_window completion during which only partial UST withdrawals are allowed
     pub withdrawal_window: u64,
+    /// Max share of their UST deposits which users can withdraw during the deposit / withdrawal windows
+    #[serde(default)]
+    pub withdrawal_schedule: WithdrawalSchedule,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -69,6 +73,12 @@ pub struct State {
     pub global_mars_reward_index: Decimal,
     /// index used to keep track of $ASTRO claimed as LP staking rewards and distribute them proportionally among the auction participants
     pub global_astro_reward_index: Decimal,
+    /// Total MARS tokens delegated to the contract by airdrop recipients
+    #[serde(default)]
+    pub airdrop_mars_deposited: Uint128,
+    /// True if MARS delegated by airdrop recipients has been returned to the airdrop contract. Liquidity can't be added to the pool thereafter
+    #[serde(default)]
+    pub are_airdrop_delegations_returned: bool,
 }
 
 impl Default for State {
@@ -83,6 +93,8 @@ impl Default for State {
             are_staked_for_dual_incentives: false,
             global_mars_reward_index: Decimal::zero(),
             global_astro_reward_index: Decimal::zero(),
+            airdrop_mars_deposited: Uint128::zero(),
+            are_airdrop_delegations_returned: false,
         }
     }
 }
@@ -111,6 +123,9 @@ pub struct UserInfo {
     pub mars_reward_index: Decimal,
     // Index used to calculate user's $ASTRO staking rewards
     pub astro_reward_index: Decimal,
+    // MARS Tokens delegated by the user via the airdrop contract. Excluded from the user's deposit once returned
+    #[serde(default)]
+    pub airdrop_mars_deposited: Uint128,
 }
 
 impl Default for UserInfo {
@@ -127,6 +142,7 @@ impl Default for UserInfo {
             withdrawn_astro_incentives: Uint128::zero(),
             mars_reward_index: Decimal::zero(),
             astro_reward_index: Decimal::zero(),
+            airdrop_mars_deposited: Uint128::zero(),
         }
     }
 }
@@ -2,14 +2,25 @@ use astroport::vesting::{
     Cw20HookMsg as VestingHookMsg, InstantiateMsg as VestingInstantiateMsg, VestingAccount,
     VestingSchedule, VestingSchedulePoint,
 };
-use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::{attr, to_binary, Addr, Coin, Decimal, Timestamp, Uint128, Uint64};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    attr, from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, Empty,
+    OwnedDeps, Querier, QuerierResult, QueryRequest, SubMsg, SystemResult, Timestamp, Uint128,
+    Uint64, WasmMsg, WasmQuery,
+};
 use cw20::Cw20ExecuteMsg;
+use mars_auction::contract::{execute, migrate, query};
+use mars_auction::state::{Config, State, UserInfo, CONFIG, STATE, USERS};
+use mars_periphery::airdrop::{
+    Cw20HookMsg as AirdropCw20HookMsg, QueryMsg as AirdropQueryMsg,
+    StateResponse as AirdropStateResponse, UserInfoResponse as AirdropUserInfoResponse,
+};
 use mars_periphery::auction::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StateResponse,
     UpdateConfigMsg, UserInfoResponse,
 };
 use mars_periphery::lockdrop::LockupDurationParams;
+use mars_periphery::withdrawal_schedule::WithdrawalSchedule;
 use terra_multi_test::{App, BankKeeper, ContractWrapper, Executor, TerraMockQuerier};
 
 fn mock_app() -> App {
@@ -200,6 +211,7 @@ fn instantiate_auction_contract(
         ust_deposit_window: 5_000_00,
         mars_deposit_window: 5_000_00,
         withdrawal_window: 2_000_00,
+        withdrawal_schedule: None,
     };
 
     // Init contract
@@ -246,9 +258,13 @@ fn instantiate_airdrop_lockdrop_contracts(
     let airdrop_msg = mars_periphery::airdrop::InstantiateMsg {
         owner: Some(owner.clone().to_string()),
         mars_token_address: mars_token_instance.clone().into_string(),
-        merkle_roots: Some(vec!["merkle_roots".to_string()]),
+        merkle_roots: None,
+        hashing_scheme: None,
         from_timestamp: Some(10_000_01),
         to_timestamp: 1000_000_00,
+        vesting: None,
+        early_exit_penalty: None,
+        sweep_delay: None,
     };
 
     // Airdrop Instance
@@ -315,17 +331,17 @@ fn instantiate_airdrop_lockdrop_contracts(
             config: mars_core_dev::address_provider::msg::ConfigParams {
                 owner: None,
                 council_address: None,
-                incentives_address: None,
+                incentives_address: Some("incentives".to_string()),
                 safety_fund_address: None,
                 mars_token_address: Some(mars_token_instance.to_string()),
                 oracle_address: None,
                 protocol_admin_address: None,
                 protocol_rewards_collector_address: None,
-                red_bank_address: None,
+                red_bank_address: Some("red_bank".to_string()),
                 staking_address: None,
                 treasury_address: None,
                 vesting_address: None,
-                xmars_token_address: None,
+                xmars_token_address: Some("xmars_token".to_string()),
             },
         },
         &[],
@@ -333,44 +349,54 @@ fn instantiate_airdrop_lockdrop_contracts(
     .unwrap();
 
     // Lockdrop Contract
-    let lockdrop_contract = Box::new(ContractWrapper::new(
-        mars_lockdrop::contract::execute,
-        mars_lockdrop::contract::instantiate,
-        mars_lockdrop::contract::query,
-    ));
+    let lockdrop_contract = Box::new(
+        ContractWrapper::new(
+            mars_lockdrop::contract::execute,
+            mars_lockdrop::contract::instantiate,
+            mars_lockdrop::contract::query,
+        )
+        .with_reply(mars_lockdrop::contract::reply),
+    );
 
     let lockdrop_code_id = app.store_code(lockdrop_contract);
 
     let lockdrop_msg = mars_periphery::lockdrop::InstantiateMsg {
         owner: owner.clone().to_string(),
         address_provider: None,
-        ma_ust_token: None,
+        assets: vec![mars_periphery::lockdrop::AssetParams {
+            denom: "uusd".to_string(),
+            price_weight: Decimal::one(),
+        }],
         init_timestamp: 10_000_01,
         deposit_window: 5_000_00,
         withdrawal_window: 2_000_00,
         lockup_durations: vec![
             LockupDurationParams {
                 duration: 3,
-                boost: Uint128::from(1_u64),
+                boost: Decimal::from_ratio(1_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 6,
-                boost: Uint128::from(2_u64),
+                boost: Decimal::from_ratio(2_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 9,
-                boost: Uint128::from(3_u64),
+                boost: Decimal::from_ratio(3_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 12,
-                boost: Uint128::from(4_u64),
+                boost: Decimal::from_ratio(4_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 15,
-                boost: Uint128::from(5_u64),
+                boost: Decimal::from_ratio(5_u64, 1_u64),
             },
         ],
+        boost_curve: None,
         seconds_per_duration_unit: 7 * 86400 as u64,
+        early_unlock_penalty: None,
+        referral_incentives_rate: None,
+        withdrawal_schedule: None,
     };
 
     let lockdrop_instance = app
@@ -391,14 +417,29 @@ fn instantiate_airdrop_lockdrop_contracts(
             new_config: mars_periphery::lockdrop::UpdateConfigMsg {
                 owner: None,
                 address_provider: Some(mars_address_provider_instance.clone().to_string()),
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &mars_periphery::lockdrop::ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
     mint_some_tokens(
         app,
         owner.clone(),
@@ -494,8 +535,15 @@ fn init_auction_mars_contracts(app: &mut App) -> (Addr, Addr, Addr, Addr, Addr,
             new_config: mars_periphery::lockdrop::UpdateConfigMsg {
                 owner: None,
                 address_provider: None,
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
@@ -509,9 +557,10 @@ fn init_auction_mars_contracts(app: &mut App) -> (Addr, Addr, Addr, Addr, Addr,
         &mars_periphery::airdrop::ExecuteMsg::UpdateConfig {
             owner: None,
             auction_contract_address: Some(auction_instance.clone().to_string()),
-            merkle_roots: None,
             from_timestamp: None,
             to_timestamp: None,
+            early_exit_penalty: None,
+            auction_deadline: None,
         },
         &[],
     )
@@ -782,6 +831,7 @@ fn proper_initialization_only_auction_astro() {
     );
     assert_eq!(auction_init_msg.ust_deposit_window, resp.ust_deposit_window);
     assert_eq!(auction_init_msg.withdrawal_window, resp.withdrawal_window);
+    assert_eq!(WithdrawalSchedule::default(), resp.withdrawal_schedule);
 
     // Check state
     let resp: StateResponse = app
@@ -1709,6 +1759,91 @@ fn test_withdraw_ust() {
     );
 }
 
+#[test]
+fn test_withdraw_ust_at_withdrawal_window_start() {
+    let mut app = mock_app();
+    let (_, _, auction_instance, _, _, _) = init_auction_mars_contracts(&mut app);
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_01)
+    });
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::DepositUst {},
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // Withdrawal window opens at `init_timestamp` + `ust_deposit_window`. Max 50% withdrawals allowed from then on
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(22_000_00)
+    });
+
+    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(10000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 5000 uusd"
+    );
+
+    // ######    SUCCESS :: Withdraw 50% successfully   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(5000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Max 1 withdrawal allowed during current window   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(10u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Max 1 withdrawal allowed during current window"
+    );
+}
+
 #[test]
 fn test_add_liquidity_to_astroport_pool() {
     let mut app = mock_app();
@@ -2521,3 +2656,179 @@ fn test_stake_lp_tokens_and_claim_rewards() {
         user_resp_after_claim.withdrawable_astro_incentives
     );
 }
+
+/// Querier answering the auction's queries to the airdrop contract as per the given delegations
+struct AirdropMockQuerier {
+    delegations: Vec<(String, Uint128)>,
+}
+
+impl Querier for AirdropMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let msg = match from_slice(bin_request).unwrap() {
+            QueryRequest::<Empty>::Wasm(WasmQuery::Smart { msg, .. }) => msg,
+            _ => panic!("unexpected query"),
+        };
+        let response = match from_binary(&msg).unwrap() {
+            AirdropQueryMsg::State {} => to_binary(&AirdropStateResponse {
+                total_airdrop_size: Uint128::from(10000000000u64),
+                total_delegated_amount: self.delegations.iter().map(|(_, amount)| *amount).sum(),
+                unclaimed_tokens: Uint128::zero(),
+                total_penalty_amount: Uint128::zero(),
+                recycled_penalty_amount: Uint128::zero(),
+                claimants_count: self.delegations.len() as u64,
+                withdrawers_count: 0,
+            }),
+            AirdropQueryMsg::UserInfo { address } => {
+                let delegated_amount = self
+                    .delegations
+                    .iter()
+                    .find(|(user, _)| *user == address)
+                    .map(|(_, amount)| *amount)
+                    .unwrap_or_default();
+                to_binary(&AirdropUserInfoResponse {
+                    airdrop_amount: delegated_amount,
+                    delegated_amount,
+                    tokens_withdrawn: false,
+                    withdrawn_amount: Uint128::zero(),
+                    vested_amount: Uint128::zero(),
+                    locked_amount: Uint128::zero(),
+                })
+            }
+            _ => panic!("unexpected airdrop query"),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    }
+}
+
+#[test]
+fn test_return_airdrop_delegations_after_migration() {
+    let airdrop_address = Addr::unchecked("airdrop_contract");
+    let mars_token_address = Addr::unchecked("mars_token");
+
+    let mut deps = OwnedDeps {
+        storage: MockStorage::new(),
+        api: MockApi::default(),
+        querier: AirdropMockQuerier {
+            delegations: vec![("airdrop_recipient".to_string(), Uint128::from(1000000u64))],
+        },
+    };
+
+    // Auction state as left behind by a deployment which didn't track airdrop delegations
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                owner: Addr::unchecked("owner"),
+                mars_token_address: mars_token_address.clone(),
+                astro_token_address: Addr::unchecked("astro_token"),
+                airdrop_contract_address: airdrop_address.clone(),
+                lockdrop_contract_address: Addr::unchecked("lockdrop_contract"),
+                astroport_lp_pool: None,
+                lp_token_address: None,
+                mars_lp_staking_contract: None,
+                generator_contract: Addr::unchecked("generator_contract"),
+                mars_rewards: Uint128::from(1000000000000u64),
+                mars_vesting_duration: 7776000u64,
+                lp_tokens_vesting_duration: 7776000u64,
+                init_timestamp: 100_000,
+                ust_deposit_window: 10_000_000,
+                mars_deposit_window: 10_000_000,
+                withdrawal_window: 500_000,
+                withdrawal_schedule: WithdrawalSchedule::default(),
+            },
+        )
+        .unwrap();
+    STATE
+        .save(
+            &mut deps.storage,
+            &State {
+                total_mars_deposited: Uint128::from(1500000u64),
+                ..State::default()
+            },
+        )
+        .unwrap();
+    USERS
+        .save(
+            &mut deps.storage,
+            &Addr::unchecked("airdrop_recipient"),
+            &UserInfo {
+                mars_deposited: Uint128::from(1000000u64),
+                ..UserInfo::default()
+            },
+        )
+        .unwrap();
+    USERS
+        .save(
+            &mut deps.storage,
+            &Addr::unchecked("lockdrop_depositor"),
+            &UserInfo {
+                mars_deposited: Uint128::from(500000u64),
+                ..UserInfo::default()
+            },
+        )
+        .unwrap();
+
+    // Migration backfills the airdrop delegations from the airdrop contract
+    let migrate_resp = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(
+        migrate_resp.attributes,
+        vec![
+            attr("action", "Auction::MigrateMsg"),
+            attr("airdrop_mars_deposited", "1000000"),
+            attr("users_migrated", "1"),
+        ]
+    );
+
+    // Auction fails :: delegations made before the upgrade are returned in full
+    let return_resp = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(airdrop_address.as_str(), &[]),
+        ExecuteMsg::ReturnAirdropDelegations {},
+    )
+    .unwrap();
+    assert_eq!(
+        return_resp.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: mars_token_address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: airdrop_address.to_string(),
+                amount: Uint128::from(1000000u64),
+                msg: to_binary(&AirdropCw20HookMsg::ReturnDelegatedMars {}).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::from(500000u64), state.total_mars_deposited);
+    assert_eq!(Uint128::zero(), state.airdrop_mars_deposited);
+    assert!(state.are_airdrop_delegations_returned);
+
+    let user_resp: UserInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserInfo {
+                address: "airdrop_recipient".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Uint128::zero(), user_resp.mars_deposited);
+
+    let user_resp: UserInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserInfo {
+                address: "lockdrop_depositor".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Uint128::from(500000u64), user_resp.mars_deposited);
+}
@@ -3,12 +3,17 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
+use mars_periphery::cw721::{
+    AllNftInfoResponse, ApprovedForAllResponse, ContractInfoResponse, NftInfoResponse,
+    NumTokensResponse, OwnerOfResponse, TokensResponse,
+};
 use mars_periphery::lockdrop::{
-    CallbackMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, LockupInfoResponse, QueryMsg,
-    UpdateConfigMsg, UserInfoResponse,
+    CallbackMsg, ConfigResponse, Cw20HookMsg, EmergencyStateResponse, ExecuteMsg, InstantiateMsg,
+    LockupInfoResponse, QueryMsg, ReferrerResponse, SimulateDepositResponse, SimulateUserResponse,
+    SimulateWeightResponse, UpdateConfigMsg, UserInfoResponse,
 };
 
-use mars_lockdrop::state::{Config, LockupInfo, State, UserInfo};
+use mars_lockdrop::state::{Config, EmergencyState, LockupInfo, State, UserInfo};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -20,13 +25,27 @@ fn main() {
     export_schema(&schema_for!(UpdateConfigMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(CallbackMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(UserInfoResponse), &out_dir);
     export_schema(&schema_for!(LockupInfoResponse), &out_dir);
+    export_schema(&schema_for!(SimulateWeightResponse), &out_dir);
+    export_schema(&schema_for!(SimulateUserResponse), &out_dir);
+    export_schema(&schema_for!(SimulateDepositResponse), &out_dir);
+    export_schema(&schema_for!(EmergencyStateResponse), &out_dir);
+    export_schema(&schema_for!(ReferrerResponse), &out_dir);
+    export_schema(&schema_for!(OwnerOfResponse), &out_dir);
+    export_schema(&schema_for!(ApprovedForAllResponse), &out_dir);
+    export_schema(&schema_for!(NumTokensResponse), &out_dir);
+    export_schema(&schema_for!(ContractInfoResponse), &out_dir);
+    export_schema(&schema_for!(NftInfoResponse), &out_dir);
+    export_schema(&schema_for!(AllNftInfoResponse), &out_dir);
+    export_schema(&schema_for!(TokensResponse), &out_dir);
 
     export_schema(&schema_for!(Config), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
     export_schema(&schema_for!(UserInfo), &out_dir);
     export_schema(&schema_for!(LockupInfo), &out_dir);
+    export_schema(&schema_for!(EmergencyState), &out_dir);
 }
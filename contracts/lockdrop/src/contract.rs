@@ -1,37 +1,61 @@
-use std::ops::Mul;
-
 use cosmwasm_std::{
-    entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut,
-    Env, Event, MessageInfo, Order, QuerierWrapper, QueryRequest, Response, StdError, StdResult,
-    Uint128, WasmMsg, WasmQuery,
+    entry_point, from_binary, to_binary, Addr, Binary, Coin, ContractResult, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, Event, Fraction, MessageInfo, Order, QuerierWrapper, QueryRequest, Reply,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20QueryMsg, Cw20ReceiveMsg, Expiration, TokenInfoResponse};
+use cw_storage_plus::{Bound, Prefix, PrimaryKey, U64Key};
 
 // TODO: Change to mars_core when repo and dependencies become public and replace package
 // with the mars_core one
 use cw2::set_contract_version;
 use mars_core_deps::address_provider::msg::QueryMsg as AddressProviderQueryMsg;
 use mars_core_deps::address_provider::MarsContract;
+use mars_core_deps::asset::Asset as RedBankAsset;
+use mars_core_deps::incentives::msg::AssetIncentiveResponse;
 use mars_core_deps::incentives::msg::ExecuteMsg as IncentivesExecuteMsg;
 use mars_core_deps::incentives::msg::QueryMsg as IncentivesQueryMsg;
 use mars_core_deps::red_bank::msg::ExecuteMsg as RedBankExecuteMsg;
+use mars_core_deps::red_bank::msg::QueryMsg as RedBankQueryMsg;
+use mars_core_deps::red_bank::Market;
 
 use mars_periphery::auction::Cw20HookMsg as AuctionCw20HookMsg;
+use mars_periphery::cw721::{
+    AllNftInfoResponse, Approval as Cw721Approval, ApprovedForAllResponse, ContractInfoResponse,
+    Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse,
+};
 use mars_periphery::helpers::{
     build_send_cw20_token_msg, build_send_native_asset_msg, build_transfer_cw20_token_msg,
-    cw20_get_balance,
+    cw20_get_amount_received_from_events, cw20_get_balance,
 };
 use mars_periphery::lockdrop::{
-    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupInfoQueryData,
-    LockupInfoResponse, MigrateMsg, QueryMsg, StateResponse, UpdateConfigMsg, UserInfoResponse,
+    format_lockup_id, parse_lockup_id, AssetInfo, AssetParams, AssetState, BoostCurve, CallbackMsg,
+    ConfigResponse, Cw20HookMsg, EarlyUnlockPenalty, EmergencyStateResponse, ExecuteMsg,
+    InstantiateMsg, LockupDurationParams, LockupInfoQueryData, LockupInfoResponse, MarsAddresses,
+    MigrateMsg, QueryMsg, ReferrerResponse, SimulateDepositResponse, SimulateUserResponse,
+    SimulateWeightResponse, SimulatedLockupPosition, StateResponse, UpdateConfigMsg,
+    UserInfoResponse,
 };
 use mars_periphery::tax::deduct_tax;
 
-use crate::state::{Config, State, UserInfo, CONFIG, LOCKUP_INFO, STATE, USER_INFO};
-use std::collections::HashSet;
+use crate::state::{
+    default_early_unlock_penalty_rate, default_nuke_batch_size, Approval, Config, EmergencyState,
+    LegacyMigrationState, LockupInfo, PendingRedBankDeposit, PendingXmarsClaim, ReferrerInfo,
+    State, UserInfo, CLAIM_XMARS_REPLY_ID, CONFIG, DEFAULT_MIGRATION_BATCH_SIZE, EMERGENCY,
+    LEGACY_CONFIG, LEGACY_LOCKUP_INFO, LEGACY_MIGRATION, LEGACY_STATE, LEGACY_USER_INFO,
+    LOCKUP_INFO, LOCKUP_INFO_KEY, LOCKUP_SEQUENCE, OPERATORS, PENDING_RED_BANK_DEPOSITS,
+    PENDING_XMARS_CLAIM, RED_BANK_DEPOSIT_REPLY_ID_OFFSET, REFERRERS, STATE, USER_INFO, UUSD_DENOM,
+};
+use std::convert::TryInto;
+
+// cw721 metadata of the lockup positions
+const NFT_NAME: &str = "Mars Lockdrop Position";
+const NFT_SYMBOL: &str = "MARS-LOCKUP";
 
-const UUSD_DENOM: &str = "uusd";
+// settings for pagination
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "mars_lockdrop";
@@ -73,37 +97,71 @@ pub fn instantiate(
         ));
     }
 
+    let boost_curve = msg.boost_curve.unwrap_or_default();
+    validate_boost_curve(&msg.lockup_durations, &boost_curve)?;
+
+    let withdrawal_schedule = msg.withdrawal_schedule.unwrap_or_default();
+    withdrawal_schedule.validate()?;
+
     let mut config = Config {
         owner: deps.api.addr_validate(&msg.owner)?,
         address_provider: None,
-        ma_ust_token: None,
+        mars_addresses: None,
+        assets: vec![],
         auction_contract_address: None,
         init_timestamp: msg.init_timestamp,
         deposit_window: msg.deposit_window,
         withdrawal_window: msg.withdrawal_window,
         lockup_durations: msg.lockup_durations,
+        boost_curve,
         seconds_per_duration_unit: msg.seconds_per_duration_unit,
         lockdrop_incentives: Uint128::zero(),
+        early_unlock_penalty_rate: default_early_unlock_penalty_rate(),
+        treasury_address: None,
+        guardian: None,
+        nuke_batch_size: default_nuke_batch_size(),
+        referral_incentives_rate: Decimal::zero(),
+        withdrawal_schedule,
     };
 
-    if msg.address_provider.is_some() {
-        config.address_provider = Some(deps.api.addr_validate(&msg.address_provider.unwrap())?);
+    if let Some(early_unlock_penalty) = msg.early_unlock_penalty {
+        set_early_unlock_penalty(deps.as_ref(), &mut config, early_unlock_penalty)?;
+    }
+    if let Some(referral_incentives_rate) = msg.referral_incentives_rate {
+        set_referral_incentives_rate(&mut config, referral_incentives_rate)?;
     }
-    if msg.ma_ust_token.is_some() {
-        config.ma_ust_token = Some(deps.api.addr_validate(&msg.ma_ust_token.unwrap())?);
+
+    if msg.address_provider.is_some() {
+        let address_provider = deps.api.addr_validate(&msg.address_provider.unwrap())?;
+        // Addresses not yet registered in the address provider are resolved later via `RefreshAddresses`
+        config.mars_addresses =
+            resolve_mars_addresses(&deps.querier, address_provider.clone()).ok();
+        config.address_provider = Some(address_provider);
     }
 
-    let state = State {
-        final_ust_locked: Uint128::zero(),
-        final_maust_locked: Uint128::zero(),
-        total_ust_locked: Uint128::zero(),
+    let mut state = State {
+        assets: vec![],
         total_maust_locked: Uint128::zero(),
         total_deposits_weight: Uint128::zero(),
         total_mars_delegated: Uint128::zero(),
         are_claims_allowed: false,
         xmars_rewards_index: Decimal::zero(),
+        total_mars_forfeited: Uint128::zero(),
+        forfeited_mars_index: Decimal::zero(),
+        total_compounded_xmars: Uint128::zero(),
+        total_compounding_shares: Uint128::zero(),
+        total_referred_weight: Uint128::zero(),
+        num_tokens: 0u64,
     };
 
+    // CHECK :: At-least 1 asset needs to be accepted for deposits
+    update_assets(&mut config, &mut state, msg.assets, true)?;
+    if config.assets.is_empty() {
+        return Err(StdError::generic_err(
+            "At-least 1 asset needs to be accepted for deposits",
+        ));
+    }
+
     CONFIG.save(deps.storage, &config)?;
     STATE.save(deps.storage, &state)?;
     Ok(Response::default())
@@ -111,29 +169,87 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    // CHECK :: Lockdrop is disabled until the legacy lockup positions are migrated
+    if !matches!(msg, ExecuteMsg::MigrateLegacyPositions { .. })
+        && LEGACY_MIGRATION
+            .may_load(deps.storage)?
+            .map_or(false, |migration| !migration.is_complete)
+    {
+        return Err(StdError::generic_err(
+            "Legacy lockup positions migration in progress",
+        ));
+    }
+
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::UpdateConfig { new_config } => update_config(deps, env, info, new_config),
-        ExecuteMsg::DepositUst { duration } => try_deposit_ust(deps, env, info, duration),
-        ExecuteMsg::WithdrawUst { duration, amount } => {
-            try_withdraw_ust(deps, env, info, duration, amount)
-        }
+        ExecuteMsg::DepositUst {
+            duration,
+            recipient,
+            referrer,
+        } => try_deposit_ust(deps, env, info, duration, recipient, referrer),
+        ExecuteMsg::WithdrawUst {
+            duration,
+            amount,
+            lockup_id,
+        } => try_withdraw_ust(deps, env, info, duration, amount, lockup_id),
+        ExecuteMsg::ExtendLockup {
+            from_duration,
+            to_duration,
+            lockup_id,
+        } => handle_extend_lockup(deps, env, info, from_duration, to_duration, lockup_id),
         ExecuteMsg::DepositMarsToAuction { amount } => {
             handle_deposit_mars_to_auction(deps, env, info, amount)
         }
         ExecuteMsg::EnableClaims {} => handle_enable_claims(deps, env, info),
+        ExecuteMsg::ClaimReferralRewards {} => handle_claim_referral_rewards(deps, info),
+        ExecuteMsg::SetCompounding { enabled } => handle_set_compounding(deps, info, enabled),
+        ExecuteMsg::RefreshAddresses {} => handle_refresh_addresses(deps, info),
         ExecuteMsg::DepositUstInRedBank {} => try_deposit_in_red_bank(deps, env, info),
         ExecuteMsg::ClaimRewardsAndUnlock {
             unlock_for_addr,
             lockup_to_unlock_duration,
+            lockup_to_unlock_id,
         } => handle_claim_rewards_and_unlock_position(
             deps,
             env,
             info,
             unlock_for_addr,
             lockup_to_unlock_duration,
+            lockup_to_unlock_id,
         ),
+        ExecuteMsg::PartialUnlock {
+            duration,
+            amount,
+            lockup_id,
+        } => handle_partial_unlock(deps, env, info, duration, amount, lockup_id),
+        ExecuteMsg::EnterEmergencyMode {} => handle_enter_emergency_mode(deps, env, info),
         ExecuteMsg::NukeLockdrop {} => handle_nuke_lockdrop(deps, env, info),
+        ExecuteMsg::EmergencyExit {} => handle_emergency_exit(deps, env, info),
+        ExecuteMsg::MigrateLegacyPositions { limit } => {
+            handle_migrate_legacy_positions(deps, info, limit)
+        }
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => handle_transfer_nft(deps, env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => handle_send_nft(deps, env, info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => handle_approve(deps, env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            handle_revoke(deps, env, info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            handle_approve_all(deps, env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => handle_revoke_all(deps, info, operator),
         ExecuteMsg::Callback(msg) => _handle_callback(deps, env, info, msg),
     }
 }
@@ -151,22 +267,250 @@ fn _handle_callback(
         ));
     }
     match msg {
-        CallbackMsg::UpdateStateOnRedBankDeposit {
-            prev_ma_ust_balance,
-        } => update_state_on_red_bank_deposit(deps, env, prev_ma_ust_balance),
-        CallbackMsg::UpdateStateOnClaim {
+        CallbackMsg::UpdateStateOnClaim { user } => update_state_on_claim(deps, env, user),
+        CallbackMsg::DissolvePosition {
             user,
-            prev_xmars_balance,
-        } => update_state_on_claim(deps, env, user, prev_xmars_balance),
-        CallbackMsg::DissolvePosition { user, duration } => {
-            try_dissolve_position(deps, env, user, duration)
-        }
+            lockup_id,
+            mars_to_redistribute,
+        } => try_dissolve_position(deps, env, user, lockup_id, mars_to_redistribute),
+        CallbackMsg::PartialUnlockPosition {
+            user,
+            lockup_id,
+            amount,
+        } => try_partial_unlock_position(deps, env, user, lockup_id, amount),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
+    let events = match msg.result {
+        ContractResult::Ok(response) => response.events,
+        ContractResult::Err(err) => return Err(StdError::generic_err(err)),
+    };
+
+    if msg.id == CLAIM_XMARS_REPLY_ID {
+        return update_state_on_xmars_claim(deps, env, events);
+    }
+    update_state_on_red_bank_deposit(deps, env, msg.id, events)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // MIGRATE :: UST specific config / state moved to the uusd asset
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    if config.assets.is_empty() {
+        let legacy_config = LEGACY_CONFIG.load(deps.storage)?;
+        let legacy_state = LEGACY_STATE.load(deps.storage)?;
+
+        config.assets.push(AssetInfo {
+            denom: UUSD_DENOM.to_string(),
+            ma_token: legacy_config.ma_ust_token,
+            price_weight: Decimal::one(),
+        });
+        // maUST held is weighted by a price weight of 1, i.e. total_maust_locked remains unchanged
+        state.assets.push(AssetState {
+            denom: UUSD_DENOM.to_string(),
+            final_locked: legacy_state.final_ust_locked,
+            final_ma_locked: legacy_state.final_maust_locked,
+            total_locked: legacy_state.total_ust_locked,
+            total_ma_locked: state.total_maust_locked,
+        });
+        CONFIG.save(deps.storage, &config)?;
+        STATE.save(deps.storage, &state)?;
+
+        // Legacy users / lockup positions are migrated in batches, see `MigrateLegacyPositions`
+        LEGACY_MIGRATION.save(deps.storage, &LegacyMigrationState::default())?;
+    }
+
+    // MIGRATE :: Mars addresses re-resolved, the cached ones being kept if the address provider can't resolve them
+    if let Some(address_provider) = config.address_provider.clone() {
+        if let Ok(mars_addresses) = resolve_mars_addresses(&deps.querier, address_provider) {
+            config.mars_addresses = Some(mars_addresses);
+            CONFIG.save(deps.storage, &config)?;
+        }
+    }
+
+    let mut users_migrated = 0u64;
+    let mut lockups_migrated = 0u64;
+    if let Some(mut migration) = LEGACY_MIGRATION.may_load(deps.storage)? {
+        if !migration.is_complete {
+            let (users_count, lockups_count) = migrate_legacy_positions_batch(
+                deps.storage,
+                &mut state,
+                &mut migration,
+                DEFAULT_MIGRATION_BATCH_SIZE,
+            )?;
+            users_migrated = users_count;
+            lockups_migrated = lockups_count;
+            STATE.save(deps.storage, &state)?;
+            LEGACY_MIGRATION.save(deps.storage, &migration)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::MigrateMsg"),
+        ("users_migrated", users_migrated.to_string().as_str()),
+        (
+            "lockup_positions_migrated",
+            lockups_migrated.to_string().as_str(),
+        ),
+    ]))
+}
+
+/// @dev Admin function. Migrates the next batch of legacy users / lockup positions, resuming after the last one migrated
+/// @params limit : Maximum number of users / lockup positions to migrate
+pub fn handle_migrate_legacy_positions(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: Only owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Legacy users / lockup positions are left to be migrated
+    let mut migration = LEGACY_MIGRATION
+        .may_load(deps.storage)?
+        .filter(|migration| !migration.is_complete)
+        .ok_or_else(|| StdError::generic_err("No legacy lockup positions left to migrate"))?;
+
+    let limit = limit.unwrap_or(DEFAULT_MIGRATION_BATCH_SIZE);
+    if limit == 0 {
+        return Err(StdError::generic_err("Invalid limit"));
+    }
+
+    let (users_migrated, lockups_migrated) =
+        migrate_legacy_positions_batch(deps.storage, &mut state, &mut migration, limit)?;
+
+    STATE.save(deps.storage, &state)?;
+    LEGACY_MIGRATION.save(deps.storage, &migration)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::MigrateLegacyPositions"),
+        ("users_migrated", users_migrated.to_string().as_str()),
+        (
+            "lockup_positions_migrated",
+            lockups_migrated.to_string().as_str(),
+        ),
+        ("is_complete", migration.is_complete.to_string().as_str()),
+    ]))
+}
+
+/// @dev Migrates up to `limit` legacy users, then legacy lockup positions, updating the migration progress. Returns the number of users and lockup positions migrated
+fn migrate_legacy_positions_batch(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    migration: &mut LegacyMigrationState,
+    limit: u32,
+) -> StdResult<(u64, u64)> {
+    let mut users_migrated = 0u64;
+    let mut budget = limit as usize;
+
+    // MIGRATE :: UST locked by the users moved to their amounts locked per asset
+    if !migration.are_users_migrated {
+        let start = migration
+            .users_start_after
+            .as_ref()
+            .map(|start_after| Bound::exclusive(start_after.as_bytes()));
+        let legacy_users = LEGACY_USER_INFO
+            .range(storage, start, None, Order::Ascending)
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (user_bytes, legacy_user_info) in legacy_users.iter() {
+            let user = Addr::unchecked(String::from_utf8(user_bytes.clone())?);
+            // Re-saving drops `total_ust_locked`, users are only migrated once
+            if let Some(total_ust_locked) = legacy_user_info.total_ust_locked {
+                let mut user_info = USER_INFO.load(storage, &user)?;
+                add_locked_amount(
+                    &mut user_info.total_locked,
+                    &Coin {
+                        denom: UUSD_DENOM.to_string(),
+                        amount: total_ust_locked,
+                    },
+                )?;
+                USER_INFO.save(storage, &user, &user_info)?;
+                users_migrated += 1;
+            }
+            migration.users_start_after = Some(user);
+        }
+
+        migration.are_users_migrated = legacy_users.len() < budget;
+        budget -= legacy_users.len();
+    }
+
+    // MIGRATE :: Lockup positions keyed by `depositor + duration` are moved to (depositor, duration, seq) keys
+    // Migrated positions are removed, the remaining ones are always found from the start
+    let legacy_lockups = if migration.are_users_migrated {
+        LEGACY_LOCKUP_INFO
+            .range(storage, None, None, Order::Ascending)
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?
+    } else {
+        vec![]
+    };
+
+    for (legacy_key, legacy_lockup) in legacy_lockups.iter() {
+        // Legacy ids are ambiguous on their own, the stored duration tells where the depositor address ends
+        let legacy_id = String::from_utf8(legacy_key.clone())?;
+        let depositor = legacy_id
+            .strip_suffix(&legacy_lockup.duration.to_string())
+            .filter(|depositor| !depositor.is_empty())
+            .map(Addr::unchecked)
+            .ok_or_else(|| {
+                StdError::generic_err(format!("Invalid legacy lockup id {}", legacy_id))
+            })?;
+        let owner = legacy_lockup
+            .owner
+            .clone()
+            .unwrap_or_else(|| depositor.clone());
+
+        let lockup_id = format_lockup_id(depositor.as_str(), legacy_lockup.duration, 0u64);
+        let lockup_info = LockupInfo {
+            owner: owner.clone(),
+            duration: legacy_lockup.duration,
+            denom: UUSD_DENOM.to_string(),
+            amount_locked: legacy_lockup.ust_locked,
+            lockdrop_reward: legacy_lockup.lockdrop_reward,
+            unlock_timestamp: legacy_lockup.unlock_timestamp,
+            withdrawal_flag: legacy_lockup.withdrawal_flag,
+            approvals: legacy_lockup.approvals.clone(),
+            referrer: None,
+        };
+        LOCKUP_INFO.save(storage, lockup_key(&lockup_id)?, &lockup_info)?;
+        LOCKUP_SEQUENCE.save(
+            storage,
+            (&depositor, U64Key::new(legacy_lockup.duration)),
+            &1u64,
+        )?;
+
+        // UPDATE :: Lockup position id in the owner's positions
+        if let Some(mut user_info) = USER_INFO.may_load(storage, &owner)? {
+            for position_id in user_info.lockup_positions.iter_mut() {
+                if *position_id == legacy_id {
+                    *position_id = lockup_id.clone();
+                }
+            }
+            USER_INFO.save(storage, &owner, &user_info)?;
+        }
+
+        LEGACY_LOCKUP_INFO.remove(storage, legacy_key);
+    }
+    state.num_tokens += legacy_lockups.len() as u64;
+
+    // UPDATE :: Migration is complete once a batch of lockup positions isn't full
+    migration.users_migrated += users_migrated;
+    migration.lockups_migrated += legacy_lockups.len() as u64;
+    migration.is_complete = migration.are_users_migrated && legacy_lockups.len() < budget;
+
+    Ok((users_migrated, legacy_lockups.len() as u64))
 }
 
 pub fn receive_cw20(
@@ -186,6 +530,10 @@ pub fn receive_cw20(
         Cw20HookMsg::IncreaseMarsIncentives {} => {
             handle_increase_mars_incentives(deps, env, info, cw20_msg.amount)
         }
+        Cw20HookMsg::ForceUnlock { lockup_id } => {
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            handle_force_unlock(deps, env, info, sender, cw20_msg.amount, lockup_id)
+        }
     }
 }
 
@@ -204,6 +552,66 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::WithdrawalPercentAllowed { timestamp } => {
             to_binary(&query_max_withdrawable_percent(deps, env, timestamp)?)
         }
+        QueryMsg::EmergencyState {} => to_binary(&query_emergency_state(deps)?),
+        QueryMsg::Referrer { address } => to_binary(&query_referrer(deps, address)?),
+        QueryMsg::SimulateWeight {
+            amount,
+            duration,
+            denom,
+        } => to_binary(&query_simulate_weight(deps, amount, duration, denom)?),
+        QueryMsg::SimulateUser { address, timestamp } => {
+            to_binary(&query_simulate_user(deps, env, address, timestamp)?)
+        }
+        QueryMsg::SimulateDeposit {
+            amount,
+            duration,
+            timestamp,
+            denom,
+        } => to_binary(&query_simulate_deposit(
+            deps, env, amount, duration, timestamp, denom,
+        )?),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_binary(&query_owner_of(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::ApprovedForAll {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => to_binary(&query_approved_for_all(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?),
+        QueryMsg::NumTokens {} => to_binary(&query_num_tokens(deps)?),
+        QueryMsg::ContractInfo {} => to_binary(&query_contract_info()),
+        QueryMsg::NftInfo { token_id } => to_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_binary(&query_all_nft_info(
+            deps,
+            env,
+            token_id,
+            include_expired.unwrap_or(false),
+        )?),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -221,17 +629,7 @@ pub fn handle_increase_mars_incentives(
 ) -> Result<Response, StdError> {
     let mut config = CONFIG.load(deps.storage)?;
 
-    if config.address_provider.is_none() {
-        return Err(StdError::generic_err("Address provider not set"));
-    }
-
-    let mars_token_address = query_address(
-        &deps.querier,
-        config.address_provider.clone().unwrap(),
-        MarsContract::MarsToken,
-    )?;
-
-    if info.sender != mars_token_address {
+    if info.sender != mars_addresses(&config)?.mars_token {
         return Err(StdError::generic_err("Only mars tokens are received!"));
     }
 
@@ -248,11 +646,11 @@ pub fn handle_increase_mars_incentives(
         .add_attribute("amount", amount))
 }
 
-/// @dev ADMIN Function. Facilitates state update. Will be used to set address_provider / maUST token address most probably, based on deployment schedule
+/// @dev ADMIN Function. Facilitates state update. Will be used to set address_provider / auction contract addresses most probably, based on deployment schedule
 /// @params new_config : New configuration struct
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_config: UpdateConfigMsg,
 ) -> StdResult<Response> {
@@ -263,13 +661,22 @@ pub fn update_config(
 
     // UPDATE :: ADDRESSES IF PROVIDED
     if new_config.address_provider.is_some() {
-        config.address_provider = Some(
-            deps.api
-                .addr_validate(&new_config.address_provider.unwrap())?,
-        );
+        let address_provider = deps
+            .api
+            .addr_validate(&new_config.address_provider.unwrap())?;
+        // Addresses cached from the previous address provider are dropped if they can't be resolved from the new one
+        if config.address_provider.as_ref() != Some(&address_provider) {
+            config.mars_addresses =
+                resolve_mars_addresses(&deps.querier, address_provider.clone()).ok();
+        }
+        config.address_provider = Some(address_provider);
     }
-    if new_config.ma_ust_token.is_some() {
-        config.ma_ust_token = Some(deps.api.addr_validate(&new_config.ma_ust_token.unwrap())?);
+    if let Some(assets) = new_config.assets {
+        // Assets can only be added / re-weighted before deposits are weighted with their price weight
+        let mut state = STATE.load(deps.storage)?;
+        let can_update_weights = env.block.time.seconds() < config.init_timestamp;
+        update_assets(&mut config, &mut state, assets, can_update_weights)?;
+        STATE.save(deps.storage, &state)?;
     }
     if new_config.auction_contract_address.is_some() {
         config.auction_contract_address = Some(
@@ -280,102 +687,233 @@ pub fn update_config(
     if new_config.owner.is_some() {
         config.owner = deps.api.addr_validate(&new_config.owner.unwrap())?;
     }
+    if let Some(guardian) = new_config.guardian {
+        config.guardian = Some(deps.api.addr_validate(&guardian)?);
+    }
+    if let Some(nuke_batch_size) = new_config.nuke_batch_size {
+        // CHECK :: nuke_batch_size needs to be valid
+        if nuke_batch_size == 0u32 {
+            return Err(StdError::generic_err("nuke_batch_size cannot be 0"));
+        }
+        config.nuke_batch_size = nuke_batch_size;
+    }
+    if let Some(early_unlock_penalty) = new_config.early_unlock_penalty {
+        // CHECK :: Penalty can't be updated once lockup positions can be unlocked
+        let state = STATE.load(deps.storage)?;
+        if state.are_claims_allowed {
+            return Err(StdError::generic_err(
+                "Early unlock penalty can only be updated before claims are enabled",
+            ));
+        }
+        set_early_unlock_penalty(deps.as_ref(), &mut config, early_unlock_penalty)?;
+    }
+    if new_config.lockup_durations.is_some() || new_config.boost_curve.is_some() {
+        // CHECK :: Boosts can't be updated once deposits are weighted with them
+        if env.block.time.seconds() >= config.init_timestamp {
+            return Err(StdError::generic_err(
+                "Lockup durations / boost curve can only be updated before the deposit window opens",
+            ));
+        }
+        if let Some(lockup_durations) = new_config.lockup_durations {
+            config.lockup_durations = lockup_durations;
+        }
+        if let Some(boost_curve) = new_config.boost_curve {
+            config.boost_curve = boost_curve;
+        }
+        validate_boost_curve(&config.lockup_durations, &config.boost_curve)?;
+    }
+    if let Some(referral_incentives_rate) = new_config.referral_incentives_rate {
+        // CHECK :: Referral incentives can't be updated once deposits are referred
+        if env.block.time.seconds() >= config.init_timestamp {
+            return Err(StdError::generic_err(
+                "Referral incentives rate can only be updated before the deposit window opens",
+            ));
+        }
+        set_referral_incentives_rate(&mut config, referral_incentives_rate)?;
+    }
+    if let Some(withdrawal_schedule) = new_config.withdrawal_schedule {
+        // CHECK :: Withdrawal limits can't be updated once deposits are made
+        if env.block.time.seconds() >= config.init_timestamp {
+            return Err(StdError::generic_err(
+                "Withdrawal schedule can only be updated before the deposit window opens",
+            ));
+        }
+        withdrawal_schedule.validate()?;
+        config.withdrawal_schedule = withdrawal_schedule;
+    }
 
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "lockdrop::ExecuteMsg::UpdateConfig"))
 }
 
-/// @dev Facilitates UST deposits locked for selected number of weeks
-/// @param duration : Number of weeks for which UST will be locked
+/// @dev ADMIN Function. Resolves the Red Bank, incentives contract, MARS and xMARS token addresses via the address provider.
+/// The resolved addresses are cached until the next refresh
+pub fn handle_refresh_addresses(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Only Owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Address provider should be set
+    let address_provider = config
+        .address_provider
+        .clone()
+        .ok_or_else(|| StdError::generic_err("Address provider not set"))?;
+
+    let mars_addresses = resolve_mars_addresses(&deps.querier, address_provider)?;
+    config.mars_addresses = Some(mars_addresses.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::RefreshAddresses"),
+        ("red_bank", mars_addresses.red_bank.as_str()),
+        ("incentives", mars_addresses.incentives.as_str()),
+        ("mars_token", mars_addresses.mars_token.as_str()),
+        ("xmars_token", mars_addresses.xmars_token.as_str()),
+    ]))
+}
+
+/// @dev Facilitates deposits of the accepted assets locked for selected number of weeks
+/// @param duration : Number of weeks for which the asset will be locked
+/// @param recipient : Address owning the lockup position. Defaults to the sender
+/// @param referrer : Address credited with the weight of the deposit, if any
 pub fn try_deposit_ust(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     duration: u64,
+    recipient: Option<String>,
+    referrer: Option<String>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
 
-    let depositor_address = info.sender.clone();
+    let depositor_address = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+    let referrer_address = referrer
+        .map(|referrer| deps.api.addr_validate(&referrer))
+        .transpose()?;
+
+    // CHECK :: Depositors can't refer themselves
+    if referrer_address.as_ref() == Some(&depositor_address) {
+        return Err(StdError::generic_err("Cannot refer yourself"));
+    }
 
     // CHECK :: Lockdrop deposit window open
     if !is_deposit_open(env.block.time.seconds(), &config) {
         return Err(StdError::generic_err("Deposit window closed"));
     }
 
+    // CHECK :: Lockdrop is not in emergency mode
+    if EMERGENCY.may_load(deps.storage)?.is_some() {
+        return Err(StdError::generic_err("Lockdrop is in emergency mode"));
+    }
+
     // Check if multiple native coins sent by the user
     if info.funds.len() > 1 {
         return Err(StdError::generic_err("Trying to deposit several coins"));
     }
 
     let native_token = info.funds.first().unwrap();
-    if native_token.denom != *UUSD_DENOM {
-        return Err(StdError::generic_err(
-            "Only UST among native tokens accepted",
-        ));
-    }
+    let asset = find_asset(&config, &native_token.denom).map_err(|_| {
+        StdError::generic_err(format!("{} not accepted for deposits", native_token.denom))
+    })?;
     // CHECK ::: Amount needs to be valid
     if native_token.amount.is_zero() {
         return Err(StdError::generic_err("Amount must be greater than 0"));
     }
 
-    let deposit_weight = calculate_weight(native_token.amount, duration, &config)?;
+    let deposit_weight =
+        calculate_weight(native_token.amount, asset.price_weight, duration, &config)?;
 
-    // LOCKUP INFO :: RETRIEVE --> UPDATE
-    let lockup_id = depositor_address.to_string() + &duration.to_string();
-    let mut lockup_info = LOCKUP_INFO
-        .may_load(deps.storage, lockup_id.as_bytes())?
+    // LOCKUP INFO :: Each deposit opens a new lockup position
+    let seq = LOCKUP_SEQUENCE
+        .may_load(deps.storage, (&depositor_address, U64Key::new(duration)))?
         .unwrap_or_default();
-
-    lockup_info.ust_locked += native_token.amount;
+    let lockup_id = format_lockup_id(depositor_address.as_str(), duration, seq);
+    let lockup_info = LockupInfo {
+        owner: depositor_address.clone(),
+        duration,
+        denom: native_token.denom.clone(),
+        amount_locked: native_token.amount,
+        unlock_timestamp: calculate_unlock_timestamp(&config, duration),
+        referrer: referrer_address.clone(),
+        ..Default::default()
+    };
 
     // USER INFO :: RETRIEVE --> UPDATE
     let mut user_info = USER_INFO
         .may_load(deps.storage, &depositor_address)?
         .unwrap_or_default();
 
-    user_info.total_ust_locked += native_token.amount;
-
-    if lockup_info.duration == 0u64 {
-        lockup_info.duration = duration;
-        lockup_info.unlock_timestamp = calculate_unlock_timestamp(&config, duration);
-        user_info.lockup_positions.push(lockup_id.clone());
-    }
+    add_locked_amount(&mut user_info.total_locked, native_token)?;
+    user_info.lockup_positions.push(lockup_id.clone());
 
     // STATE :: UPDATE --> SAVE
-    state.total_ust_locked += native_token.amount;
+    let asset_state = find_asset_state_mut(&mut state, &native_token.denom)?;
+    asset_state.total_locked += native_token.amount;
     state.total_deposits_weight += deposit_weight;
 
+    // REFERRER INFO :: Credited with the deposit's weight
+    let mut attributes = vec![];
+    if let Some(referrer_address) = &referrer_address {
+        let mut referrer_info = REFERRERS
+            .may_load(deps.storage, referrer_address)?
+            .unwrap_or_default();
+        referrer_info.referred_weight += deposit_weight;
+        referrer_info.deposits_referred += 1;
+        add_locked_amount(&mut referrer_info.total_referred, native_token)?;
+        state.total_referred_weight += deposit_weight;
+        REFERRERS.save(deps.storage, referrer_address, &referrer_info)?;
+        attributes.push(("referrer", referrer_address.to_string()));
+    }
+    state.num_tokens += 1;
+
     STATE.save(deps.storage, &state)?;
-    LOCKUP_INFO.save(deps.storage, lockup_id.as_bytes(), &lockup_info)?;
+    LOCKUP_INFO.save(deps.storage, lockup_key(&lockup_id)?, &lockup_info)?;
+    LOCKUP_SEQUENCE.save(
+        deps.storage,
+        (&depositor_address, U64Key::new(duration)),
+        &(seq + 1),
+    )?;
     USER_INFO.save(deps.storage, &depositor_address, &user_info)?;
 
-    Ok(Response::new().add_attributes(vec![
-        ("action", "lockdrop::ExecuteMsg::lock_ust"),
-        ("user", &depositor_address.to_string()),
-        ("duration", duration.to_string().as_str()),
-        ("ust_deposited", native_token.amount.to_string().as_str()),
-    ]))
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::lock_ust"),
+            ("user", &depositor_address.to_string()),
+            ("depositor", info.sender.as_str()),
+            ("duration", duration.to_string().as_str()),
+            ("denom", native_token.denom.as_str()),
+            ("amount_deposited", native_token.amount.to_string().as_str()),
+            ("lockup_id", lockup_id.as_str()),
+        ])
+        .add_attributes(attributes))
 }
 
-/// @dev Facilitates UST withdrawal from an existing Lockup position. Can only be called when deposit / withdrawal window is open
+/// @dev Facilitates withdrawal of the asset locked in an existing Lockup position. Can only be called when deposit / withdrawal window is open
 /// @param duration : Duration of the lockup position from which withdrawal is to be made
-/// @param withdraw_amount :  UST amount to be withdrawn
+/// @param withdraw_amount :  Amount of the asset to be withdrawn
+/// @param lockup_id : Id of the lockup position. Required if the user holds several positions with the same duration
 pub fn try_withdraw_ust(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     duration: u64,
     withdraw_amount: Uint128,
+    lockup_id: Option<String>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
 
     // USER ADDRESS AND LOCKUP DETAILS
     let withdrawer_address = info.sender;
-    let lockup_id = withdrawer_address.to_string() + &duration.to_string();
-    let mut lockup_info = LOCKUP_INFO
-        .may_load(deps.storage, lockup_id.as_bytes())?
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &withdrawer_address)?
         .unwrap_or_default();
 
     // CHECK :: Lockdrop withdrawal window open
@@ -384,13 +922,13 @@ pub fn try_withdraw_ust(
     }
 
     // CHECK :: Valid Lockup
-    if lockup_info.ust_locked.is_zero() {
-        return Err(StdError::generic_err("Lockup doesn't exist"));
-    }
+    let lockup_id = find_user_lockup_id(deps.storage, &user_info, duration, lockup_id)?
+        .ok_or_else(|| StdError::generic_err("Lockup doesn't exist"))?;
+    let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
 
     // Check :: Amount should be within the allowed withdrawal limit bounds
     let max_withdrawal_percent = allowed_withdrawal_percent(env.block.time.seconds(), &config);
-    let max_withdrawal_allowed = lockup_info.ust_locked * max_withdrawal_percent;
+    let max_withdrawal_allowed = lockup_info.amount_locked * max_withdrawal_percent;
     if withdraw_amount > max_withdrawal_allowed {
         return Err(StdError::generic_err(format!(
             "Amount exceeds maximum allowed withdrawal limit of {} ",
@@ -408,33 +946,48 @@ pub fn try_withdraw_ust(
         lockup_info.withdrawal_flag = true;
     }
 
-    // LOCKUP INFO :: RETRIEVE --> UPDATE
-    lockup_info.ust_locked -= withdraw_amount;
-
-    // USER INFO :: RETRIEVE --> UPDATE
-    let mut user_info = USER_INFO
-        .may_load(deps.storage, &withdrawer_address)?
-        .unwrap_or_default();
+    // LOCKUP INFO :: UPDATE
+    lockup_info.amount_locked -= withdraw_amount;
+    let withdrawn_asset = Coin {
+        denom: lockup_info.denom.clone(),
+        amount: withdraw_amount,
+    };
 
-    user_info.total_ust_locked -= withdraw_amount;
-    if lockup_info.ust_locked == Uint128::zero() {
+    // USER INFO :: UPDATE
+    sub_locked_amount(&mut user_info.total_locked, &withdrawn_asset)?;
+    if lockup_info.amount_locked == Uint128::zero() {
         remove_lockup_pos_from_user_info(&mut user_info, lockup_id.clone())?;
-        LOCKUP_INFO.remove(deps.storage, lockup_id.as_bytes());
+        LOCKUP_INFO.remove(deps.storage, lockup_key(&lockup_id)?);
+        state.num_tokens -= 1;
     } else {
-        LOCKUP_INFO.save(deps.storage, lockup_id.as_bytes(), &lockup_info)?;
+        LOCKUP_INFO.save(deps.storage, lockup_key(&lockup_id)?, &lockup_info)?;
     }
     USER_INFO.save(deps.storage, &withdrawer_address, &user_info)?;
 
     // STATE :: UPDATE --> SAVE
-    state.total_ust_locked -= withdraw_amount;
-    state.total_deposits_weight -= calculate_weight(withdraw_amount, duration, &config)?;
+    let price_weight = find_asset(&config, &withdrawn_asset.denom)?.price_weight;
+    let withdrawn_weight = calculate_weight(withdraw_amount, price_weight, duration, &config)?;
+    let asset_state = find_asset_state_mut(&mut state, &withdrawn_asset.denom)?;
+    asset_state.total_locked -= withdraw_amount;
+    state.total_deposits_weight -= withdrawn_weight;
+
+    // REFERRER INFO :: Withdrawn weight is no longer credited to the referrer
+    if let Some(referrer_address) = &lockup_info.referrer {
+        let mut referrer_info = REFERRERS.load(deps.storage, referrer_address)?;
+        referrer_info.referred_weight = referrer_info
+            .referred_weight
+            .checked_sub(withdrawn_weight)?;
+        sub_locked_amount(&mut referrer_info.total_referred, &withdrawn_asset)?;
+        state.total_referred_weight = state.total_referred_weight.checked_sub(withdrawn_weight)?;
+        REFERRERS.save(deps.storage, referrer_address, &referrer_info)?;
+    }
     STATE.save(deps.storage, &state)?;
 
-    // COSMOS_MSG ::TRANSFER WITHDRAWN UST
+    // COSMOS_MSG ::TRANSFER WITHDRAWN ASSET
     let withdraw_msg = build_send_native_asset_msg(
         deps.as_ref(),
         withdrawer_address.clone(),
-        UUSD_DENOM,
+        &withdrawn_asset.denom,
         withdraw_amount.into(),
     )?;
 
@@ -444,10 +997,100 @@ pub fn try_withdraw_ust(
             ("action", "lockdrop::ExecuteMsg::withdraw_ust"),
             ("user", &withdrawer_address.to_string()),
             ("duration", duration.to_string().as_str()),
-            ("ust_withdrawn", withdraw_amount.to_string().as_str()),
+            ("denom", withdrawn_asset.denom.as_str()),
+            ("amount_withdrawn", withdraw_amount.to_string().as_str()),
+            ("lockup_id", lockup_id.as_str()),
         ]))
 }
 
+/// @dev Moves a lockup position to a longer duration, re-computing its weight and boost. The position keeps its lockup id (and hence its token id). Can only be called when deposit / withdrawal window is open
+/// @param from_duration : Current duration of the lockup position
+/// @param to_duration : Duration to which the lockup position is extended
+/// @param lockup_id : Id of the lockup position. Required if the user holds several positions with `from_duration`
+pub fn handle_extend_lockup(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from_duration: u64,
+    to_duration: u64,
+    lockup_id: Option<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    let user_address = info.sender;
+    let user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    // CHECK :: Lockdrop deposit / withdrawal window open, weights are final once closed
+    if !is_withdraw_open(env.block.time.seconds(), &config) {
+        return Err(StdError::generic_err(
+            "Lockup positions can only be extended until the withdrawal window closes",
+        ));
+    }
+
+    // CHECK :: Lockdrop is not in emergency mode
+    if EMERGENCY.may_load(deps.storage)?.is_some() {
+        return Err(StdError::generic_err("Lockdrop is in emergency mode"));
+    }
+
+    // CHECK :: Lockup duration can only be extended
+    if to_duration <= from_duration {
+        return Err(StdError::generic_err(
+            "Lockup duration can only be extended",
+        ));
+    }
+
+    // CHECK :: Valid Lockup
+    let lockup_id = find_user_lockup_id(deps.storage, &user_info, from_duration, lockup_id)?
+        .ok_or_else(|| StdError::generic_err("Lockup doesn't exist"))?;
+    let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+
+    // Weight re-computed with the boost of the new duration. Fails if the duration isn't supported
+    let price_weight = find_asset(&config, &lockup_info.denom)?.price_weight;
+    let prev_weight = calculate_weight(
+        lockup_info.amount_locked,
+        price_weight,
+        from_duration,
+        &config,
+    )?;
+    let new_weight = calculate_weight(
+        lockup_info.amount_locked,
+        price_weight,
+        to_duration,
+        &config,
+    )?;
+    let weight_increase = new_weight.checked_sub(prev_weight)?;
+
+    // LOCKUP INFO :: Updated in place under its lockup id
+    lockup_info.duration = to_duration;
+    lockup_info.unlock_timestamp = calculate_unlock_timestamp(&config, to_duration);
+
+    // STATE :: UPDATE --> SAVE
+    state.total_deposits_weight += weight_increase;
+
+    // REFERRER INFO :: Credited with the weight increase
+    if let Some(referrer_address) = &lockup_info.referrer {
+        let mut referrer_info = REFERRERS.load(deps.storage, referrer_address)?;
+        referrer_info.referred_weight += weight_increase;
+        state.total_referred_weight += weight_increase;
+        REFERRERS.save(deps.storage, referrer_address, &referrer_info)?;
+    }
+
+    STATE.save(deps.storage, &state)?;
+    LOCKUP_INFO.save(deps.storage, lockup_key(&lockup_id)?, &lockup_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::ExtendLockup"),
+        ("user", user_address.as_str()),
+        ("from_duration", from_duration.to_string().as_str()),
+        ("to_duration", to_duration.to_string().as_str()),
+        ("lockup_id", lockup_id.as_str()),
+        ("weight", new_weight.to_string().as_str()),
+    ]))
+}
+
 /// @dev Function callable only by Auction contract to enable MARS Claims by users. Called along-with Bootstrap Auction contract's LP Pool provide liquidity tx
 pub fn handle_enable_claims(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
@@ -480,26 +1123,86 @@ pub fn handle_enable_claims(deps: DepsMut, env: Env, info: MessageInfo) -> StdRe
     Ok(Response::new().add_attribute("action", "Lockdrop::ExecuteMsg::EnableClaims"))
 }
 
-/// @dev Admin Function. Deposits all UST into the Red Bank
-pub fn try_deposit_in_red_bank(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+/// @dev Transfers the sender's share of the referral incentives (MARS), pro-rata to his referred weight. Can only be claimed once, after claims are enabled
+pub fn handle_claim_referral_rewards(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
 
-    // CHECK :: Only Owner can call this function
-    if info.sender != config.owner {
-        return Err(StdError::generic_err("Unauthorized"));
+    // CHECK :: Are claims allowed
+    if !state.are_claims_allowed {
+        return Err(StdError::generic_err("Claim not allowed"));
     }
 
-    // CHECK :: Address provider should be set
-    if config.address_provider.is_none() {
-        return Err(StdError::generic_err("Address provider not set"));
+    // CHECK :: Sender needs to have referred deposits, rewards are claimed only once
+    let mut referrer_info = REFERRERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    let mars_to_transfer = calculate_referral_incentives(&config, &state, &referrer_info);
+    if mars_to_transfer.is_zero() {
+        return Err(StdError::generic_err("No referral rewards to claim"));
+    }
+    if referrer_info.mars_claimed {
+        return Err(StdError::generic_err("Referral rewards already claimed"));
+    }
+
+    let mars_token_address = mars_addresses(&config)?.mars_token.clone();
+
+    referrer_info.mars_claimed = true;
+    REFERRERS.save(deps.storage, &info.sender, &referrer_info)?;
+
+    Ok(Response::new()
+        .add_message(build_transfer_cw20_token_msg(
+            info.sender.clone(),
+            mars_token_address.to_string(),
+            mars_to_transfer,
+        )?)
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::ClaimReferralRewards"),
+            ("referrer", info.sender.as_str()),
+            ("mars_claimed", mars_to_transfer.to_string().as_str()),
+        ]))
+}
+
+/// @dev Enables / disables compounding of the sender's xMARS rewards. Applies to the rewards claimed from then on,
+/// xMARS already compounded remains in the contract until the lockup positions are unlocked
+/// @params enabled : Boolean value indicating if the xMARS rewards are to be compounded
+pub fn handle_set_compounding(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> StdResult<Response> {
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // CHECK :: User needs to hold lockup positions
+    if user_info.lockup_positions.is_empty() {
+        return Err(StdError::generic_err("No lockup positions"));
     }
 
-    // CHECK :: maUST address should be set
-    if config.ma_ust_token.is_none() {
-        return Err(StdError::generic_err("maUST not set"));
+    user_info.is_compounding = enabled;
+    USER_INFO.save(deps.storage, &info.sender, &user_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::SetCompounding"),
+        ("user", info.sender.as_str()),
+        ("enabled", enabled.to_string().as_str()),
+    ]))
+}
+
+/// @dev Admin Function. Deposits all the locked assets into the Red Bank, each into its own market
+pub fn try_deposit_in_red_bank(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK :: Only Owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
     }
 
+    // CHECK :: Mars addresses should be resolved
+    let red_bank = mars_addresses(&config)?.red_bank.clone();
+
     // CHECK :: Lockdrop withdrawal window should be closed
     if env.block.time.seconds() < config.init_timestamp
         || is_withdraw_open(env.block.time.seconds(), &config)
@@ -510,44 +1213,82 @@ pub fn try_deposit_in_red_bank(deps: DepsMut, env: Env, info: MessageInfo) -> St
     }
 
     // CHECK :: Revert in-case funds have already been deposited in red-bank
-    if state.final_maust_locked > Uint128::zero() {
+    if state
+        .assets
+        .iter()
+        .any(|asset_state| !asset_state.final_ma_locked.is_zero())
+    {
         return Err(StdError::generic_err("Already deposited"));
     }
 
-    // FETCH CURRENT BALANCES (UST / maUST), PREPARE DEPOSIT MSG
-    let red_bank = query_address(
-        &deps.querier,
-        config.address_provider.unwrap(),
-        MarsContract::RedBank,
-    )?;
-    let ma_ust_balance = cw20_get_balance(
-        &deps.querier,
-        config.ma_ust_token.unwrap(),
-        env.contract.address.clone(),
-    )?;
-
-    // COSMOS_MSG :: DEPOSIT UST IN RED BANK
-    let deposit_msg = build_deposit_into_redbank_msg(
-        deps.as_ref(),
-        red_bank,
-        UUSD_DENOM.to_string(),
-        state.total_ust_locked,
-    )?;
-
-    // COSMOS_MSG :: UPDATE CONTRACT STATE
-    let update_state_msg = CallbackMsg::UpdateStateOnRedBankDeposit {
-        prev_ma_ust_balance: ma_ust_balance,
+    // CHECK :: Lockdrop is not in emergency mode
+    if EMERGENCY.may_load(deps.storage)?.is_some() {
+        return Err(StdError::generic_err("Lockdrop is in emergency mode"));
     }
-    .to_cosmos_msg(&env.contract.address)?;
 
-    Ok(Response::new()
-        .add_messages(vec![deposit_msg, update_state_msg])
-        .add_attributes(vec![
+    let mut sub_msgs = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    // Each asset is deposited into its own Red Bank market. The maTokens minted are read from the deposit's reply
+    for (index, asset_state) in state.assets.iter().enumerate() {
+        if asset_state.total_locked.is_zero() {
+            continue;
+        }
+
+        // QUERY :: maToken of the asset's Red Bank market
+        let ma_token =
+            query_red_bank_market(&deps.querier, &red_bank, &asset_state.denom)?.ma_token_address;
+        find_asset_mut(&mut config, &asset_state.denom)?.ma_token = Some(ma_token.clone());
+
+        // FETCH CURRENT maToken BALANCE :: Fallback in case the reply doesn't report the maTokens minted
+        let ma_balance = cw20_get_balance(
+            &deps.querier,
+            ma_token.clone(),
+            env.contract.address.clone(),
+        )?;
+
+        let reply_id = RED_BANK_DEPOSIT_REPLY_ID_OFFSET + index as u64;
+        PENDING_RED_BANK_DEPOSITS.save(
+            deps.storage,
+            U64Key::new(reply_id),
+            &PendingRedBankDeposit {
+                denom: asset_state.denom.clone(),
+                ma_token: ma_token.clone(),
+                prev_ma_balance: ma_balance,
+            },
+        )?;
+
+        // SUB_MSG :: DEPOSIT ASSET IN RED BANK, CONTRACT STATE UPDATED UPON REPLY
+        sub_msgs.push(SubMsg::reply_on_success(
+            build_deposit_into_redbank_msg(
+                deps.as_ref(),
+                red_bank.clone(),
+                asset_state.denom.clone(),
+                asset_state.total_locked,
+            )?,
+            reply_id,
+        ));
+
+        events.push(
+            Event::new("lockdrop::DepositInRedBank")
+                .add_attribute("denom", &asset_state.denom)
+                .add_attribute("amount_deposited", asset_state.total_locked)
+                .add_attribute("ma_token", ma_token),
+        );
+    }
+
+    // CHECK :: Assets need to be locked
+    if sub_msgs.is_empty() {
+        return Err(StdError::generic_err("No assets to deposit"));
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_submessages(sub_msgs)
+        .add_events(events)
+        .add_attributes(vec![
             ("action", "lockdrop::ExecuteMsg::DepositInRedBank"),
-            (
-                "ust_deposited_in_red_bank",
-                state.total_ust_locked.to_string().as_str(),
-            ),
             ("timestamp", env.block.time.seconds().to_string().as_str()),
         ]))
 }
@@ -578,10 +1319,8 @@ pub fn handle_deposit_mars_to_auction(
         return Err(StdError::generic_err("Auction deposits no longer possible"));
     }
 
-    // CHECK :: Address provider should be set
-    if config.address_provider.is_none() {
-        return Err(StdError::generic_err("Address provider not set"));
-    }
+    // CHECK :: Mars addresses should be resolved
+    let mars_token_address = mars_addresses(&config)?.mars_token.clone();
 
     // CHECK :: Auction contract address should be set
     if config.auction_contract_address.is_none() {
@@ -603,11 +1342,7 @@ pub fn handle_deposit_mars_to_auction(
 
     // If user's total maUST share == 0 :: We update it
     if user_info.total_maust_share.is_zero() {
-        user_info.total_maust_share = calculate_ma_ust_share(
-            user_info.total_ust_locked,
-            state.final_ust_locked,
-            state.final_maust_locked,
-        );
+        user_info.total_maust_share = calculate_user_maust_share(&config, &state, &user_info)?;
         response = response.add_attribute(
             "user_total_maust_share",
             user_info.total_maust_share.to_string(),
@@ -641,12 +1376,6 @@ pub fn handle_deposit_mars_to_auction(
     STATE.save(deps.storage, &state)?;
     USER_INFO.save(deps.storage, &user_address, &user_info)?;
 
-    let mars_token_address = query_address(
-        &deps.querier,
-        config.address_provider.unwrap(),
-        MarsContract::MarsToken,
-    )?;
-
     // COSMOS_MSG ::Delegate MARS to the LP Bootstrapping via Auction contract
     let delegate_msg = build_send_cw20_token_msg(
         config.auction_contract_address.unwrap().to_string(),
@@ -664,134 +1393,378 @@ pub fn handle_deposit_mars_to_auction(
     Ok(response)
 }
 
-/// @dev Function to claim Rewards and optionally unlock a lockup position (either naturally or forcefully). Claims pending incentives (xMARS) internally and accounts for them via the index updates
-/// @params lockup_to_unlock_duration : Duration of the lockup to be unlocked. If 0 then no lockup is to be unlocked
-pub fn handle_nuke_lockdrop(
-    mut deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+/// @dev Owner / guardian Function. Enters emergency mode, disabling deposits. Lockup positions can then be dissolved via `NukeLockdrop` / `EmergencyExit`
+pub fn handle_enter_emergency_mode(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Only owner / guardian can call this function
+    if !is_owner_or_guardian(&info.sender, &config) {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Emergency mode is only entered once
+    if EMERGENCY.may_load(deps.storage)?.is_some() {
+        return Err(StdError::generic_err("Lockdrop is in emergency mode"));
+    }
+
+    let emergency = EmergencyState {
+        activated_at: env.block.time.seconds(),
+        activated_by: info.sender.clone(),
+        start_after: None,
+        users_processed: 0u64,
+        lockups_dissolved: 0u64,
+        is_complete: false,
+    };
+    EMERGENCY.save(deps.storage, &emergency)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::EnterEmergencyMode"),
+        ("activated_by", info.sender.as_str()),
+    ]))
+}
+
+/// @dev Owner / guardian Function. Dissolves the lockup positions of the next batch of users (`nuke_batch_size`), resuming after the last user processed. Only allowed in emergency mode
+pub fn handle_nuke_lockdrop(mut deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
+    let mut emergency = load_emergency_state(deps.storage)?;
 
-    let mut cosmos_msgs = vec![];
-    let mut events: Vec<Event> = vec![];
+    // CHECK :: Only owner / guardian can call this function
+    if !is_owner_or_guardian(&info.sender, &config) {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Users are left to be processed
+    if emergency.is_complete {
+        return Err(StdError::generic_err(
+            "All lockup positions have already been dissolved",
+        ));
+    }
 
-    // find the first 10 user positions
+    // find the next batch of users, after the last user processed
+    let start = emergency
+        .start_after
+        .as_ref()
+        .map(|start_after| Bound::exclusive(start_after.as_bytes()));
     let user_list = USER_INFO
-        .range(deps.storage, None, None, Order::Ascending)
-        .take(10)
-        .map(|item| {
-            let (user_bytes, position) = item?;
-            let user = String::from_utf8(user_bytes)?;
-            Ok((user, position))
-        })
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(config.nuke_batch_size as usize)
+        .map(|user_bytes| Ok(Addr::unchecked(String::from_utf8(user_bytes)?)))
         .collect::<StdResult<Vec<_>>>()?;
 
-    // Loop over user positions
-    for (user_addr, _) in user_list.iter() {
-        // USER INFO :: RETRIEVE --> UPDATE
-        let mut user_info = USER_INFO
-            .may_load(deps.storage, &Addr::unchecked(user_addr.clone()))?
-            .unwrap_or_default();
-
-        // Remove userInfo if all UST has been refunded
-        if user_info.total_ust_locked == Uint128::zero() {
-            USER_INFO.remove(deps.storage, &Addr::unchecked(user_addr));
-            continue;
-        }
-
-        let mut total_ust_unlocked = Uint128::zero();
-        let mut total_maust_withdrawn = Uint128::zero();
-        let mut lockup_positions_unlocked: Vec<String> = vec![];
+    let mut cosmos_msgs = vec![];
+    let mut events: Vec<Event> = vec![];
 
-        // Loop over all Lockup Positions
-        for lockup_id in user_info.lockup_positions.iter() {
-            let mut lockup_info = LOCKUP_INFO
-                .may_load(deps.storage, lockup_id.as_bytes())?
-                .unwrap_or_default();
-
-            let maust_to_withdraw = calculate_ma_ust_share(
-                lockup_info.ust_locked,
-                state.final_ust_locked,
-                state.final_maust_locked,
-            );
-
-            // UPDATE STATE
-            state.total_maust_locked -= maust_to_withdraw;
-
-            total_ust_unlocked = total_ust_unlocked.checked_add(lockup_info.ust_locked)?;
-            total_maust_withdrawn = total_maust_withdrawn.checked_add(maust_to_withdraw)?;
-            lockup_positions_unlocked.push(lockup_id.clone());
-
-            // DISSOLVE LOCKUP POSITION
-            lockup_info.ust_locked = Uint128::zero();
-
-            // Transfer maUST to user
-            if !maust_to_withdraw.is_zero() {
-                cosmos_msgs.push(build_transfer_cw20_token_msg(
-                    Addr::unchecked(user_addr.clone()),
-                    config.ma_ust_token.clone().unwrap().to_string(),
-                    maust_to_withdraw,
-                )?);
-            }
+    // Loop over users, dissolving all their lockup positions
+    for user_address in user_list.iter() {
+        let (refund_msg, user_events) = dissolve_user_lockup_positions(
+            deps.branch(),
+            &config,
+            &mut state,
+            &mut emergency,
+            user_address,
+            env.block.time.seconds(),
+        )?;
+        cosmos_msgs.extend(refund_msg);
+        events.extend(user_events);
+    }
 
-            events.push(
-                Event::new("lockdrop::DissolvePosition")
-                    .add_attribute("user", user_addr)
-                    .add_attribute("lockup_id", lockup_id)
-                    .add_attribute("maust_refunded", maust_to_withdraw),
-            );
+    // UPDATE CURSOR :: Nuke is complete once a batch isn't full
+    if let Some(last_user) = user_list.last() {
+        emergency.start_after = Some(last_user.clone());
+    }
+    emergency.is_complete = user_list.len() < config.nuke_batch_size as usize;
 
-            LOCKUP_INFO.remove(deps.storage, lockup_id.as_bytes());
-        }
+    STATE.save(deps.storage, &state)?;
+    EMERGENCY.save(deps.storage, &emergency)?;
 
-        // UPDATE USER INFO
-        user_info.total_ust_locked = user_info.total_ust_locked.checked_sub(total_ust_unlocked)?;
-        user_info.total_maust_share = user_info
-            .total_maust_share
-            .checked_sub(total_maust_withdrawn)?;
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_events(events)
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::NukeLockdrop"),
+            ("users_processed", user_list.len().to_string().as_str()),
+            ("is_complete", emergency.is_complete.to_string().as_str()),
+        ]))
+}
 
-        let unlocked_ids: HashSet<_> = lockup_positions_unlocked.into_iter().collect();
-        user_info
-            .lockup_positions
-            .retain(|id| !unlocked_ids.contains(id));
+/// @dev Dissolves all the user's lockup positions, refunding the UST (or maUST once deposited in the Red Bank) locked. Only allowed in emergency mode
+pub fn handle_emergency_exit(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut emergency = load_emergency_state(deps.storage)?;
 
-        // Remove userInfo if all UST has been refunded
-        if user_info.total_ust_locked.is_zero() {
-            USER_INFO.remove(deps.storage, &Addr::unchecked(user_addr));
-        } else {
-            USER_INFO.save(deps.storage, &Addr::unchecked(user_addr), &user_info)?;
-        }
+    // CHECK :: User has lockup positions to dissolve
+    let user_info = USER_INFO
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if user_info.lockup_positions.is_empty() {
+        return Err(StdError::generic_err("No lockup positions to dissolve"));
     }
 
+    let (refund_msg, events) = dissolve_user_lockup_positions(
+        deps.branch(),
+        &config,
+        &mut state,
+        &mut emergency,
+        &info.sender,
+        env.block.time.seconds(),
+    )?;
+
     STATE.save(deps.storage, &state)?;
+    EMERGENCY.save(deps.storage, &emergency)?;
 
-    Ok(Response::new().add_messages(cosmos_msgs).add_events(events))
+    Ok(Response::new()
+        .add_messages(refund_msg)
+        .add_events(events)
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::EmergencyExit"),
+            ("user", info.sender.as_str()),
+        ]))
 }
 
 /// @dev Function to claim Rewards and optionally unlock a lockup position (either naturally or forcefully). Claims pending incentives (xMARS) internally and accounts for them via the index updates
 /// @params lockup_to_unlock_duration : Duration of the lockup to be unlocked. If 0 then no lockup is to be unlocked
+/// @params lockup_to_unlock_id : Id of the lockup to be unlocked. Required if the user holds several positions with the duration to unlock
 pub fn handle_claim_rewards_and_unlock_position(
-    mut deps: DepsMut,
+    deps: DepsMut,
     env: Env,
     info: MessageInfo,
     unlock_for_addr: Option<Addr>,
     lockup_to_unlock_duration_option: Option<u64>,
+    lockup_to_unlock_id: Option<String>,
 ) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-    let state = STATE.load(deps.storage)?;
-
-    // CHECK :: Address provider should be set
-    if config.address_provider.is_none() {
-        return Err(StdError::generic_err("Address provider not set"));
-    }
-
     let user_address = if let Some(unlock_for_addr) = unlock_for_addr {
         unlock_for_addr
     } else {
-        info.sender.clone()
+        info.sender
+    };
+
+    let user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    // If a lockup is to be unlocked, then we check that it is a valid lockup position
+    let lockup_to_unlock_id = match lockup_to_unlock_duration_option {
+        Some(lockup_to_unlock_duration) => Some(
+            find_user_lockup_id(
+                deps.storage,
+                &user_info,
+                lockup_to_unlock_duration,
+                lockup_to_unlock_id,
+            )?
+            .ok_or_else(|| StdError::generic_err("Invalid lockup"))?,
+        ),
+        None => None,
     };
+    if let Some(lockup_id) = &lockup_to_unlock_id {
+        let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(lockup_id)?)?;
+
+        // CHECK :: Lockup positions can only be unlocked without penalty once the lockup duration is over
+        if lockup_info.unlock_timestamp > env.block.time.seconds() {
+            let time_remaining = lockup_info.unlock_timestamp - env.block.time.seconds();
+            return Err(StdError::generic_err(format!(
+                "{} seconds to Unlock",
+                time_remaining
+            )));
+        }
+    }
+
+    claim_rewards_and_unlock_position(
+        deps,
+        env,
+        user_address,
+        lockup_to_unlock_id,
+        Uint128::zero(),
+    )
+}
+
+/// @dev Unlocks part of a lockup position once the lockup duration is over, the rest of the position remains locked. Claims the user's rewards beforehand
+/// @params duration : Duration of the lockup position to be partially unlocked
+/// @params amount : Amount of the asset locked to be unlocked. Unlocks the whole position if equal to the amount locked
+/// @params lockup_id : Id of the lockup position. Required if the user holds several positions with the given duration
+pub fn handle_partial_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+    amount: Uint128,
+    lockup_id: Option<String>,
+) -> StdResult<Response> {
+    let user_address = info.sender;
+    let user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    // CHECK :: Valid Lockup
+    let lockup_id = find_user_lockup_id(deps.storage, &user_info, duration, lockup_id)?
+        .ok_or_else(|| StdError::generic_err("Invalid lockup"))?;
+    let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+
+    // CHECK :: Lockup positions can only be unlocked without penalty once the lockup duration is over
+    if lockup_info.unlock_timestamp > env.block.time.seconds() {
+        let time_remaining = lockup_info.unlock_timestamp - env.block.time.seconds();
+        return Err(StdError::generic_err(format!(
+            "{} seconds to Unlock",
+            time_remaining
+        )));
+    }
+
+    // CHECK :: Amount needs to be valid
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Amount must be greater than 0"));
+    }
+    if amount > lockup_info.amount_locked {
+        return Err(StdError::generic_err(format!(
+            "Amount exceeds the amount locked of {}",
+            lockup_info.amount_locked
+        )));
+    }
+
+    // Unlocking the whole position dissolves it
+    if amount == lockup_info.amount_locked {
+        return claim_rewards_and_unlock_position(
+            deps,
+            env.clone(),
+            user_address,
+            Some(lockup_id),
+            Uint128::zero(),
+        );
+    }
+
+    let response = claim_rewards_and_unlock_position(
+        deps,
+        env.clone(),
+        user_address.clone(),
+        None,
+        Uint128::zero(),
+    )?;
+
+    // CALLBACK MSG :: PARTIALLY UNLOCK LOCKUP POSITION, ONCE THE USER'S REWARDS ARE CLAIMED
+    let callback_partial_unlock_msg = CallbackMsg::PartialUnlockPosition {
+        user: user_address,
+        lockup_id: lockup_id.clone(),
+        amount,
+    }
+    .to_cosmos_msg(&env.contract.address)?;
+
+    Ok(response
+        .add_message(callback_partial_unlock_msg)
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::PartialUnlock"),
+            ("lockup_id", lockup_id.as_str()),
+            ("denom", lockup_info.denom.as_str()),
+            ("amount", amount.to_string().as_str()),
+        ]))
+}
+
+/// @dev Facilitates unlocking a lockup position before its unlock timestamp by returning (a share of) its lockdrop reward. Forfeited MARS is transferred to the treasury or redistributed to the remaining lockers. Claims the owner's pending rewards
+/// @params sender : Owner of the lockup position
+/// @params amount : Number of MARS tokens returned
+/// @params lockup_id : Id of the lockup position to be unlocked
+pub fn handle_force_unlock(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    lockup_id: String,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: Mars addresses should be resolved
+    let mars_token_address = mars_addresses(&config)?.mars_token.clone();
+    if info.sender != mars_token_address {
+        return Err(StdError::generic_err("Only mars tokens are received!"));
+    }
+
+    if !state.are_claims_allowed {
+        return Err(StdError::generic_err("Claim not allowed"));
+    }
+
+    // CHECK :: Only the owner can force unlock a lockup position, before its unlock timestamp
+    let lockup_info = LOCKUP_INFO
+        .may_load(deps.storage, lockup_key(&lockup_id)?)?
+        .ok_or_else(|| StdError::generic_err("Lockup doesn't exist"))?;
+    if lockup_info.owner != sender {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+    if lockup_info.unlock_timestamp <= env.block.time.seconds() {
+        return Err(StdError::generic_err(
+            "Lockup position can be unlocked without penalty",
+        ));
+    }
+
+    // Lockdrop reward of the position is updated upon finalization
+    let mut user_info = USER_INFO.load(deps.storage, &sender)?;
+    finalize_user_shares(deps.branch(), &config, &state, &mut user_info)?;
+    USER_INFO.save(deps.storage, &sender, &user_info)?;
+    let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+
+    // CHECK :: MARS returned needs to match the penalty
+    let mars_to_return = lockup_info.lockdrop_reward * config.early_unlock_penalty_rate;
+    if amount != mars_to_return {
+        return Err(StdError::generic_err(format!(
+            "{} MARS needs to be returned to force unlock the position",
+            mars_to_return
+        )));
+    }
+
+    state.total_mars_forfeited += amount;
+    STATE.save(deps.storage, &state)?;
+
+    // Forfeited MARS is transferred to the treasury if set, redistributed to the remaining lockers otherwise
+    let mut messages = vec![];
+    let mut mars_to_redistribute = amount;
+    if let Some(treasury_address) = config.treasury_address {
+        messages.push(build_transfer_cw20_token_msg(
+            treasury_address,
+            mars_token_address.to_string(),
+            amount,
+        )?);
+        mars_to_redistribute = Uint128::zero();
+    }
+
+    let response = claim_rewards_and_unlock_position(
+        deps,
+        env,
+        sender,
+        Some(lockup_id.clone()),
+        mars_to_redistribute,
+    )?;
+
+    Ok(response.add_messages(messages).add_attributes(vec![
+        ("action", "lockdrop::Cw20HookMsg::ForceUnlock"),
+        ("lockup_id", lockup_id.as_str()),
+        ("mars_forfeited", amount.to_string().as_str()),
+    ]))
+}
+
+/// @dev Claims the user's rewards and optionally unlocks a (validated) lockup position
+/// @params user_address : User address whose rewards are to be claimed
+/// @params lockup_to_unlock_id : Id of the lockup to be unlocked, if any
+/// @params mars_to_redistribute : Forfeited MARS to be redistributed to the remaining lockers once the position is unlocked
+fn claim_rewards_and_unlock_position(
+    mut deps: DepsMut,
+    env: Env,
+    user_address: Addr,
+    lockup_to_unlock_id: Option<String>,
+    mars_to_redistribute: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK :: Mars addresses should be resolved
+    let addresses = mars_addresses(&config)?.clone();
 
     let mut user_info = USER_INFO
         .may_load(deps.storage, &user_address)?
@@ -802,35 +1775,27 @@ pub fn handle_claim_rewards_and_unlock_position(
         "Auction::ExecuteMsg::ClaimRewardsAndUnlockPosition",
     );
 
-    // If a lockup is to be unlocked, then we check that it is a valid lockup position
-    if let Some(lockup_to_unlock_duration) = lockup_to_unlock_duration_option {
-        let lockup_id = user_address.to_string() + &lockup_to_unlock_duration.to_string();
-        let lockup_info = LOCKUP_INFO
-            .may_load(deps.storage, lockup_id.as_bytes())?
-            .unwrap_or_default();
+    if let Some(lockup_id) = &lockup_to_unlock_id {
+        let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(lockup_id)?)?;
 
-        if lockup_info.ust_locked == Uint128::zero() {
+        if lockup_info.amount_locked == Uint128::zero() {
             return Err(StdError::generic_err("Invalid lockup"));
         }
 
-        // if lockup_info.unlock_timestamp > env.block.time.seconds() {
-        //     let time_remaining = lockup_info.unlock_timestamp - env.block.time.seconds();
-        //     return Err(StdError::generic_err(format!(
-        //         "{} seconds to Unlock",
-        //         time_remaining
-        //     )));
-        // }
-
         response = response
             .add_attribute("action", "unlock_position")
-            .add_attribute("ust_amount", lockup_info.ust_locked.to_string())
+            .add_attribute("denom", lockup_info.denom.clone())
+            .add_attribute("amount", lockup_info.amount_locked.to_string())
             .add_attribute("duration", lockup_info.duration.to_string())
     }
 
     // CHECKS ::
-    // 2. Valid lockup positions available ?
+    // 2. Valid lockup positions available ? (or rewards settled upon lockup position transfers)
     // 3. Are claims allowed
-    if user_info.total_ust_locked == Uint128::zero() {
+    if user_info.total_locked.is_empty()
+        && user_info.unclaimed_xmars.is_zero()
+        && user_info.unclaimed_forfeited_mars.is_zero()
+    {
         return Err(StdError::generic_err("No lockup to claim rewards for"));
     }
     if !state.are_claims_allowed {
@@ -839,11 +1804,7 @@ pub fn handle_claim_rewards_and_unlock_position(
 
     // If user's total maUST share == 0 :: We update it
     if user_info.total_maust_share.is_zero() {
-        user_info.total_maust_share = calculate_ma_ust_share(
-            user_info.total_ust_locked,
-            state.final_ust_locked,
-            state.final_maust_locked,
-        );
+        user_info.total_maust_share = calculate_user_maust_share(&config, &state, &user_info)?;
         response = response.add_attribute(
             "user_total_maust_share",
             user_info.total_maust_share.to_string(),
@@ -864,16 +1825,8 @@ pub fn handle_claim_rewards_and_unlock_position(
         );
     }
 
-    // QUERY:: XMARS & Incentives Contract addresses
-    let mars_contracts = vec![MarsContract::Incentives, MarsContract::XMarsToken];
-    let mut addresses_query = query_addresses(
-        &deps.querier.clone(),
-        config.address_provider.unwrap(),
-        mars_contracts,
-    )
-    .map_err(|_| StdError::generic_err("mars address provider query failed"))?;
-    let xmars_address = addresses_query.pop().unwrap();
-    let incentives_address = addresses_query.pop().unwrap();
+    let xmars_address = addresses.xmars_token;
+    let incentives_address = addresses.incentives;
 
     // MARS REWARDS :: Query if any rewards to claim and if so, claim them (we receive them as XMARS)
     let mars_unclaimed: Uint128 = query_pending_mars_to_be_claimed(
@@ -881,29 +1834,44 @@ pub fn handle_claim_rewards_and_unlock_position(
         incentives_address.to_string(),
         env.contract.address.to_string(),
     )?;
-    let xmars_balance =
-        cw20_get_balance(&deps.querier, xmars_address, env.contract.address.clone())?;
-
     if !mars_unclaimed.is_zero() {
+        // FETCH CURRENT xMARS BALANCE :: Fallback in case the reply doesn't report the xMARS claimed
+        let xmars_balance = cw20_get_balance(
+            &deps.querier,
+            xmars_address.clone(),
+            env.contract.address.clone(),
+        )?;
+        PENDING_XMARS_CLAIM.save(
+            deps.storage,
+            &PendingXmarsClaim {
+                xmars_token: xmars_address,
+                prev_xmars_balance: xmars_balance,
+            },
+        )?;
+
+        // SUB_MSG :: CLAIM XMARS, XMARS REWARDS INDEX UPDATED UPON REPLY
         let claim_xmars_msg = build_claim_xmars_rewards(incentives_address)?;
         response = response
-            .add_message(claim_xmars_msg)
+            .add_submessage(SubMsg::reply_on_success(
+                claim_xmars_msg,
+                CLAIM_XMARS_REPLY_ID,
+            ))
             .add_attribute("xmars_claimed", "true");
     }
 
     // CALLBACK ::  UPDATE STATE
     let callback_msg = CallbackMsg::UpdateStateOnClaim {
         user: user_address.clone(),
-        prev_xmars_balance: xmars_balance,
     }
     .to_cosmos_msg(&env.contract.address)?;
     response = response.add_message(callback_msg);
 
     // CALLBACK MSG :: DISSOLVE LOCKUP POSITION
-    if let Some(lockup_to_unlock_duration) = lockup_to_unlock_duration_option {
+    if let Some(lockup_id) = lockup_to_unlock_id {
         let callback_dissolve_position_msg = CallbackMsg::DissolvePosition {
             user: user_address.clone(),
-            duration: lockup_to_unlock_duration,
+            lockup_id,
+            mars_to_redistribute,
         }
         .to_cosmos_msg(&env.contract.address)?;
         response = response.add_message(callback_dissolve_position_msg);
@@ -913,83 +1881,296 @@ pub fn handle_claim_rewards_and_unlock_position(
     Ok(response)
 }
 
-//----------------------------------------------------------------------------------------
-// Callback Functions
-//----------------------------------------------------------------------------------------
+/// @dev cw721 :: Transfers a lockup position along-with its MARS incentives and pending xMARS rewards to the recipient
+/// @params recipient : Address to which the lockup position is to be transferred
+/// @params token_id : Id of the lockup position to be transferred
+pub fn handle_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> StdResult<Response> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let messages = transfer_lockup_position(deps, &env, &info.sender, &recipient, &token_id)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::TransferNft"),
+        ("sender", info.sender.as_str()),
+        ("recipient", recipient.as_str()),
+        ("token_id", token_id.as_str()),
+    ]))
+}
 
-/// @dev Callback function. Updates state after UST is deposited in the Red Bank
-/// @params prev_ma_ust_balance : Previous maUST Token balance
-pub fn update_state_on_red_bank_deposit(
+/// @dev cw721 :: Transfers a lockup position to a contract and triggers an action on the receiving contract
+/// @params contract : Contract to which the lockup position is to be transferred
+/// @params token_id : Id of the lockup position to be transferred
+/// @params msg : Message to be executed by the receiving contract upon `ReceiveNft`
+pub fn handle_send_nft(
     deps: DepsMut,
     env: Env,
-    prev_ma_ust_balance: Uint128,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
 ) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?;
+    let contract = deps.api.addr_validate(&contract)?;
+    let messages = transfer_lockup_position(deps, &env, &info.sender, &contract, &token_id)?;
+
+    // COSMOS_MSG :: NOTIFY THE RECEIVING CONTRACT
+    let receive_msg = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    }
+    .into_cosmos_msg(contract.to_string())?;
 
-    let cur_ma_ust_balance = cw20_get_balance(
-        &deps.querier,
-        config.ma_ust_token.unwrap(),
-        env.contract.address,
-    )?;
-    let m_ust_minted = cur_ma_ust_balance - prev_ma_ust_balance;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_message(receive_msg)
+        .add_attributes(vec![
+            ("action", "lockdrop::ExecuteMsg::SendNft"),
+            ("sender", info.sender.as_str()),
+            ("recipient", contract.as_str()),
+            ("token_id", token_id.as_str()),
+        ]))
+}
 
-    // STATE :: UPDATE --> SAVE
-    state.final_ust_locked = state.total_ust_locked;
-    state.final_maust_locked = m_ust_minted;
+/// @dev cw721 :: Allows the spender to transfer the lockup position. Can only be called by the owner of the position or one of its operators
+/// @params spender : Address allowed to transfer the lockup position
+/// @params token_id : Id of the lockup position
+/// @params expires : Expiration of the approval. Never expires if not provided
+pub fn handle_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> StdResult<Response> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let mut lockup_info = load_lockup_for_approval(deps.as_ref(), &env, &info.sender, &token_id)?;
 
-    state.total_ust_locked = Uint128::zero();
-    state.total_maust_locked = m_ust_minted;
+    // CHECK :: Approval shouldn't be expired
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(StdError::generic_err(
+            "Cannot set approval that is already expired",
+        ));
+    }
 
-    STATE.save(deps.storage, &state)?;
+    lockup_info
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    lockup_info.approvals.push(Approval {
+        spender: spender.clone(),
+        expires,
+    });
+    LOCKUP_INFO.save(deps.storage, lockup_key(&token_id)?, &lockup_info)?;
 
     Ok(Response::new().add_attributes(vec![
-        ("action", "lockdrop::CallbackMsg::RedBankDeposit"),
-        ("maUST_minted", m_ust_minted.to_string().as_str()),
+        ("action", "lockdrop::ExecuteMsg::Approve"),
+        ("sender", info.sender.as_str()),
+        ("spender", spender.as_str()),
+        ("token_id", token_id.as_str()),
     ]))
 }
 
-/// @dev Callback function. Updated indexes (if xMars is claimed), calculates user's Mars rewards (if not already done), and transfers rewards (MARS and xMars) to the user
-/// @params user : User address
-/// @params prev_xmars_balance : Previous xMars balance. Used to calculate how much xMars was claimed from the incentives contract
-pub fn update_state_on_claim(
+/// @dev cw721 :: Removes the spender's approval to transfer the lockup position. Can only be called by the owner of the position or one of its operators
+/// @params spender : Address whose approval is to be removed
+/// @params token_id : Id of the lockup position
+pub fn handle_revoke(
     deps: DepsMut,
     env: Env,
-    user: Addr,
-    prev_xmars_balance: Uint128,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
 ) -> StdResult<Response> {
-    let config = CONFIG.load(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?; // Index is updated
-    let mut user_info = USER_INFO.may_load(deps.storage, &user)?.unwrap_or_default();
-
-    // QUERY:: xMars and Mars Contract addresses
-    let mars_contracts = vec![MarsContract::MarsToken, MarsContract::XMarsToken];
-    let mut addresses_query = query_addresses(
-        &deps.querier,
-        config.address_provider.unwrap(),
-        mars_contracts,
-    )
-    .map_err(|_| StdError::generic_err("mars address provider query failed"))?;
+    let spender = deps.api.addr_validate(&spender)?;
+    let mut lockup_info = load_lockup_for_approval(deps.as_ref(), &env, &info.sender, &token_id)?;
 
-    let xmars_address = addresses_query.pop().unwrap();
-    let mars_address = addresses_query.pop().unwrap();
+    lockup_info
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    LOCKUP_INFO.save(deps.storage, lockup_key(&token_id)?, &lockup_info)?;
 
-    let mut response = Response::new().add_attribute("user_address", user.to_string());
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::Revoke"),
+        ("sender", info.sender.as_str()),
+        ("spender", spender.as_str()),
+        ("token_id", token_id.as_str()),
+    ]))
+}
 
-    // Calculate XMARS Claimed as rewards
-    let cur_xmars_balance =
-        cw20_get_balance(&deps.querier, xmars_address.clone(), env.contract.address)?;
-    let xmas_accrued = cur_xmars_balance - prev_xmars_balance;
-    response = response.add_attribute("total_xmars_claimed", xmas_accrued.to_string());
+/// @dev cw721 :: Allows the operator to transfer any of the sender's lockup positions
+/// @params operator : Address allowed to transfer the sender's lockup positions
+/// @params expires : Expiration of the approval. Never expires if not provided
+pub fn handle_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> StdResult<Response> {
+    let operator = deps.api.addr_validate(&operator)?;
 
-    // UPDATE :: GLOBAL & USER INDEX (XMARS rewards tracker)
-    if xmas_accrued > Uint128::zero() {
-        update_xmars_rewards_index(&mut state, xmas_accrued);
+    // CHECK :: Approval shouldn't be expired
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(StdError::generic_err(
+            "Cannot set approval that is already expired",
+        ));
+    }
+
+    OPERATORS.save(deps.storage, (&info.sender, &operator), &expires)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::ApproveAll"),
+        ("sender", info.sender.as_str()),
+        ("operator", operator.as_str()),
+    ]))
+}
+
+/// @dev cw721 :: Removes the operator's approval to transfer the sender's lockup positions
+/// @params operator : Address whose approval is to be removed
+pub fn handle_revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> StdResult<Response> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::ExecuteMsg::RevokeAll"),
+        ("sender", info.sender.as_str()),
+        ("operator", operator.as_str()),
+    ]))
+}
+
+//----------------------------------------------------------------------------------------
+// Reply Functions
+//----------------------------------------------------------------------------------------
+
+/// @dev Reply function. Updates state after an asset is deposited in the Red Bank, with the maTokens minted as
+/// reported by the deposit's events (or the maToken balance difference if not reported)
+/// @params reply_id : Reply id of the deposit
+/// @params events : Events of the deposit's execution
+pub fn update_state_on_red_bank_deposit(
+    deps: DepsMut,
+    env: Env,
+    reply_id: u64,
+    events: Vec<Event>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    let pending = PENDING_RED_BANK_DEPOSITS
+        .may_load(deps.storage, U64Key::new(reply_id))?
+        .ok_or_else(|| StdError::generic_err(format!("Unknown reply id: {}", reply_id)))?;
+    PENDING_RED_BANK_DEPOSITS.remove(deps.storage, U64Key::new(reply_id));
+
+    let denom = pending.denom;
+    let asset = find_asset(&config, &denom)?;
+
+    let ma_minted = match cw20_get_amount_received_from_events(
+        &events,
+        &pending.ma_token,
+        &env.contract.address,
+    ) {
+        Some(ma_minted) => ma_minted,
+        None => cw20_get_balance(&deps.querier, pending.ma_token, env.contract.address)?
+            .checked_sub(pending.prev_ma_balance)?,
+    };
+
+    // STATE :: UPDATE --> SAVE
+    let asset_state = find_asset_state_mut(&mut state, &denom)?;
+    asset_state.final_locked = asset_state.total_locked;
+    asset_state.final_ma_locked = ma_minted;
+
+    asset_state.total_locked = Uint128::zero();
+    asset_state.total_ma_locked = ma_minted;
+
+    // Rewards are shared pro-rata to the maTokens held, weighted by the assets' price weights
+    state.total_maust_locked += ma_minted * asset.price_weight;
+
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::Reply::RedBankDeposit"),
+        ("denom", denom.as_str()),
+        ("ma_minted", ma_minted.to_string().as_str()),
+    ]))
+}
+
+/// @dev Reply function. Updates the xMARS rewards index with the xMARS claimed from the incentives contract, as
+/// reported by the claim's events (or the xMARS balance difference if not reported)
+/// @params events : Events of the claim's execution
+pub fn update_state_on_xmars_claim(
+    deps: DepsMut,
+    env: Env,
+    events: Vec<Event>,
+) -> StdResult<Response> {
+    let mut state = STATE.load(deps.storage)?;
+
+    let pending = PENDING_XMARS_CLAIM
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("No pending xMARS claim"))?;
+    PENDING_XMARS_CLAIM.remove(deps.storage);
+
+    let xmars_accrued = match cw20_get_amount_received_from_events(
+        &events,
+        &pending.xmars_token,
+        &env.contract.address,
+    ) {
+        Some(xmars_accrued) => xmars_accrued,
+        None => cw20_get_balance(&deps.querier, pending.xmars_token, env.contract.address)?
+            .checked_sub(pending.prev_xmars_balance)?,
+    };
+
+    // UPDATE :: GLOBAL INDEX (XMARS rewards tracker)
+    if xmars_accrued > Uint128::zero() {
+        update_xmars_rewards_index(&mut state, xmars_accrued);
     }
 
-    // COSMOS MSG :: SEND X-MARS (DEPOSIT INCENTIVES) IF > 0
-    let pending_xmars_rewards = compute_user_accrued_reward(&state, &mut user_info);
-    if pending_xmars_rewards > Uint128::zero() {
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lockdrop::Reply::XmarsClaim"),
+        ("total_xmars_claimed", xmars_accrued.to_string().as_str()),
+    ]))
+}
+
+//----------------------------------------------------------------------------------------
+// Callback Functions
+//----------------------------------------------------------------------------------------
+
+/// @dev Callback function. Calculates user's Mars rewards (if not already done), and transfers rewards (MARS and xMars) to the user.
+/// The xMARS rewards index is updated beforehand, upon the reply of the contract's xMars claim
+/// @params user : User address
+pub fn update_state_on_claim(deps: DepsMut, _env: Env, user: Addr) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?; // Index is updated
+    let mut user_info = USER_INFO.may_load(deps.storage, &user)?.unwrap_or_default();
+
+    let addresses = mars_addresses(&config)?;
+    let xmars_address = addresses.xmars_token.clone();
+    let mars_address = addresses.mars_token.clone();
+
+    let mut response = Response::new().add_attribute("user_address", user.to_string());
+
+    // COSMOS MSG :: SEND X-MARS (DEPOSIT INCENTIVES) IF > 0, COMPOUNDED UNTIL THE LOCKUP POSITIONS ARE UNLOCKED IF OPTED IN
+    let pending_xmars_rewards =
+        compute_user_accrued_reward(&state, &mut user_info) + user_info.unclaimed_xmars;
+    user_info.unclaimed_xmars = Uint128::zero();
+    if pending_xmars_rewards > Uint128::zero()
+        && user_info.is_compounding
+        && !user_info.lockup_positions.is_empty()
+    {
+        user_info.compounding_shares += compound_xmars(&mut state, pending_xmars_rewards);
+        response =
+            response.add_attribute("user_xmars_compounded", pending_xmars_rewards.to_string());
+    } else if pending_xmars_rewards > Uint128::zero() {
         user_info.total_xmars_claimed += pending_xmars_rewards;
 
         let transfer_xmars_msg = build_transfer_cw20_token_msg(
@@ -1018,6 +2199,22 @@ pub fn update_state_on_claim(
             .add_attribute("user_mars_claimed", mars_to_transfer.to_string());
     }
 
+    // COSMOS MSG :: SEND FORFEITED MARS (REDISTRIBUTED UPON FORCED UNLOCKS) IF > 0
+    let pending_forfeited_mars =
+        compute_user_forfeited_mars(&state, &mut user_info) + user_info.unclaimed_forfeited_mars;
+    user_info.unclaimed_forfeited_mars = Uint128::zero();
+    if pending_forfeited_mars > Uint128::zero() {
+        let transfer_mars_msg = build_transfer_cw20_token_msg(
+            user.clone(),
+            mars_address.to_string(),
+            pending_forfeited_mars,
+        )?;
+        response = response.add_message(transfer_mars_msg).add_attribute(
+            "user_forfeited_mars_claimed",
+            pending_forfeited_mars.to_string(),
+        );
+    }
+
     // SAVE UPDATED STATES
     STATE.save(deps.storage, &state)?;
     USER_INFO.save(deps.storage, &user, &user_info)?;
@@ -1028,57 +2225,187 @@ pub fn update_state_on_claim(
 // CALLBACK :: CALLED BY try_unlock_position FUNCTION --> DELETES LOCKUP POSITION
 /// @dev  Callback function. Unlocks a lockup position. Either naturally after duration expiration or forcefully by returning MARS (lockdrop incentives)
 /// @params user : User address whose position is to be unlocked
-/// @params duration :Lockup duration of the position to be unlocked
+/// @params lockup_id : Id of the lockup position to be unlocked
+/// @params mars_to_redistribute : MARS forfeited upon a forced unlock, redistributed to the remaining lockers
 pub fn try_dissolve_position(
     deps: DepsMut,
     _env: Env,
     user: Addr,
-    duration: u64,
+    lockup_id: String,
+    mars_to_redistribute: Uint128,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
     let mut user_info = USER_INFO.may_load(deps.storage, &user)?.unwrap_or_default();
+    let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+    let asset = find_asset(&config, &lockup_info.denom)?;
 
-    let lockup_id = user.to_string() + &duration.to_string();
-    let mut lockup_info = LOCKUP_INFO
-        .may_load(deps.storage, lockup_id.as_bytes())?
-        .unwrap_or_default();
+    let (ma_to_withdraw, maust_share) = calculate_lockup_ma_share(&config, &state, &lockup_info)?;
 
-    let maust_to_withdraw = calculate_ma_ust_share(
-        lockup_info.ust_locked,
-        state.final_ust_locked,
-        state.final_maust_locked,
-    );
+    // COMPOUNDED XMARS :: Paid out pro-rata to the position's maUST share
+    let compounding_shares = position_compounding_shares(&user_info, maust_share);
+    let xmars_compounded = redeem_compounding_shares(&mut state, compounding_shares)?;
+    user_info.compounding_shares -= compounding_shares;
 
     // UPDATE STATE
-    state.total_maust_locked -= maust_to_withdraw;
+    let asset_state = find_asset_state_mut(&mut state, &lockup_info.denom)?;
+    asset_state.total_ma_locked -= ma_to_withdraw;
+    state.total_maust_locked -= maust_share;
+
+    // FORFEITED MARS :: Redistributed to the remaining lockers
+    if !mars_to_redistribute.is_zero() {
+        if state.total_maust_locked.is_zero() {
+            return Err(StdError::generic_err(
+                "No lockup positions to redistribute the forfeited MARS to",
+            ));
+        }
+        update_forfeited_mars_index(&mut state, mars_to_redistribute);
+    }
 
-    // UPDATE USER INFO
-    // user_info.total_ust_locked = user_info.total_ust_locked - lockup_info.ust_locked;
-    user_info.total_maust_share -= maust_to_withdraw;
+    // UPDATE USER INFO :: Amount locked is reduced as well, so that the maUST share of unlocked positions isn't recomputed
+    sub_locked_amount(
+        &mut user_info.total_locked,
+        &Coin {
+            denom: lockup_info.denom.clone(),
+            amount: lockup_info.amount_locked,
+        },
+    )?;
+    user_info.total_maust_share -= maust_share;
 
     // DISSOLVE LOCKUP POSITION
-    lockup_info.ust_locked = Uint128::zero();
+    lockup_info.amount_locked = Uint128::zero();
     remove_lockup_pos_from_user_info(&mut user_info, lockup_id.clone())?;
 
     let mut cosmos_msgs = vec![];
 
-    let maust_transfer_msg = build_transfer_cw20_token_msg(
+    let ma_transfer_msg = build_transfer_cw20_token_msg(
         user.clone(),
-        config.ma_ust_token.unwrap().to_string(),
-        maust_to_withdraw,
+        asset
+            .ma_token
+            .clone()
+            .ok_or_else(|| StdError::generic_err(format!("maToken not set for {}", asset.denom)))?
+            .to_string(),
+        ma_to_withdraw,
     )?;
-    cosmos_msgs.push(maust_transfer_msg);
+    cosmos_msgs.push(ma_transfer_msg);
+
+    if !xmars_compounded.is_zero() {
+        let xmars_address = mars_addresses(&config)?.xmars_token.clone();
+        user_info.total_xmars_claimed += xmars_compounded;
+        cosmos_msgs.push(build_transfer_cw20_token_msg(
+            user.clone(),
+            xmars_address.to_string(),
+            xmars_compounded,
+        )?);
+    }
 
+    state.num_tokens -= 1;
     STATE.save(deps.storage, &state)?;
     USER_INFO.save(deps.storage, &user, &user_info)?;
-    LOCKUP_INFO.remove(deps.storage, lockup_id.as_bytes());
+    LOCKUP_INFO.remove(deps.storage, lockup_key(&lockup_id)?);
 
     Ok(Response::new()
         .add_messages(cosmos_msgs)
         .add_attributes(vec![
             ("action", "lockdrop::Callback::DissolvePosition"),
-            ("ma_ust_transferred", maust_to_withdraw.to_string().as_str()),
+            ("denom", lockup_info.denom.as_str()),
+            ("ma_transferred", ma_to_withdraw.to_string().as_str()),
+            ("xmars_compounded", xmars_compounded.to_string().as_str()),
+            (
+                "mars_redistributed",
+                mars_to_redistribute.to_string().as_str(),
+            ),
+        ]))
+}
+
+/// @dev Callback function. Unlocks part of a lockup position, after the user's rewards have been claimed. The rest of the position remains locked
+/// @params user : User address whose position is to be partially unlocked
+/// @params lockup_id : Id of the lockup position
+/// @params amount : Amount of the asset locked to be unlocked
+pub fn try_partial_unlock_position(
+    deps: DepsMut,
+    _env: Env,
+    user: Addr,
+    lockup_id: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USER_INFO.may_load(deps.storage, &user)?.unwrap_or_default();
+    let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+    let asset = find_asset(&config, &lockup_info.denom)?;
+
+    let unlocked_asset = Coin {
+        denom: lockup_info.denom.clone(),
+        amount,
+    };
+    let (ma_to_withdraw, maust_share) = calculate_lockup_ma_share(
+        &config,
+        &state,
+        &LockupInfo {
+            amount_locked: amount,
+            ..lockup_info.clone()
+        },
+    )?;
+
+    // COMPOUNDED XMARS :: Paid out pro-rata to the maUST share unlocked
+    let compounding_shares = if user_info.total_maust_share.is_zero() {
+        Uint128::zero()
+    } else {
+        user_info
+            .compounding_shares
+            .multiply_ratio(maust_share, user_info.total_maust_share)
+    };
+    let xmars_compounded = redeem_compounding_shares(&mut state, compounding_shares)?;
+    user_info.compounding_shares -= compounding_shares;
+
+    // UPDATE STATE
+    let asset_state = find_asset_state_mut(&mut state, &lockup_info.denom)?;
+    asset_state.total_ma_locked -= ma_to_withdraw;
+    state.total_maust_locked -= maust_share;
+
+    // UPDATE USER INFO / LOCKUP POSITION :: The rest of the position keeps accruing xMARS rewards
+    sub_locked_amount(&mut user_info.total_locked, &unlocked_asset)?;
+    user_info.total_maust_share -= maust_share;
+    lockup_info.amount_locked -= amount;
+
+    let mut cosmos_msgs = vec![build_transfer_cw20_token_msg(
+        user.clone(),
+        asset
+            .ma_token
+            .clone()
+            .ok_or_else(|| StdError::generic_err(format!("maToken not set for {}", asset.denom)))?
+            .to_string(),
+        ma_to_withdraw,
+    )?];
+
+    if !xmars_compounded.is_zero() {
+        let xmars_address = mars_addresses(&config)?.xmars_token.clone();
+        user_info.total_xmars_claimed += xmars_compounded;
+        cosmos_msgs.push(build_transfer_cw20_token_msg(
+            user.clone(),
+            xmars_address.to_string(),
+            xmars_compounded,
+        )?);
+    }
+
+    STATE.save(deps.storage, &state)?;
+    USER_INFO.save(deps.storage, &user, &user_info)?;
+    LOCKUP_INFO.save(deps.storage, lockup_key(&lockup_id)?, &lockup_info)?;
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            ("action", "lockdrop::Callback::PartialUnlockPosition"),
+            ("lockup_id", lockup_id.as_str()),
+            ("denom", lockup_info.denom.as_str()),
+            ("amount_unlocked", amount.to_string().as_str()),
+            (
+                "amount_locked",
+                lockup_info.amount_locked.to_string().as_str(),
+            ),
+            ("ma_transferred", ma_to_withdraw.to_string().as_str()),
+            ("xmars_compounded", xmars_compounded.to_string().as_str()),
         ]))
 }
 
@@ -1093,14 +2420,22 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse {
         owner: config.owner.to_string(),
         address_provider: config.address_provider,
-        ma_ust_token: config.ma_ust_token,
+        mars_addresses: config.mars_addresses,
+        assets: config.assets,
         auction_contract_address: config.auction_contract_address,
         init_timestamp: config.init_timestamp,
         deposit_window: config.deposit_window,
         withdrawal_window: config.withdrawal_window,
         lockup_durations: config.lockup_durations,
+        boost_curve: config.boost_curve,
         seconds_per_duration_unit: config.seconds_per_duration_unit,
         lockdrop_incentives: config.lockdrop_incentives,
+        early_unlock_penalty_rate: config.early_unlock_penalty_rate,
+        treasury_address: config.treasury_address,
+        guardian: config.guardian,
+        nuke_batch_size: config.nuke_batch_size,
+        referral_incentives_rate: config.referral_incentives_rate,
+        withdrawal_schedule: config.withdrawal_schedule,
     })
 }
 
@@ -1108,14 +2443,17 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
     let state: State = STATE.load(deps.storage)?;
     Ok(StateResponse {
-        final_ust_locked: state.final_ust_locked,
-        final_maust_locked: state.final_maust_locked,
-        total_ust_locked: state.total_ust_locked,
+        assets: state.assets,
         total_maust_locked: state.total_maust_locked,
         total_mars_delegated: state.total_mars_delegated,
         are_claims_allowed: state.are_claims_allowed,
         total_deposits_weight: state.total_deposits_weight,
         xmars_rewards_index: state.xmars_rewards_index,
+        total_mars_forfeited: state.total_mars_forfeited,
+        forfeited_mars_index: state.forfeited_mars_index,
+        total_compounded_xmars: state.total_compounded_xmars,
+        total_compounding_shares: state.total_compounding_shares,
+        total_referred_weight: state.total_referred_weight,
     })
 }
 
@@ -1130,26 +2468,17 @@ pub fn query_user_info(deps: Deps, env: Env, user_address_: String) -> StdResult
         .unwrap_or_default();
 
     // Calculate user's maUST share if not already done
-    if user_info.total_maust_share == Uint128::zero() && state.final_maust_locked != Uint128::zero()
-    {
-        user_info.total_maust_share = calculate_ma_ust_share(
-            user_info.total_ust_locked,
-            state.final_ust_locked,
-            state.final_maust_locked,
-        );
+    if user_info.total_maust_share == Uint128::zero() {
+        user_info.total_maust_share = calculate_user_maust_share(&config, &state, &user_info)?;
     }
 
     // Calculate user's lockdrop incentive share if not finalized
     if user_info.total_mars_incentives == Uint128::zero() {
         for lockup_id in user_info.lockup_positions.iter() {
-            let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_id.as_bytes())?;
+            let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(lockup_id)?)?;
 
-            let position_rewards = calculate_mars_incentives_for_lockup(
-                lockup_info.ust_locked,
-                lockup_info.duration,
-                &config,
-                state.total_deposits_weight,
-            )?;
+            let position_rewards =
+                calculate_mars_incentives_for_lockup(&lockup_info, &config, &state)?;
             user_info.total_mars_incentives += position_rewards;
         }
     }
@@ -1157,15 +2486,8 @@ pub fn query_user_info(deps: Deps, env: Env, user_address_: String) -> StdResult
     let mut pending_xmars_to_claim = Uint128::zero();
 
     // QUERY:: Contract addresses
-    if config.address_provider.is_some() {
-        let mars_contracts = vec![MarsContract::Incentives];
-        let mut addresses_query = query_addresses(
-            &deps.querier,
-            config.address_provider.unwrap(),
-            mars_contracts,
-        )
-        .map_err(|_| StdError::generic_err("mars address provider query failed"))?;
-        let incentives_address = addresses_query.pop().unwrap();
+    if let Some(addresses) = &config.mars_addresses {
+        let incentives_address = &addresses.incentives;
 
         // QUERY :: XMARS REWARDS TO BE CLAIMED  ?
         let xmas_accrued: Uint128 = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
@@ -1178,9 +2500,13 @@ pub fn query_user_info(deps: Deps, env: Env, user_address_: String) -> StdResult
         update_xmars_rewards_index(&mut state, xmas_accrued);
         pending_xmars_to_claim = compute_user_accrued_reward(&state, &mut user_info);
     }
+    pending_xmars_to_claim += user_info.unclaimed_xmars;
+
+    let pending_forfeited_mars_to_claim =
+        compute_user_forfeited_mars(&state, &mut user_info) + user_info.unclaimed_forfeited_mars;
 
     Ok(UserInfoResponse {
-        total_ust_locked: user_info.total_ust_locked,
+        total_locked: user_info.total_locked,
         total_maust_share: user_info.total_maust_share,
         lockup_position_ids: user_info.lockup_positions,
         total_mars_incentives: user_info.total_mars_incentives,
@@ -1189,42 +2515,50 @@ pub fn query_user_info(deps: Deps, env: Env, user_address_: String) -> StdResult
         reward_index: user_info.reward_index,
         total_xmars_claimed: user_info.total_xmars_claimed,
         pending_xmars_to_claim,
+        pending_forfeited_mars_to_claim,
+        is_compounding: user_info.is_compounding,
+        compounding_shares: user_info.compounding_shares,
+        compounded_xmars: compounding_shares_to_xmars(&state, user_info.compounding_shares),
     })
 }
 
-/// @dev Returns summarized details regarding the user
+/// @dev Returns summarized details regarding the user's lockup position with the given duration. Fails if the user holds several of them
 pub fn query_lockup_info(deps: Deps, user: String, duration: u64) -> StdResult<LockupInfoResponse> {
-    let lockup_id = user + &duration.to_string();
-    query_lockup_info_with_id(deps, lockup_id)
+    let user_address = deps.api.addr_validate(&user)?;
+    let user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    match find_user_lockup_id(deps.storage, &user_info, duration, None)? {
+        Some(lockup_id) => query_lockup_info_with_id(deps, lockup_id),
+        None => Ok(LockupInfoResponse { lockup_info: None }),
+    }
 }
 
 /// @dev Returns summarized details regarding the user
 pub fn query_lockup_info_with_id(deps: Deps, lockup_id: String) -> StdResult<LockupInfoResponse> {
-    let lockup_info_query = LOCKUP_INFO.may_load(deps.storage, lockup_id.as_bytes())?;
+    let lockup_info_query = LOCKUP_INFO.may_load(deps.storage, lockup_key(&lockup_id)?)?;
 
     if let Some(lockup_info) = lockup_info_query {
+        let config = CONFIG.load(deps.storage)?;
         let state: State = STATE.load(deps.storage)?;
+        let (ma_token_balance, _) = calculate_lockup_ma_share(&config, &state, &lockup_info)?;
+
         let mut lockup_info_query_data = LockupInfoQueryData {
+            owner: lockup_info.owner.clone(),
             duration: lockup_info.duration,
-            ust_locked: lockup_info.ust_locked,
-            maust_balance: calculate_ma_ust_share(
-                lockup_info.ust_locked,
-                state.final_ust_locked,
-                state.final_maust_locked,
-            ),
+            denom: lockup_info.denom.clone(),
+            amount_locked: lockup_info.amount_locked,
+            ma_token_balance,
             lockdrop_reward: lockup_info.lockdrop_reward,
             unlock_timestamp: lockup_info.unlock_timestamp,
             withdrawal_flag: lockup_info.withdrawal_flag,
+            referrer: lockup_info.referrer.clone(),
         };
 
         if lockup_info_query_data.lockdrop_reward == Uint128::zero() {
-            let config = CONFIG.load(deps.storage)?;
-            lockup_info_query_data.lockdrop_reward = calculate_mars_incentives_for_lockup(
-                lockup_info_query_data.ust_locked,
-                lockup_info_query_data.duration,
-                &config,
-                state.total_deposits_weight,
-            )?;
+            lockup_info_query_data.lockdrop_reward =
+                calculate_mars_incentives_for_lockup(&lockup_info, &config, &state)?;
         }
 
         Ok(LockupInfoResponse {
@@ -1235,6 +2569,50 @@ pub fn query_lockup_info_with_id(deps: Deps, lockup_id: String) -> StdResult<Loc
     }
 }
 
+/// @dev Returns the deposits referred by the address along-with its share of the referral incentives
+/// @params address : Address of the referrer
+pub fn query_referrer(deps: Deps, address: String) -> StdResult<ReferrerResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let referrer_address = deps.api.addr_validate(&address)?;
+    let referrer_info = REFERRERS
+        .may_load(deps.storage, &referrer_address)?
+        .unwrap_or_default();
+
+    Ok(ReferrerResponse {
+        address,
+        referred_weight: referrer_info.referred_weight,
+        deposits_referred: referrer_info.deposits_referred,
+        mars_incentives: calculate_referral_incentives(&config, &state, &referrer_info),
+        total_referred: referrer_info.total_referred,
+        is_claimed: referrer_info.mars_claimed,
+    })
+}
+
+/// @dev Returns the emergency mode status along-with the `NukeLockdrop` progress
+pub fn query_emergency_state(deps: Deps) -> StdResult<EmergencyStateResponse> {
+    let emergency = EMERGENCY.may_load(deps.storage)?;
+
+    Ok(match emergency {
+        Some(emergency) => EmergencyStateResponse {
+            is_emergency_mode: true,
+            activated_at: Some(emergency.activated_at),
+            start_after: emergency.start_after.map(|user| user.to_string()),
+            users_processed: emergency.users_processed,
+            lockups_dissolved: emergency.lockups_dissolved,
+            is_complete: emergency.is_complete,
+        },
+        None => EmergencyStateResponse {
+            is_emergency_mode: false,
+            activated_at: None,
+            start_after: None,
+            users_processed: 0u64,
+            lockups_dissolved: 0u64,
+            is_complete: false,
+        },
+    })
+}
+
 /// @dev Returns max withdrawable % for a position
 pub fn query_max_withdrawable_percent(
     deps: Deps,
@@ -1257,6 +2635,298 @@ pub fn query_max_withdrawable_percent(
     Ok(max_withdrawable_percent)
 }
 
+/// @dev Returns the boost and weight of the amount locked for the duration, as per the configured boost curve and the asset's price weight
+/// @params amount : Amount of the asset to be locked
+/// @params duration : Number of weeks for which the asset would be locked
+/// @params denom : Denom of the asset to be locked. Defaults to uusd
+pub fn query_simulate_weight(
+    deps: Deps,
+    amount: Uint128,
+    duration: u64,
+    denom: Option<String>,
+) -> StdResult<SimulateWeightResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom.unwrap_or_else(|| UUSD_DENOM.to_string());
+    let price_weight = find_asset(&config, &denom)?.price_weight;
+
+    Ok(SimulateWeightResponse {
+        boost: calculate_boost(duration, &config)?,
+        weight: calculate_weight(amount, price_weight, duration, &config)?,
+    })
+}
+
+/// @dev Projects the user's lockdrop reward, withdrawal limits, unlock times, pending xMARS rewards and MARS emissions at the given timestamp
+/// @params address : User address
+/// @params timestamp : Timestamp at which the values are projected. Defaults to the current block time
+pub fn query_simulate_user(
+    deps: Deps,
+    env: Env,
+    address: String,
+    timestamp: Option<u64>,
+) -> StdResult<SimulateUserResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let user_address = deps.api.addr_validate(&address)?;
+    let user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+    let current_timestamp = env.block.time.seconds();
+    let timestamp = timestamp.unwrap_or(current_timestamp);
+
+    // Rewards pending as of the current block
+    let user_response = query_user_info(deps, env, address)?;
+
+    // MARS emitted until the timestamp, shared pro-rata to the maUST shares. Only converted to xMARS once claimed
+    let mut estimated_mars_emissions = Uint128::zero();
+    if timestamp > current_timestamp
+        && !state.total_maust_locked.is_zero()
+        && !user_response.total_maust_share.is_zero()
+    {
+        if let Some(addresses) = &config.mars_addresses {
+            let mars_emissions = estimate_mars_emissions(
+                &deps.querier,
+                &addresses.incentives,
+                &config,
+                &state,
+                timestamp - current_timestamp,
+            )?;
+            estimated_mars_emissions = mars_emissions
+                .multiply_ratio(user_response.total_maust_share, state.total_maust_locked);
+        }
+    }
+
+    // Lockdrop rewards of the positions are stored once the user's MARS incentives are finalized
+    let is_finalized = !user_info.total_mars_incentives.is_zero();
+    let mut lockup_positions = vec![];
+    for lockup_id in user_info.lockup_positions {
+        let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
+        let price_weight = find_asset(&config, &lockup_info.denom)?.price_weight;
+        let mars_incentives = if is_finalized {
+            lockup_info.lockdrop_reward
+        } else {
+            calculate_mars_incentives_for_lockup(&lockup_info, &config, &state)?
+        };
+
+        lockup_positions.push(SimulatedLockupPosition {
+            weight: calculate_weight(
+                lockup_info.amount_locked,
+                price_weight,
+                lockup_info.duration,
+                &config,
+            )?,
+            mars_incentives,
+            max_withdrawable: max_withdrawable_amount(timestamp, &config, &lockup_info),
+            is_unlockable: lockup_info.unlock_timestamp <= timestamp,
+            lockup_id,
+            denom: lockup_info.denom,
+            duration: lockup_info.duration,
+            amount_locked: lockup_info.amount_locked,
+            unlock_timestamp: lockup_info.unlock_timestamp,
+        });
+    }
+
+    Ok(SimulateUserResponse {
+        timestamp,
+        total_mars_incentives: user_response.total_mars_incentives,
+        withdrawal_percent_allowed: allowed_withdrawal_percent(timestamp, &config),
+        pending_xmars_to_claim: user_response.pending_xmars_to_claim,
+        estimated_mars_emissions,
+        lockup_positions,
+    })
+}
+
+/// @dev Projects the lockdrop reward, withdrawal limit and unlock time of a deposit, as if made at the current block
+/// @params amount : Amount of the asset to be locked
+/// @params duration : Number of weeks for which the asset would be locked
+/// @params timestamp : Timestamp at which the withdrawal limit is projected. Defaults to the current block time
+/// @params denom : Denom of the asset to be locked. Defaults to uusd
+pub fn query_simulate_deposit(
+    deps: Deps,
+    env: Env,
+    amount: Uint128,
+    duration: u64,
+    timestamp: Option<u64>,
+    denom: Option<String>,
+) -> StdResult<SimulateDepositResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let timestamp = timestamp.unwrap_or_else(|| env.block.time.seconds());
+    let denom = denom.unwrap_or_else(|| UUSD_DENOM.to_string());
+    let price_weight = find_asset(&config, &denom)?.price_weight;
+
+    let weight = calculate_weight(amount, price_weight, duration, &config)?;
+    let lockup_info = LockupInfo {
+        duration,
+        denom,
+        amount_locked: amount,
+        unlock_timestamp: calculate_unlock_timestamp(&config, duration),
+        ..Default::default()
+    };
+
+    // The deposit's weight adds up to the total weight
+    state.total_deposits_weight += weight;
+
+    Ok(SimulateDepositResponse {
+        timestamp,
+        boost: calculate_boost(duration, &config)?,
+        weight,
+        mars_incentives: calculate_mars_incentives_for_lockup(&lockup_info, &config, &state)?,
+        max_withdrawable: max_withdrawable_amount(timestamp, &config, &lockup_info),
+        unlock_timestamp: lockup_info.unlock_timestamp,
+    })
+}
+
+/// @dev cw721 :: Returns the owner of the lockup position along-with its approvals
+/// @params token_id : Id of the lockup position
+/// @params include_expired : Boolean value indicating if expired approvals are to be returned
+pub fn query_owner_of(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<OwnerOfResponse> {
+    let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&token_id)?)?;
+    Ok(owner_of_response(&env, &lockup_info, include_expired))
+}
+
+/// @dev cw721 :: Returns the operators allowed to transfer the owner's lockup positions
+/// @params owner : Owner of the lockup positions
+/// @params include_expired : Boolean value indicating if expired approvals are to be returned
+/// @params start_after : Operator address after which the approvals are returned
+/// @params limit : Max number of approvals to be returned
+pub fn query_approved_for_all(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: bool,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ApprovedForAllResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|start_after| Bound::exclusive(start_after.as_bytes()));
+
+    let operators = OPERATORS
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, expires)) => include_expired || !expires.is_expired(&env.block),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| {
+            let (k, expires) = item?;
+            let spender = String::from_utf8(k)
+                .map_err(|_| StdError::generic_err("Invalid operator address"))?;
+            Ok(Cw721Approval { spender, expires })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ApprovedForAllResponse { operators })
+}
+
+/// @dev cw721 :: Returns the number of lockup positions
+pub fn query_num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
+    let state = STATE.load(deps.storage)?;
+    Ok(NumTokensResponse {
+        count: state.num_tokens,
+    })
+}
+
+/// @dev cw721 :: Returns the name and symbol of the lockup positions
+pub fn query_contract_info() -> ContractInfoResponse {
+    ContractInfoResponse {
+        name: NFT_NAME.to_string(),
+        symbol: NFT_SYMBOL.to_string(),
+    }
+}
+
+/// @dev cw721 :: Returns the metadata of the lockup position
+/// @params token_id : Id of the lockup position
+pub fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
+    let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&token_id)?)?;
+    Ok(nft_info_response(&token_id, &lockup_info))
+}
+
+/// @dev cw721 :: Returns the metadata and owner of the lockup position
+/// @params token_id : Id of the lockup position
+/// @params include_expired : Boolean value indicating if expired approvals are to be returned
+pub fn query_all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<AllNftInfoResponse> {
+    let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&token_id)?)?;
+    Ok(AllNftInfoResponse {
+        access: owner_of_response(&env, &lockup_info, include_expired),
+        info: nft_info_response(&token_id, &lockup_info),
+    })
+}
+
+/// @dev cw721 :: Returns the ids of the lockup positions owned by the user, ordered as in `query_all_tokens`
+/// @params owner : Owner of the lockup positions
+/// @params start_after : Lockup Id after which the ids are returned
+/// @params limit : Max number of ids to be returned
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let user_info = USER_INFO
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    let start = start_after
+        .map(|start_after| lockup_key(&start_after))
+        .transpose()?
+        .map(|start_after| start_after.joined_key());
+
+    // Ids are ordered by their storage key, i.e. by depositor, duration and sequence number
+    let mut tokens = user_info
+        .lockup_positions
+        .into_iter()
+        .map(|token_id| Ok((lockup_key(&token_id)?.joined_key(), token_id)))
+        .collect::<StdResult<Vec<_>>>()?;
+    tokens.sort();
+    let tokens = tokens
+        .into_iter()
+        .filter(|(key, _)| match &start {
+            Some(start) => key > start,
+            None => true,
+        })
+        .take(limit)
+        .map(|(_, token_id)| token_id)
+        .collect();
+
+    Ok(TokensResponse { tokens })
+}
+
+/// @dev cw721 :: Returns the ids of all the lockup positions, ordered by depositor, duration and sequence number
+/// @params start_after : Lockup Id after which the ids are returned
+/// @params limit : Max number of ids to be returned
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|start_after| lockup_key(&start_after))
+        .transpose()?
+        .map(|start_after| Bound::exclusive(start_after.joined_key()));
+
+    let tokens = all_lockups()
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|k| lockup_id_from_raw_key(&k))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
 //----------------------------------------------------------------------------------------
 // HELPERS
 //----------------------------------------------------------------------------------------
@@ -1301,50 +2971,584 @@ fn remove_lockup_pos_from_user_info(user_info: &mut UserInfo, lockup_id: String)
     }
 }
 
-///  @dev Helper function to calculate maximum % of UST deposited that can be withdrawn
-/// @params current_timestamp : Current block timestamp
-/// @params config : Contract configuration
-fn allowed_withdrawal_percent(current_timestamp: u64, config: &Config) -> Decimal {
-    let withdrawal_cutoff_init_point = config.init_timestamp + config.deposit_window;
-
-    // Deposit window :: 100% withdrawals allowed
-    if current_timestamp < withdrawal_cutoff_init_point {
-        return Decimal::from_ratio(100u32, 100u32);
+/// @dev Returns the id of the user's lockup position with the given duration, if any. Lockup positions keep their id when transferred or extended, hence neither the owner's address nor the duration can be derived from the id
+/// @params storage : Contract storage
+/// @params user_info : User Info struct
+/// @params duration : Duration of the lockup position
+/// @params lockup_id : Id of the lockup position. Required if the user holds several positions with the given duration
+fn find_user_lockup_id(
+    storage: &dyn Storage,
+    user_info: &UserInfo,
+    duration: u64,
+    lockup_id: Option<String>,
+) -> StdResult<Option<String>> {
+    let mut matching_ids = vec![];
+    for position_id in user_info.lockup_positions.iter() {
+        let lockup_info = LOCKUP_INFO.load(storage, lockup_key(position_id)?)?;
+        if lockup_info.duration == duration {
+            matching_ids.push(position_id.clone());
+        }
     }
 
-    let withdrawal_cutoff_second_point =
-        withdrawal_cutoff_init_point + (config.withdrawal_window / 2u64);
-    // Deposit window closed, 1st half of withdrawal window :: 50% withdrawals allowed
-    if current_timestamp <= withdrawal_cutoff_second_point {
-        return Decimal::from_ratio(50u32, 100u32);
+    if let Some(lockup_id) = lockup_id {
+        return Ok(matching_ids.into_iter().find(|id| *id == lockup_id));
     }
 
-    // max withdrawal allowed decreasing linearly from 50% to 0% vs time elapsed
-    let withdrawal_cutoff_final = withdrawal_cutoff_init_point + config.withdrawal_window;
-    //  Deposit window closed, 2nd half of withdrawal window :: max withdrawal allowed decreases linearly from 50% to 0% vs time elapsed
-    if current_timestamp < withdrawal_cutoff_final {
-        let time_left = withdrawal_cutoff_final - current_timestamp;
-        Decimal::from_ratio(
-            50u64 * time_left,
-            100u64 * (withdrawal_cutoff_final - withdrawal_cutoff_second_point),
-        )
-    }
-    // Withdrawals not allowed
-    else {
-        Decimal::from_ratio(0u32, 100u32)
+    if matching_ids.len() > 1 {
+        return Err(StdError::generic_err(format!(
+            "Multiple lockup positions with duration {}, lockup id needs to be provided",
+            duration
+        )));
     }
+    Ok(matching_ids.pop())
 }
 
-//-----------------------------
-// HELPER FUNCTIONS :: COMPUTATIONS
-//-----------------------------
+/// @dev Returns true if the address is the owner or the guardian
+fn is_owner_or_guardian(address: &Addr, config: &Config) -> bool {
+    *address == config.owner || Some(address) == config.guardian.as_ref()
+}
 
-/// @dev Function to calculate & update MARS rewards allocated for each of the user position
-/// @params config: configuration struct
-/// @params state: state struct
-/// @params user_info : user Info struct
-/// Returns user's total MARS rewards
-fn update_mars_rewards_allocated_to_lockup_positions(
+/// @dev Loads the emergency state. Fails if the lockdrop isn't in emergency mode
+fn load_emergency_state(storage: &dyn Storage) -> StdResult<EmergencyState> {
+    EMERGENCY
+        .may_load(storage)?
+        .ok_or_else(|| StdError::generic_err("Lockdrop is not in emergency mode"))
+}
+
+/// @dev Dissolves all the user's lockup positions, refunding the assets locked (or the corresponding maTokens once deposited in the Red Bank) along-with the user's pending rewards. Used in emergency mode
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params emergency : Emergency state struct
+/// @params user_address : User whose lockup positions are to be dissolved
+/// @params current_timestamp : Current block timestamp
+/// Returns the refund msgs (one per asset / maToken refunded, plus the rewards paid) along-with the events of the dissolved positions
+fn dissolve_user_lockup_positions(
+    mut deps: DepsMut,
+    config: &Config,
+    state: &mut State,
+    emergency: &mut EmergencyState,
+    user_address: &Addr,
+    current_timestamp: u64,
+) -> StdResult<(Vec<CosmosMsg>, Vec<Event>)> {
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, user_address)?
+        .unwrap_or_default();
+
+    // REWARDS :: Deposit weights (hence the lockdrop incentives) are final once the deposit / withdrawal windows are closed
+    // The user's pending rewards are paid out along-with the refunds
+    let are_weights_final = current_timestamp
+        >= config.init_timestamp + config.deposit_window + config.withdrawal_window;
+    if are_weights_final && !user_info.lockup_positions.is_empty() {
+        finalize_user_shares(deps.branch(), config, state, &mut user_info)?;
+    }
+    let pending_xmars =
+        compute_user_accrued_reward(state, &mut user_info) + user_info.unclaimed_xmars;
+    let mut pending_mars =
+        compute_user_forfeited_mars(state, &mut user_info) + user_info.unclaimed_forfeited_mars;
+    if !user_info.lockdrop_claimed {
+        pending_mars += user_info.total_mars_incentives - user_info.delegated_mars_incentives;
+    }
+
+    let mut assets_unlocked: Vec<Coin> = vec![];
+    let mut ma_tokens_withdrawn: Vec<Coin> = vec![];
+    let mut events: Vec<Event> = vec![];
+
+    // Loop over all Lockup Positions
+    for lockup_id in user_info.lockup_positions.iter() {
+        let lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(lockup_id)?)?;
+
+        let (ma_to_withdraw, maust_share) = calculate_lockup_ma_share(config, state, &lockup_info)?;
+
+        // WEIGHTS :: Dissolved deposits no longer share the lockdrop / referral incentives, unless the weights are final
+        // Saturating, as rounding in the weights of partial withdrawals mustn't block the refunds
+        if !are_weights_final {
+            let price_weight = find_asset(config, &lockup_info.denom)?.price_weight;
+            let dissolved_weight = calculate_weight(
+                lockup_info.amount_locked,
+                price_weight,
+                lockup_info.duration,
+                config,
+            )?;
+            state.total_deposits_weight =
+                state.total_deposits_weight.saturating_sub(dissolved_weight);
+            if let Some(referrer_address) = &lockup_info.referrer {
+                let mut referrer_info = REFERRERS.load(deps.storage, referrer_address)?;
+                referrer_info.referred_weight = referrer_info
+                    .referred_weight
+                    .saturating_sub(dissolved_weight);
+                sub_locked_amount(
+                    &mut referrer_info.total_referred,
+                    &Coin {
+                        denom: lockup_info.denom.clone(),
+                        amount: lockup_info.amount_locked,
+                    },
+                )?;
+                state.total_referred_weight =
+                    state.total_referred_weight.saturating_sub(dissolved_weight);
+                REFERRERS.save(deps.storage, referrer_address, &referrer_info)?;
+            }
+        }
+
+        // REFUND :: Asset if not yet deposited in the Red Bank, maToken otherwise
+        let asset_state = find_asset_state_mut(state, &lockup_info.denom)?;
+        if asset_state.final_ma_locked.is_zero() {
+            asset_state.total_locked = asset_state
+                .total_locked
+                .checked_sub(lockup_info.amount_locked)?;
+            add_locked_amount(
+                &mut assets_unlocked,
+                &Coin {
+                    denom: lockup_info.denom.clone(),
+                    amount: lockup_info.amount_locked,
+                },
+            )?;
+        } else {
+            asset_state.total_ma_locked =
+                asset_state.total_ma_locked.checked_sub(ma_to_withdraw)?;
+            state.total_maust_locked = state.total_maust_locked.checked_sub(maust_share)?;
+            add_locked_amount(
+                &mut ma_tokens_withdrawn,
+                &Coin {
+                    denom: lockup_info.denom.clone(),
+                    amount: ma_to_withdraw,
+                },
+            )?;
+        }
+
+        events.push(
+            Event::new("lockdrop::DissolvePosition")
+                .add_attribute("user", user_address)
+                .add_attribute("lockup_id", lockup_id)
+                .add_attribute("denom", &lockup_info.denom)
+                .add_attribute("amount_unlocked", lockup_info.amount_locked)
+                .add_attribute("ma_refunded", ma_to_withdraw),
+        );
+
+        // DISSOLVE LOCKUP POSITION
+        LOCKUP_INFO.remove(deps.storage, lockup_key(lockup_id)?);
+    }
+
+    if !user_info.lockup_positions.is_empty() {
+        emergency.users_processed += 1u64;
+        emergency.lockups_dissolved += user_info.lockup_positions.len() as u64;
+        state.num_tokens -= user_info.lockup_positions.len() as u64;
+    }
+
+    // Remove userInfo as all the assets have been refunded
+    USER_INFO.remove(deps.storage, user_address);
+
+    let mut refund_msgs = vec![];
+
+    // PAY :: xMARS rewards and MARS (lockdrop incentives, forfeited MARS) pending for the user
+    if !pending_xmars.is_zero() {
+        refund_msgs.push(build_transfer_cw20_token_msg(
+            user_address.clone(),
+            mars_addresses(config)?.xmars_token.to_string(),
+            pending_xmars,
+        )?);
+    }
+    if !pending_mars.is_zero() {
+        refund_msgs.push(build_transfer_cw20_token_msg(
+            user_address.clone(),
+            mars_addresses(config)?.mars_token.to_string(),
+            pending_mars,
+        )?);
+    }
+    if !pending_xmars.is_zero() || !pending_mars.is_zero() {
+        events.push(
+            Event::new("lockdrop::ClaimPendingRewards")
+                .add_attribute("user", user_address)
+                .add_attribute("xmars_claimed", pending_xmars)
+                .add_attribute("mars_claimed", pending_mars),
+        );
+    }
+
+    // REFUND :: xMARS compounded by the user
+    if !user_info.compounding_shares.is_zero() {
+        let xmars_refunded = redeem_compounding_shares(state, user_info.compounding_shares)?;
+        let xmars_address = mars_addresses(config)?.xmars_token.clone();
+        refund_msgs.push(build_transfer_cw20_token_msg(
+            user_address.clone(),
+            xmars_address.to_string(),
+            xmars_refunded,
+        )?);
+        events.push(
+            Event::new("lockdrop::RefundCompoundedXmars")
+                .add_attribute("user", user_address)
+                .add_attribute("xmars_refunded", xmars_refunded),
+        );
+    }
+
+    for asset_unlocked in assets_unlocked {
+        refund_msgs.push(build_send_native_asset_msg(
+            deps.as_ref(),
+            user_address.clone(),
+            &asset_unlocked.denom,
+            asset_unlocked.amount.into(),
+        )?);
+    }
+    for ma_token_withdrawn in ma_tokens_withdrawn {
+        let ma_token = find_asset(config, &ma_token_withdrawn.denom)?
+            .ma_token
+            .clone()
+            .ok_or_else(|| {
+                StdError::generic_err(format!("maToken not set for {}", ma_token_withdrawn.denom))
+            })?;
+        refund_msgs.push(build_transfer_cw20_token_msg(
+            user_address.clone(),
+            ma_token.to_string(),
+            ma_token_withdrawn.amount,
+        )?);
+    }
+
+    Ok((refund_msgs, events))
+}
+
+/// @dev Returns the storage key of a lockup position, i.e. (depositor, duration, seq)
+/// @params lockup_id : Id of the lockup position
+fn lockup_key(lockup_id: &str) -> StdResult<(Addr, U64Key, U64Key)> {
+    let (depositor, duration, seq) = parse_lockup_id(lockup_id)?;
+    Ok((
+        Addr::unchecked(depositor),
+        U64Key::new(duration),
+        U64Key::new(seq),
+    ))
+}
+
+/// @dev Returns the prefix over all the lockup positions. Maps with composite keys can only be iterated by prefix
+fn all_lockups() -> Prefix<LockupInfo> {
+    Prefix::new(LOCKUP_INFO_KEY.as_bytes(), &[])
+}
+
+/// @dev Returns the id of a lockup position from its raw storage key, i.e. the length prefixed depositor and duration followed by the sequence number
+/// @params key : Raw storage key of the lockup position
+fn lockup_id_from_raw_key(key: &[u8]) -> StdResult<String> {
+    let invalid_key = || StdError::generic_err("Invalid lockup position key");
+
+    let read_u64 = |bytes: &[u8]| -> StdResult<u64> {
+        let bytes: [u8; 8] = bytes.try_into().map_err(|_| invalid_key())?;
+        Ok(u64::from_be_bytes(bytes))
+    };
+
+    if key.len() < 2 {
+        return Err(invalid_key());
+    }
+    let depositor_len = u16::from_be_bytes([key[0], key[1]]) as usize;
+    // depositor | duration length prefix (2 bytes) | duration (8 bytes) | seq (8 bytes)
+    if key.len() != 2 + depositor_len + 2 + 8 + 8 {
+        return Err(invalid_key());
+    }
+    let depositor =
+        String::from_utf8(key[2..2 + depositor_len].to_vec()).map_err(|_| invalid_key())?;
+    let duration = read_u64(&key[4 + depositor_len..12 + depositor_len])?;
+    let seq = read_u64(&key[12 + depositor_len..])?;
+
+    Ok(format_lockup_id(&depositor, duration, seq))
+}
+
+/// @dev Transfers a lockup position. The position's asset / maToken share and MARS incentives are moved to the recipient, and the sender's pending xMARS rewards and forfeited MARS are settled pro-rata to the maUST share transferred.
+/// Returns the messages to be executed (MARS incentives of the position if the recipient has already claimed the lockdrop rewards)
+/// @params sender : Owner of the lockup position, an approved spender or an operator of the owner
+/// @params recipient : Address to which the lockup position is to be transferred
+/// @params token_id : Id of the lockup position to be transferred
+fn transfer_lockup_position(
+    mut deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    recipient: &Addr,
+    token_id: &str,
+) -> StdResult<Vec<CosmosMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK :: Lockup positions can only be transferred once their locked amount is final
+    if env.block.time.seconds() < config.init_timestamp
+        || is_withdraw_open(env.block.time.seconds(), &config)
+    {
+        return Err(StdError::generic_err(
+            "Lockup positions can only be transferred once the deposit / withdrawal windows are closed",
+        ));
+    }
+
+    let lockup_info = LOCKUP_INFO
+        .may_load(deps.storage, lockup_key(token_id)?)?
+        .ok_or_else(|| StdError::generic_err("Lockup doesn't exist"))?;
+    check_can_transfer(deps.as_ref(), env, sender, &lockup_info)?;
+
+    let owner = lockup_info.owner;
+    if *recipient == owner {
+        return Err(StdError::generic_err(
+            "Cannot transfer a lockup position to its owner",
+        ));
+    }
+
+    let mut owner_info = USER_INFO.load(deps.storage, &owner)?;
+    let mut recipient_info = USER_INFO
+        .may_load(deps.storage, recipient)?
+        .unwrap_or_default();
+
+    // Finalize maUST shares and MARS incentives of both users before moving the position
+    finalize_user_shares(deps.branch(), &config, &state, &mut owner_info)?;
+    finalize_user_shares(deps.branch(), &config, &state, &mut recipient_info)?;
+
+    // Lockdrop reward of the position is updated upon finalization
+    let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(token_id)?)?;
+    let (_, maust_share) = calculate_lockup_ma_share(&config, &state, &lockup_info)?;
+
+    // XMARS REWARDS :: Settle both users, pending rewards accrued by the position move along-with it
+    let owner_pending_xmars = compute_user_accrued_reward(&state, &mut owner_info);
+    let recipient_pending_xmars = compute_user_accrued_reward(&state, &mut recipient_info);
+    let xmars_transferred = if owner_info.total_maust_share.is_zero() {
+        Uint128::zero()
+    } else {
+        owner_pending_xmars.multiply_ratio(maust_share, owner_info.total_maust_share)
+    };
+    owner_info.unclaimed_xmars += owner_pending_xmars - xmars_transferred;
+    recipient_info.unclaimed_xmars += recipient_pending_xmars + xmars_transferred;
+
+    // FORFEITED MARS :: Settled the same way as the xMARS rewards
+    let owner_forfeited_mars = compute_user_forfeited_mars(&state, &mut owner_info);
+    let recipient_forfeited_mars = compute_user_forfeited_mars(&state, &mut recipient_info);
+    let forfeited_mars_transferred = if owner_info.total_maust_share.is_zero() {
+        Uint128::zero()
+    } else {
+        owner_forfeited_mars.multiply_ratio(maust_share, owner_info.total_maust_share)
+    };
+    owner_info.unclaimed_forfeited_mars += owner_forfeited_mars - forfeited_mars_transferred;
+    recipient_info.unclaimed_forfeited_mars +=
+        recipient_forfeited_mars + forfeited_mars_transferred;
+
+    // COMPOUNDED XMARS :: The position's compounding shares move along-with it
+    let compounding_shares = position_compounding_shares(&owner_info, maust_share);
+    owner_info.compounding_shares -= compounding_shares;
+    recipient_info.compounding_shares += compounding_shares;
+
+    // MARS INCENTIVES :: Move the position's lockdrop reward if not already claimed
+    let mut messages = vec![];
+    if !owner_info.lockdrop_claimed {
+        let owner_mars_incentives = owner_info
+            .total_mars_incentives
+            .checked_sub(lockup_info.lockdrop_reward)?;
+
+        // CHECK :: MARS delegated to the auction needs to remain covered by the owner's incentives
+        if owner_mars_incentives < owner_info.delegated_mars_incentives {
+            return Err(StdError::generic_err(format!(
+                "MARS delegated to the auction exceeds the owner's MARS incentives after the transfer. Delegated MARS = {}, MARS incentives after the transfer = {} ",
+                owner_info.delegated_mars_incentives, owner_mars_incentives
+            )));
+        }
+        owner_info.total_mars_incentives = owner_mars_incentives;
+
+        if !recipient_info.lockdrop_claimed {
+            recipient_info.total_mars_incentives += lockup_info.lockdrop_reward;
+        } else if !lockup_info.lockdrop_reward.is_zero() {
+            // Recipient has already claimed the lockdrop rewards :: MARS is transferred right away
+            let mars_token_address = mars_addresses(&config)?.mars_token.clone();
+            messages.push(build_transfer_cw20_token_msg(
+                recipient.clone(),
+                mars_token_address.to_string(),
+                lockup_info.lockdrop_reward,
+            )?);
+        }
+    } else if recipient_info.total_mars_incentives.is_zero() {
+        // Lockdrop reward of the position has been claimed by the owner. Prevents it from being allocated to the recipient again
+        recipient_info.lockdrop_claimed = true;
+    }
+
+    // ASSET / maUST :: Move the position's share
+    let position_locked = Coin {
+        denom: lockup_info.denom.clone(),
+        amount: lockup_info.amount_locked,
+    };
+    sub_locked_amount(&mut owner_info.total_locked, &position_locked)?;
+    owner_info.total_maust_share = owner_info.total_maust_share.checked_sub(maust_share)?;
+    add_locked_amount(&mut recipient_info.total_locked, &position_locked)?;
+    recipient_info.total_maust_share += maust_share;
+
+    // LOCKUP POSITION :: Update owner, approvals are cleared
+    remove_lockup_pos_from_user_info(&mut owner_info, token_id.to_string())?;
+    recipient_info.lockup_positions.push(token_id.to_string());
+    lockup_info.owner = recipient.clone();
+    lockup_info.approvals = vec![];
+
+    LOCKUP_INFO.save(deps.storage, lockup_key(token_id)?, &lockup_info)?;
+    USER_INFO.save(deps.storage, &owner, &owner_info)?;
+    USER_INFO.save(deps.storage, recipient, &recipient_info)?;
+
+    Ok(messages)
+}
+
+/// @dev Returns an error if the sender is neither the owner of the lockup position, an approved spender nor an operator of the owner
+/// @params sender : Address trying to transfer the lockup position
+/// @params lockup_info : Lockup position to be transferred
+fn check_can_transfer(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    lockup_info: &LockupInfo,
+) -> StdResult<()> {
+    if lockup_info.owner == *sender {
+        return Ok(());
+    }
+
+    if lockup_info
+        .approvals
+        .iter()
+        .any(|approval| approval.spender == *sender && !approval.expires.is_expired(&env.block))
+    {
+        return Ok(());
+    }
+
+    check_is_operator(deps, env, sender, lockup_info)
+}
+
+/// @dev Returns an error if the sender is neither the owner of the lockup position nor an operator of the owner
+/// @params sender : Address trying to update the approvals of the lockup position
+/// @params lockup_info : Lockup position
+fn check_is_operator(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    lockup_info: &LockupInfo,
+) -> StdResult<()> {
+    if lockup_info.owner == *sender {
+        return Ok(());
+    }
+
+    match OPERATORS.may_load(deps.storage, (&lockup_info.owner, sender))? {
+        Some(expires) if !expires.is_expired(&env.block) => Ok(()),
+        _ => Err(StdError::generic_err("Unauthorized")),
+    }
+}
+
+/// @dev Loads the lockup position whose approvals are to be updated by the sender
+/// @params sender : Address updating the approvals
+/// @params token_id : Id of the lockup position
+fn load_lockup_for_approval(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    token_id: &str,
+) -> StdResult<LockupInfo> {
+    let lockup_info = LOCKUP_INFO
+        .may_load(deps.storage, lockup_key(token_id)?)?
+        .ok_or_else(|| StdError::generic_err("Lockup doesn't exist"))?;
+    check_is_operator(deps, env, sender, &lockup_info)?;
+    Ok(lockup_info)
+}
+
+/// @dev Returns cw721 OwnerOf response of the lockup position
+/// @params lockup_info : Lockup position
+/// @params include_expired : Boolean value indicating if expired approvals are to be returned
+fn owner_of_response(
+    env: &Env,
+    lockup_info: &LockupInfo,
+    include_expired: bool,
+) -> OwnerOfResponse {
+    OwnerOfResponse {
+        owner: lockup_info.owner.to_string(),
+        approvals: lockup_info
+            .approvals
+            .iter()
+            .filter(|approval| include_expired || !approval.expires.is_expired(&env.block))
+            .map(|approval| Cw721Approval {
+                spender: approval.spender.to_string(),
+                expires: approval.expires,
+            })
+            .collect(),
+    }
+}
+
+/// @dev Returns cw721 NftInfo response of the lockup position
+/// @params token_id : Id of the lockup position
+/// @params lockup_info : Lockup position
+fn nft_info_response(token_id: &str, lockup_info: &LockupInfo) -> NftInfoResponse {
+    NftInfoResponse {
+        name: format!("{} #{}", NFT_NAME, token_id),
+        description: format!(
+            "{} {} locked for {} duration units, unlocks at timestamp {}",
+            lockup_info.amount_locked,
+            lockup_info.denom,
+            lockup_info.duration,
+            lockup_info.unlock_timestamp
+        ),
+        image: None,
+    }
+}
+
+/// @dev Validates the early unlock penalty and sets it in the config
+/// @params config : Configuration struct
+/// @params early_unlock_penalty : Penalty for unlocking a lockup position before its unlock timestamp
+fn set_early_unlock_penalty(
+    deps: Deps,
+    config: &mut Config,
+    early_unlock_penalty: EarlyUnlockPenalty,
+) -> StdResult<()> {
+    // CHECK :: Penalty rate needs to be within (0, 1]
+    if early_unlock_penalty.penalty_rate.is_zero()
+        || early_unlock_penalty.penalty_rate > Decimal::one()
+    {
+        return Err(StdError::generic_err("Invalid early unlock penalty rate"));
+    }
+
+    config.early_unlock_penalty_rate = early_unlock_penalty.penalty_rate;
+    config.treasury_address = early_unlock_penalty
+        .treasury_address
+        .map(|treasury_address| deps.api.addr_validate(&treasury_address))
+        .transpose()?;
+    Ok(())
+}
+
+/// @dev Sets the share of the lockdrop incentives allocated to the referrers
+/// @params referral_incentives_rate : Share of the lockdrop incentives (MARS) allocated to the referrers
+fn set_referral_incentives_rate(
+    config: &mut Config,
+    referral_incentives_rate: Decimal,
+) -> StdResult<()> {
+    // CHECK :: Rate needs to be within [0, 1)
+    if referral_incentives_rate >= Decimal::one() {
+        return Err(StdError::generic_err("Invalid referral incentives rate"));
+    }
+
+    config.referral_incentives_rate = referral_incentives_rate;
+    Ok(())
+}
+
+///  @dev Helper function to calculate maximum % of the amount deposited that can be withdrawn
+/// @params current_timestamp : Current block timestamp
+/// @params config : Contract configuration
+fn allowed_withdrawal_percent(current_timestamp: u64, config: &Config) -> Decimal {
+    config.withdrawal_schedule.max_withdrawal_percent(
+        config.init_timestamp + config.deposit_window,
+        config.withdrawal_window,
+        current_timestamp,
+    )
+}
+
+/// @dev Returns the amount of the asset which can be withdrawn from a lockup position at the given timestamp
+/// @params timestamp : Timestamp of the withdrawal
+/// @params config : Configuration struct
+/// @params lockup_info : Lockup position
+fn max_withdrawable_amount(timestamp: u64, config: &Config, lockup_info: &LockupInfo) -> Uint128 {
+    // Max 1 withdrawal allowed after the deposit window
+    let is_withdrawn =
+        lockup_info.withdrawal_flag && timestamp >= config.init_timestamp + config.deposit_window;
+    if !is_withdraw_open(timestamp, config) || is_withdrawn {
+        return Uint128::zero();
+    }
+    lockup_info.amount_locked * allowed_withdrawal_percent(timestamp, config)
+}
+
+//-----------------------------
+// HELPER FUNCTIONS :: COMPUTATIONS
+//-----------------------------
+
+/// @dev Function to calculate & update MARS rewards allocated for each of the user position
+/// @params config: configuration struct
+/// @params state: state struct
+/// @params user_info : user Info struct
+/// Returns user's total MARS rewards
+fn update_mars_rewards_allocated_to_lockup_positions(
     deps: DepsMut,
     config: &Config,
     state: &State,
@@ -1354,58 +3558,225 @@ fn update_mars_rewards_allocated_to_lockup_positions(
 
     for lockup_id in user_info.lockup_positions {
         // Retrieve mutable Lockup position
-        let mut lockup_info = LOCKUP_INFO
-            .load(deps.storage, lockup_id.as_bytes())
-            .unwrap();
+        let mut lockup_info = LOCKUP_INFO.load(deps.storage, lockup_key(&lockup_id)?)?;
 
-        let position_rewards = calculate_mars_incentives_for_lockup(
-            lockup_info.ust_locked,
-            lockup_info.duration,
-            config,
-            state.total_deposits_weight,
-        )?;
+        let position_rewards = calculate_mars_incentives_for_lockup(&lockup_info, config, state)?;
 
         lockup_info.lockdrop_reward = position_rewards;
         total_mars_rewards += position_rewards;
-        LOCKUP_INFO.save(deps.storage, lockup_id.as_bytes(), &lockup_info)?;
+        LOCKUP_INFO.save(deps.storage, lockup_key(&lockup_id)?, &lockup_info)?;
     }
     Ok(total_mars_rewards)
 }
 
+/// @dev Computes the user's maUST share (once the assets are deposited in the Red Bank) and MARS incentives if not already done
+/// @params config: configuration struct
+/// @params state: state struct
+/// @params user_info : user Info struct
+fn finalize_user_shares(
+    deps: DepsMut,
+    config: &Config,
+    state: &State,
+    user_info: &mut UserInfo,
+) -> StdResult<()> {
+    if user_info.total_maust_share.is_zero() {
+        user_info.total_maust_share = calculate_user_maust_share(config, state, user_info)?;
+    }
+    if user_info.total_mars_incentives.is_zero() {
+        user_info.total_mars_incentives = update_mars_rewards_allocated_to_lockup_positions(
+            deps,
+            config,
+            state,
+            user_info.clone(),
+        )?;
+    }
+    Ok(())
+}
+
 /// @dev Helper function to calculate MARS rewards for a particular Lockup position
-/// @params deposited_ust : UST deposited to that particular Lockup position
-/// @params duration : Duration of the lockup
+/// @params lockup_info : Lockup position
 /// @params config : Configuration struct
-/// @params total_deposits_weight : Total calculated weight of all the UST deposited in the contract
+/// @params state : State struct
 fn calculate_mars_incentives_for_lockup(
-    deposited_ust: Uint128,
-    duration: u64,
+    lockup_info: &LockupInfo,
     config: &Config,
-    total_deposits_weight: Uint128,
+    state: &State,
 ) -> StdResult<Uint128> {
+    let total_deposits_weight = state.total_deposits_weight;
     if total_deposits_weight == Uint128::zero() {
         return Ok(Uint128::zero());
     }
-    let amount_weight = calculate_weight(deposited_ust, duration, config)?;
-    Ok(config.lockdrop_incentives * Decimal::from_ratio(amount_weight, total_deposits_weight))
+    let price_weight = find_asset(config, &lockup_info.denom)?.price_weight;
+    let amount_weight = calculate_weight(
+        lockup_info.amount_locked,
+        price_weight,
+        lockup_info.duration,
+        config,
+    )?;
+    let lockers_incentives = config.lockdrop_incentives - total_referral_incentives(config, state);
+    Ok(lockers_incentives * Decimal::from_ratio(amount_weight, total_deposits_weight))
+}
+
+/// @dev Helper function. Returns the share of the lockdrop incentives allocated to the referrers, none if no deposit has been referred
+/// @params config : Configuration struct
+/// @params state : State struct
+fn total_referral_incentives(config: &Config, state: &State) -> Uint128 {
+    if state.total_referred_weight.is_zero() {
+        return Uint128::zero();
+    }
+    config.lockdrop_incentives * config.referral_incentives_rate
+}
+
+/// @dev Helper function. Returns the referrer's share of the referral incentives, pro-rata to his referred weight
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params referrer_info : ReferrerInfo struct
+fn calculate_referral_incentives(
+    config: &Config,
+    state: &State,
+    referrer_info: &ReferrerInfo,
+) -> Uint128 {
+    if state.total_referred_weight.is_zero() {
+        return Uint128::zero();
+    }
+    total_referral_incentives(config, state)
+        .multiply_ratio(referrer_info.referred_weight, state.total_referred_weight)
 }
 
 /// @dev Helper function. Returns effective weight for the amount to be used for calculating lockdrop rewards
-/// @params amount : Number of LP tokens
+/// @params amount : Amount of the asset locked
+/// @params price_weight : Price weight of the asset locked
+/// @params duration : Selected duration unit
+/// @config : Config struct
+fn calculate_weight(
+    amount: Uint128,
+    price_weight: Decimal,
+    duration: u64,
+    config: &Config,
+) -> StdResult<Uint128> {
+    Ok(amount * price_weight * calculate_boost(duration, config)?)
+}
+
+/// @dev Helper function. Returns the boost of the duration as per the configured boost curve
 /// @params duration : Selected duration unit
 /// @config : Config struct
-fn calculate_weight(amount: Uint128, duration: u64, config: &Config) -> StdResult<Uint128> {
-    // get boost value for duration
-    for lockup_option in config.lockup_durations.iter() {
-        if lockup_option.duration == duration {
-            return Ok(amount.mul(lockup_option.boost));
+fn calculate_boost(duration: u64, config: &Config) -> StdResult<Decimal> {
+    let boost = match &config.boost_curve {
+        BoostCurve::Fixed => config
+            .lockup_durations
+            .iter()
+            .find(|lockup_option| lockup_option.duration == duration)
+            .map(|lockup_option| lockup_option.boost),
+        BoostCurve::Linear {
+            min_duration,
+            max_duration,
+            min_boost,
+            max_boost,
+        } => {
+            if *min_duration <= duration && duration <= *max_duration {
+                Some(interpolate_boost(
+                    (*min_duration, *min_boost),
+                    (*max_duration, *max_boost),
+                    duration,
+                ))
+            } else {
+                None
+            }
         }
+        BoostCurve::PiecewiseLinear => config
+            .lockup_durations
+            .windows(2)
+            .find(|points| points[0].duration <= duration && duration <= points[1].duration)
+            .map(|points| {
+                interpolate_boost(
+                    (points[0].duration, points[0].boost),
+                    (points[1].duration, points[1].boost),
+                    duration,
+                )
+            }),
+    };
+
+    boost.ok_or_else(|| StdError::generic_err(format!("Boost not found for duration {}", duration)))
+}
+
+/// @dev Helper function. Interpolates linearly the boost of the duration between two (duration, boost) points
+/// @params start : Lower (duration, boost) point
+/// @params end : Upper (duration, boost) point
+/// @params duration : Selected duration unit, within the [start, end] durations
+fn interpolate_boost(start: (u64, Decimal), end: (u64, Decimal), duration: u64) -> Decimal {
+    let (start_duration, start_boost) = start;
+    let (end_duration, end_boost) = end;
+    if start_duration == end_duration {
+        return start_boost;
     }
 
-    Err(StdError::generic_err(format!(
-        "Boost not found for duration {}",
-        duration
-    )))
+    // Interpolated on the decimals' atomics, as Decimal * Decimal is not supported
+    let range = end_duration - start_duration;
+    let boost_atomics = Uint128::new(start_boost.numerator())
+        .multiply_ratio(end_duration - duration, range)
+        + Uint128::new(end_boost.numerator()).multiply_ratio(duration - start_duration, range);
+    Decimal::from_ratio(boost_atomics, start_boost.denominator())
+}
+
+/// @dev Helper function. Validates the lockup durations against the boost curve. The boost can't decrease as the
+/// duration increases, extending a lockup position never lowers its weight
+/// @params lockup_durations : Durations and boosties params
+/// @params boost_curve : Boost curve used to compute the boost of a lockup duration
+fn validate_boost_curve(
+    lockup_durations: &[LockupDurationParams],
+    boost_curve: &BoostCurve,
+) -> StdResult<()> {
+    let decreasing_boost_err =
+        || StdError::generic_err("Boost can't decrease as the lockup duration increases");
+
+    match boost_curve {
+        BoostCurve::Fixed => {
+            // CHECK :: Boost needs to be non-decreasing with the duration
+            let mut points = lockup_durations.to_vec();
+            points.sort_by_key(|point| point.duration);
+            if points
+                .windows(2)
+                .any(|points| points[0].boost > points[1].boost)
+            {
+                return Err(decreasing_boost_err());
+            }
+        }
+        BoostCurve::Linear {
+            min_duration,
+            max_duration,
+            min_boost,
+            max_boost,
+        } => {
+            // CHECK :: Duration range needs to be valid
+            if min_duration >= max_duration {
+                return Err(StdError::generic_err("Invalid boost curve duration range"));
+            }
+            // CHECK :: Boost needs to be non-decreasing with the duration
+            if min_boost > max_boost {
+                return Err(decreasing_boost_err());
+            }
+        }
+        BoostCurve::PiecewiseLinear => {
+            // CHECK :: At-least 2 points sorted by increasing duration are needed
+            if lockup_durations.len() < 2
+                || lockup_durations
+                    .windows(2)
+                    .any(|points| points[0].duration >= points[1].duration)
+            {
+                return Err(StdError::generic_err(
+                    "Piecewise linear boost curve needs at-least 2 lockup durations sorted by increasing duration",
+                ));
+            }
+            // CHECK :: Boost needs to be non-decreasing with the duration
+            if lockup_durations
+                .windows(2)
+                .any(|points| points[0].boost > points[1].boost)
+            {
+                return Err(decreasing_boost_err());
+            }
+        }
+    }
+    Ok(())
 }
 
 /// @dev Accrue xMARS rewards by updating the reward index
@@ -1419,32 +3790,289 @@ fn update_xmars_rewards_index(state: &mut State, xmas_accrued: Uint128) {
     state.xmars_rewards_index = state.xmars_rewards_index + xmars_rewards_index_increment;
 }
 
+/// @dev Redistributes forfeited MARS to the remaining lockers by updating the forfeited MARS index
+/// @params state : Global state struct
+/// @params mars_forfeited : MARS returned upon a forced unlock
+fn update_forfeited_mars_index(state: &mut State, mars_forfeited: Uint128) {
+    if state.total_maust_locked == Uint128::zero() {
+        return;
+    }
+    let forfeited_mars_index_increment =
+        Decimal::from_ratio(mars_forfeited, state.total_maust_locked);
+    state.forfeited_mars_index = state.forfeited_mars_index + forfeited_mars_index_increment;
+}
+
+/// @dev Accrue forfeited MARS for the user by updating the user forfeited MARS index and returns the forfeited MARS to be claimed by the user
+/// @params state : Global state struct
+/// @params user_info : UserInfo struct
+fn compute_user_forfeited_mars(state: &State, user_info: &mut UserInfo) -> Uint128 {
+    let pending_mars = (user_info.total_maust_share * state.forfeited_mars_index)
+        - (user_info.total_maust_share * user_info.forfeited_mars_index);
+    user_info.forfeited_mars_index = state.forfeited_mars_index;
+    pending_mars
+}
+
 /// @dev Accrue MARS reward for the user by updating the user reward index and and returns the pending rewards (xMars) to be claimed by the user
 /// @params state : Global state struct
 /// @params user_info : UserInfo struct
 fn compute_user_accrued_reward(state: &State, user_info: &mut UserInfo) -> Uint128 {
-    if state.final_ust_locked == Uint128::zero() {
-        return Uint128::zero();
-    }
     let pending_xmars = (user_info.total_maust_share * state.xmars_rewards_index)
         - (user_info.total_maust_share * user_info.reward_index);
     user_info.reward_index = state.xmars_rewards_index;
     pending_xmars
 }
 
-/// @dev Returns maUST Token share against UST amount. Calculated as =  (deposited UST / Final UST deposited) * Final maUST Locked
-/// @params ust_locked_share : UST amount for which maUST share is to be calculated
-/// @params final_ust_locked : Total UST amount which was deposited into Red Bank
-/// @params final_maust_locked : Total maUST tokens minted againt the UST deposited into Red Bank
-fn calculate_ma_ust_share(
-    ust_locked_share: Uint128,
-    final_ust_locked: Uint128,
-    final_maust_locked: Uint128,
+/// @dev Adds xMARS to the compounded xMARS and returns the number of shares issued against it
+/// @params state : Global state struct
+/// @params xmars_amount : xMARS rewards to be compounded
+fn compound_xmars(state: &mut State, xmars_amount: Uint128) -> Uint128 {
+    let shares = if state.total_compounding_shares.is_zero()
+        || state.total_compounded_xmars.is_zero()
+    {
+        xmars_amount
+    } else {
+        xmars_amount.multiply_ratio(state.total_compounding_shares, state.total_compounded_xmars)
+    };
+    state.total_compounded_xmars += xmars_amount;
+    state.total_compounding_shares += shares;
+    shares
+}
+
+/// @dev Returns the compounded xMARS against a number of shares
+/// @params state : Global state struct
+/// @params shares : Shares of the compounded xMARS
+fn compounding_shares_to_xmars(state: &State, shares: Uint128) -> Uint128 {
+    if state.total_compounding_shares.is_zero() {
+        return Uint128::zero();
+    }
+    shares.multiply_ratio(state.total_compounded_xmars, state.total_compounding_shares)
+}
+
+/// @dev Burns shares of the compounded xMARS and returns the xMARS to be paid out against them
+/// @params state : Global state struct
+/// @params shares : Shares of the compounded xMARS to be redeemed
+fn redeem_compounding_shares(state: &mut State, shares: Uint128) -> StdResult<Uint128> {
+    let xmars_amount = compounding_shares_to_xmars(state, shares);
+    state.total_compounding_shares = state.total_compounding_shares.checked_sub(shares)?;
+    state.total_compounded_xmars = state.total_compounded_xmars.checked_sub(xmars_amount)?;
+    Ok(xmars_amount)
+}
+
+/// @dev Returns the user's compounding shares attributed to a lockup position, pro-rata to its maUST share. All the remaining shares are attributed to the user's last lockup position
+/// @params user_info : UserInfo struct of the position's owner
+/// @params maust_share : maUST share of the lockup position
+fn position_compounding_shares(user_info: &UserInfo, maust_share: Uint128) -> Uint128 {
+    if user_info.lockup_positions.len() <= 1 || user_info.total_maust_share.is_zero() {
+        return user_info.compounding_shares;
+    }
+    user_info
+        .compounding_shares
+        .multiply_ratio(maust_share, user_info.total_maust_share)
+}
+
+/// @dev Returns maToken share against the amount of an asset. Calculated as =  (deposited amount / Final amount deposited) * Final maTokens Locked
+/// @params locked_share : Amount for which maToken share is to be calculated
+/// @params final_locked : Total amount of the asset which was deposited into Red Bank
+/// @params final_ma_locked : Total maTokens minted againt the asset deposited into Red Bank
+fn calculate_ma_share(
+    locked_share: Uint128,
+    final_locked: Uint128,
+    final_ma_locked: Uint128,
 ) -> Uint128 {
-    if final_ust_locked == Uint128::zero() {
+    if final_locked == Uint128::zero() {
         return Uint128::zero();
     }
-    final_maust_locked * Decimal::from_ratio(ust_locked_share, final_ust_locked)
+    final_ma_locked * Decimal::from_ratio(locked_share, final_locked)
+}
+
+/// @dev Returns the maToken share of a lockup position along-with its maUST share, i.e. the maToken share weighted by the asset's price weight
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params lockup_info : Lockup position
+fn calculate_lockup_ma_share(
+    config: &Config,
+    state: &State,
+    lockup_info: &LockupInfo,
+) -> StdResult<(Uint128, Uint128)> {
+    let asset = find_asset(config, &lockup_info.denom)?;
+    let asset_state = find_asset_state(state, &lockup_info.denom)?;
+    let ma_share = calculate_ma_share(
+        lockup_info.amount_locked,
+        asset_state.final_locked,
+        asset_state.final_ma_locked,
+    );
+    Ok((ma_share, ma_share * asset.price_weight))
+}
+
+/// @dev Returns the user's maUST share, i.e. the sum of his maToken shares weighted by the assets' price weights. Zero until the assets are deposited in the Red Bank
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params user_info : User Info struct
+fn calculate_user_maust_share(
+    config: &Config,
+    state: &State,
+    user_info: &UserInfo,
+) -> StdResult<Uint128> {
+    let mut maust_share = Uint128::zero();
+    for locked in user_info.total_locked.iter() {
+        let asset = find_asset(config, &locked.denom)?;
+        let asset_state = find_asset_state(state, &locked.denom)?;
+        let ma_share = calculate_ma_share(
+            locked.amount,
+            asset_state.final_locked,
+            asset_state.final_ma_locked,
+        );
+        maust_share += ma_share * asset.price_weight;
+    }
+    Ok(maust_share)
+}
+
+//-----------------------------
+// HELPER FUNCTIONS :: ASSETS
+//-----------------------------
+
+/// @dev Validates the assets and adds / updates them (matched by denom) in the config, new assets being added to the state as well
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params assets : Assets to be added / updated
+/// @params can_update_weights : Boolean value indicating if assets can be added / re-weighted, i.e. if deposits haven't been weighted yet
+fn update_assets(
+    config: &mut Config,
+    state: &mut State,
+    assets: Vec<AssetParams>,
+    can_update_weights: bool,
+) -> StdResult<()> {
+    for asset_params in assets {
+        // CHECK :: Denom and price weight need to be valid
+        if asset_params.denom.is_empty() {
+            return Err(StdError::generic_err("Invalid denom"));
+        }
+        if asset_params.price_weight.is_zero() {
+            return Err(StdError::generic_err(format!(
+                "Invalid price weight for {}",
+                asset_params.denom
+            )));
+        }
+        let reweighted = match config
+            .assets
+            .iter_mut()
+            .find(|asset| asset.denom == asset_params.denom)
+        {
+            Some(asset) => {
+                let reweighted = asset.price_weight != asset_params.price_weight;
+                asset.price_weight = asset_params.price_weight;
+                reweighted
+            }
+            None => {
+                config.assets.push(AssetInfo {
+                    denom: asset_params.denom.clone(),
+                    ma_token: None,
+                    price_weight: asset_params.price_weight,
+                });
+                state.assets.push(AssetState {
+                    denom: asset_params.denom,
+                    final_locked: Uint128::zero(),
+                    final_ma_locked: Uint128::zero(),
+                    total_locked: Uint128::zero(),
+                    total_ma_locked: Uint128::zero(),
+                });
+                true
+            }
+        };
+
+        // CHECK :: Deposits are weighted with the assets' price weights
+        if reweighted && !can_update_weights {
+            return Err(StdError::generic_err(
+                "Assets can only be added / re-weighted before the deposit window opens",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// @dev Returns the asset accepted for deposits with the given denom
+/// @params config : Configuration struct
+/// @params denom : Denom of the asset
+fn find_asset<'a>(config: &'a Config, denom: &str) -> StdResult<&'a AssetInfo> {
+    config
+        .assets
+        .iter()
+        .find(|asset| asset.denom == denom)
+        .ok_or_else(|| StdError::generic_err(format!("Asset {} not found", denom)))
+}
+
+/// @dev Returns the accepted asset with the given denom, to be updated
+/// @params config : Configuration struct
+/// @params denom : Denom of the asset
+fn find_asset_mut<'a>(config: &'a mut Config, denom: &str) -> StdResult<&'a mut AssetInfo> {
+    config
+        .assets
+        .iter_mut()
+        .find(|asset| asset.denom == denom)
+        .ok_or_else(|| StdError::generic_err(format!("Asset {} not found", denom)))
+}
+
+/// @dev Returns the Mars protocol addresses resolved via the address provider
+/// @params config : Configuration struct
+fn mars_addresses(config: &Config) -> StdResult<&MarsAddresses> {
+    config
+        .mars_addresses
+        .as_ref()
+        .ok_or_else(|| StdError::generic_err("Mars addresses not set"))
+}
+
+/// @dev Returns the amounts locked / maTokens held of the asset with the given denom
+/// @params state : State struct
+/// @params denom : Denom of the asset
+fn find_asset_state<'a>(state: &'a State, denom: &str) -> StdResult<&'a AssetState> {
+    state
+        .assets
+        .iter()
+        .find(|asset_state| asset_state.denom == denom)
+        .ok_or_else(|| StdError::generic_err(format!("Asset {} not found", denom)))
+}
+
+/// @dev Returns the amounts locked / maTokens held of the asset with the given denom, to be updated
+/// @params state : State struct
+/// @params denom : Denom of the asset
+fn find_asset_state_mut<'a>(state: &'a mut State, denom: &str) -> StdResult<&'a mut AssetState> {
+    state
+        .assets
+        .iter_mut()
+        .find(|asset_state| asset_state.denom == denom)
+        .ok_or_else(|| StdError::generic_err(format!("Asset {} not found", denom)))
+}
+
+/// @dev Adds the amount to the amounts locked per asset
+/// @params amounts : Amounts locked per asset
+/// @params coin : Amount of the asset to be added
+fn add_locked_amount(amounts: &mut Vec<Coin>, coin: &Coin) -> StdResult<()> {
+    if coin.amount.is_zero() {
+        return Ok(());
+    }
+    match amounts.iter_mut().find(|locked| locked.denom == coin.denom) {
+        Some(locked) => locked.amount = locked.amount.checked_add(coin.amount)?,
+        None => amounts.push(coin.clone()),
+    }
+    Ok(())
+}
+
+/// @dev Subtracts the amount from the amounts locked per asset. Assets with nothing left locked are removed
+/// @params amounts : Amounts locked per asset
+/// @params coin : Amount of the asset to be subtracted
+fn sub_locked_amount(amounts: &mut Vec<Coin>, coin: &Coin) -> StdResult<()> {
+    if coin.amount.is_zero() {
+        return Ok(());
+    }
+    let index = amounts
+        .iter()
+        .position(|locked| locked.denom == coin.denom)
+        .ok_or_else(|| StdError::generic_err(format!("No {} locked", coin.denom)))?;
+    amounts[index].amount = amounts[index].amount.checked_sub(coin.amount)?;
+    if amounts[index].amount.is_zero() {
+        amounts.remove(index);
+    }
+    Ok(())
 }
 
 //-----------------------------
@@ -1471,17 +4099,57 @@ pub fn query_pending_mars_to_be_claimed(
     Ok(response)
 }
 
-fn query_address(
+/// @dev Returns the Red Bank market of a native asset
+/// @params red_bank : Red Bank contract address
+/// @params denom : Denom of the asset
+fn query_red_bank_market(
     querier: &QuerierWrapper,
-    address_provider_address: Addr,
-    contract: MarsContract,
-) -> StdResult<Addr> {
-    let query: Addr = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: address_provider_address.to_string(),
-        msg: to_binary(&AddressProviderQueryMsg::Address { contract })?,
-    }))?;
+    red_bank: &Addr,
+    denom: &str,
+) -> StdResult<Market> {
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: red_bank.to_string(),
+        msg: to_binary(&RedBankQueryMsg::Market {
+            asset: RedBankAsset::Native {
+                denom: denom.to_string(),
+            },
+        })?,
+    }))
+}
 
-    Ok(query)
+/// @dev Resolves the Red Bank, incentives contract, MARS and xMARS token addresses via the address provider
+/// @params address_provider : Address provider contract address
+fn resolve_mars_addresses(
+    querier: &QuerierWrapper,
+    address_provider: Addr,
+) -> StdResult<MarsAddresses> {
+    let mars_contracts = vec![
+        MarsContract::RedBank,
+        MarsContract::Incentives,
+        MarsContract::MarsToken,
+        MarsContract::XMarsToken,
+    ];
+    let addresses = query_addresses(querier, address_provider, mars_contracts.clone())?;
+
+    // CHECK :: All the addresses need to be set in the address provider
+    if addresses.len() != mars_contracts.len() {
+        return Err(StdError::generic_err("mars address provider query failed"));
+    }
+    for (contract, address) in mars_contracts.iter().zip(addresses.iter()) {
+        if address.as_str().is_empty() {
+            return Err(StdError::generic_err(format!(
+                "{:?} address not set in the address provider",
+                contract
+            )));
+        }
+    }
+
+    Ok(MarsAddresses {
+        red_bank: addresses[0].clone(),
+        incentives: addresses[1].clone(),
+        mars_token: addresses[2].clone(),
+        xmars_token: addresses[3].clone(),
+    })
 }
 
 fn query_addresses(
@@ -1511,14 +4179,65 @@ fn query_addresses(
 // COSMOS_MSGs
 //-----------------------------
 
-/// @dev Helper function. Returns CosmosMsg to deposit UST into the Red Bank
+/// @dev Estimates the MARS emitted to the maTokens held by the contract over a period, as per the incentives contract's
+/// current emission rates. Each maToken's emission is shared pro-rata to the maTokens held among all its holders
+/// @params incentives_address : Incentives contract address
+/// @params config : Configuration struct
+/// @params state : State struct
+/// @params seconds : Length of the period
+fn estimate_mars_emissions(
+    querier: &QuerierWrapper,
+    incentives_address: &Addr,
+    config: &Config,
+    state: &State,
+    seconds: u64,
+) -> StdResult<Uint128> {
+    let mut mars_emissions = Uint128::zero();
+
+    for asset_state in state.assets.iter() {
+        let ma_token = match &find_asset(config, &asset_state.denom)?.ma_token {
+            Some(ma_token) if !asset_state.total_ma_locked.is_zero() => ma_token,
+            _ => continue,
+        };
+
+        let incentive_response: AssetIncentiveResponse =
+            querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: incentives_address.to_string(),
+                msg: to_binary(&IncentivesQueryMsg::AssetIncentive {
+                    ma_token_address: ma_token.to_string(),
+                })?,
+            }))?;
+        let asset_incentive = match incentive_response.asset_incentive {
+            Some(asset_incentive) => asset_incentive,
+            None => continue,
+        };
+
+        let token_info: TokenInfoResponse =
+            querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: ma_token.to_string(),
+                msg: to_binary(&Cw20QueryMsg::TokenInfo {})?,
+            }))?;
+        if token_info.total_supply.is_zero() {
+            continue;
+        }
+
+        mars_emissions += asset_incentive
+            .emission_per_second
+            .checked_mul(Uint128::from(seconds))?
+            .multiply_ratio(asset_state.total_ma_locked, token_info.total_supply);
+    }
+
+    Ok(mars_emissions)
+}
+
+/// @dev Helper function. Returns CosmosMsg to deposit a native asset into its Red Bank market
 /// @params redbank_address : Red Bank contract address
-/// @params denom_stable : uusd stable denom
-/// @params amount : UST amount to be deposited
+/// @params denom : Denom of the asset
+/// @params amount : Amount to be deposited
 fn build_deposit_into_redbank_msg(
     deps: Deps,
     redbank_address: Addr,
-    denom_stable: String,
+    denom: String,
     amount: Uint128,
 ) -> StdResult<CosmosMsg> {
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -1526,13 +4245,11 @@ fn build_deposit_into_redbank_msg(
         funds: vec![deduct_tax(
             deps,
             Coin {
-                denom: denom_stable.to_string(),
+                denom: denom.to_string(),
                 amount,
             },
         )?],
-        msg: to_binary(&RedBankExecuteMsg::DepositNative {
-            denom: denom_stable,
-        })?,
+        msg: to_binary(&RedBankExecuteMsg::DepositNative { denom })?,
     }))
 }
 
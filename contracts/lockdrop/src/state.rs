@@ -1,10 +1,14 @@
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw20::Expiration;
+use cw_storage_plus::{Item, Map, U64Key};
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use mars_periphery::lockdrop::LockupDurationParams;
+use mars_periphery::lockdrop::{
+    AssetInfo, AssetState, BoostCurve, LockupDurationParams, MarsAddresses,
+};
+use mars_periphery::withdrawal_schedule::WithdrawalSchedule;
 
 pub const CONFIG_KEY: &str = "config";
 pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
@@ -13,7 +17,43 @@ pub const STATE_KEY: &str = "state";
 pub const STATE: Item<State> = Item::new(STATE_KEY);
 
 pub const USER_INFO: Map<&Addr, UserInfo> = Map::new("users");
-pub const LOCKUP_INFO: Map<&[u8], LockupInfo> = Map::new("lockup_position");
+/// Lockup positions :: (depositor, duration, seq) -> lockup position
+pub const LOCKUP_INFO_KEY: &str = "lockup_positions";
+pub const LOCKUP_INFO: Map<(Addr, U64Key, U64Key), LockupInfo> = Map::new(LOCKUP_INFO_KEY);
+/// Sequence number of the next lockup position :: (depositor, duration) -> seq
+pub const LOCKUP_SEQUENCE: Map<(&Addr, U64Key), u64> = Map::new("lockup_sequence");
+/// Lockup positions keyed by `depositor + duration`. Migrated to `LOCKUP_INFO` upon `migrate`
+pub const LEGACY_LOCKUP_INFO: Map<&[u8], LegacyLockupInfo> = Map::new("lockup_position");
+/// Operators allowed to transfer all of the owner's lockup positions :: (owner, operator) -> expiration
+pub const OPERATORS: Map<(&Addr, &Addr), Expiration> = Map::new("operators");
+/// Deposits referred and referral incentives claim status :: referrer -> referrer info
+pub const REFERRERS: Map<&Addr, ReferrerInfo> = Map::new("referrers");
+/// Emergency mode status and `NukeLockdrop` progress. Set once emergency mode is entered
+pub const EMERGENCY: Item<EmergencyState> = Item::new("emergency");
+/// Red Bank deposits awaiting their reply :: reply id -> pending deposit
+pub const PENDING_RED_BANK_DEPOSITS: Map<U64Key, PendingRedBankDeposit> =
+    Map::new("pending_red_bank_deposits");
+/// xMARS claim awaiting its reply. Removed once the reply is processed
+pub const PENDING_XMARS_CLAIM: Item<PendingXmarsClaim> = Item::new("pending_xmars_claim");
+
+/// UST specific config / state / user info stored by earlier versions of the contract. Moved to the uusd asset upon `migrate`
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new(CONFIG_KEY);
+pub const LEGACY_STATE: Item<LegacyState> = Item::new(STATE_KEY);
+pub const LEGACY_USER_INFO: Map<&Addr, LegacyUserInfo> = Map::new("users");
+/// Progress of the migration of the legacy users / lockup positions. Set upon `migrate` of earlier versions of the contract
+pub const LEGACY_MIGRATION: Item<LegacyMigrationState> = Item::new("legacy_migration");
+
+/// Number of users processed per `NukeLockdrop` call, unless configured otherwise
+pub const DEFAULT_NUKE_BATCH_SIZE: u32 = 10;
+/// Number of legacy users / lockup positions migrated per `MigrateLegacyPositions` call, unless provided otherwise
+pub const DEFAULT_MIGRATION_BATCH_SIZE: u32 = 30;
+/// Denom of the assets locked by earlier versions of the contract
+pub const UUSD_DENOM: &str = "uusd";
+
+/// Reply id of the xMARS rewards claim from the incentives contract
+pub const CLAIM_XMARS_REPLY_ID: u64 = 1;
+/// Reply id of the Red Bank deposit of the first asset. Each asset's deposit is replied to with `offset + asset index`
+pub const RED_BANK_DEPOSIT_REPLY_ID_OFFSET: u64 = 100;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -21,8 +61,12 @@ pub struct Config {
     pub owner: Addr,
     /// Contract used to query addresses related to red-bank (MARS Token)
     pub address_provider: Option<Addr>,
-    ///  maUST token address - Minted upon UST deposits into red bank
-    pub ma_ust_token: Option<Addr>,
+    /// Mars protocol addresses resolved via the address provider, cached until the next `RefreshAddresses`
+    #[serde(default)]
+    pub mars_addresses: Option<MarsAddresses>,
+    /// Assets accepted for deposits, along-with their maToken and price weight
+    #[serde(default)]
+    pub assets: Vec<AssetInfo>,
     /// Auction Contract address to which MARS tokens can be deposited for bootstrapping MARS-UST Pool
     pub auction_contract_address: Option<Addr>,
     /// Timestamp when Contract will start accepting deposits
@@ -33,21 +77,51 @@ pub struct Config {
     pub withdrawal_window: u64,
     ///  Durations and boosties params
     pub lockup_durations: Vec<LockupDurationParams>,
+    /// Boost curve used to compute the boost of a lockup duration
+    #[serde(default)]
+    pub boost_curve: BoostCurve,
     /// Number of seconds per week
     pub seconds_per_duration_unit: u64,
     /// Total MARS lockdrop incentives to be distributed among the users
     pub lockdrop_incentives: Uint128,
+    /// Share of the position's lockdrop reward (MARS) to be returned to force unlock it
+    #[serde(default = "default_early_unlock_penalty_rate")]
+    pub early_unlock_penalty_rate: Decimal,
+    /// Address receiving the forfeited MARS. Forfeited MARS is redistributed to the remaining lockers if not set
+    #[serde(default)]
+    pub treasury_address: Option<Addr>,
+    /// Account which can enter emergency mode along-with the owner
+    #[serde(default)]
+    pub guardian: Option<Addr>,
+    /// Number of users whose lockup positions are dissolved per `NukeLockdrop` call
+    #[serde(default = "default_nuke_batch_size")]
+    pub nuke_batch_size: u32,
+    /// Share of the lockdrop incentives (MARS) allocated to the referrers, pro-rata to their referred weight
+    #[serde(default)]
+    pub referral_incentives_rate: Decimal,
+    /// Max share of their deposits which users can withdraw during the deposit / withdrawal windows
+    #[serde(default)]
+    pub withdrawal_schedule: WithdrawalSchedule,
+}
+
+pub fn default_early_unlock_penalty_rate() -> Decimal {
+    Decimal::one()
+}
+
+pub fn default_nuke_batch_size() -> u32 {
+    DEFAULT_NUKE_BATCH_SIZE
+}
+
+pub fn default_lockup_denom() -> String {
+    UUSD_DENOM.to_string()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
-    /// Total UST deposited at the end of Lockdrop window. This value remains unchanged post the lockdrop window
-    pub final_ust_locked: Uint128,
-    /// maUST minted at the end of Lockdrop window upon UST deposit in red bank. This value remains unchanged post the lockdrop window
-    pub final_maust_locked: Uint128,
-    /// UST deposited in the contract. This value is updated real-time upon each UST deposit / unlock
-    pub total_ust_locked: Uint128,
-    /// maUST held by the contract. This value is updated real-time upon each maUST withdrawal from red bank
+    /// Amounts locked / maTokens held per asset
+    #[serde(default)]
+    pub assets: Vec<AssetState>,
+    /// maTokens held by the contract, weighted by the assets' price weights (i.e. in maUST terms). This value is updated real-time upon each maToken withdrawal
     pub total_maust_locked: Uint128,
     /// MARS Tokens deposited into the bootstrap auction contract
     pub total_mars_delegated: Uint128,
@@ -57,13 +131,32 @@ pub struct State {
     pub total_deposits_weight: Uint128,
     /// Ratio of MARS rewards accured to total_maust_locked. Used to calculate MARS incentives accured by each user
     pub xmars_rewards_index: Decimal,
+    /// MARS returned upon forced unlocks of lockup positions
+    #[serde(default)]
+    pub total_mars_forfeited: Uint128,
+    /// Ratio of forfeited MARS redistributed to total_maust_locked. Used to calculate forfeited MARS accrued by each user
+    #[serde(default)]
+    pub forfeited_mars_index: Decimal,
+    /// xMARS rewards kept by the contract on behalf of the users compounding their rewards
+    #[serde(default)]
+    pub total_compounded_xmars: Uint128,
+    /// Total shares of the compounded xMARS
+    #[serde(default)]
+    pub total_compounding_shares: Uint128,
+    /// Total weight of the deposits made with a referrer
+    #[serde(default)]
+    pub total_referred_weight: Uint128,
+    /// Number of lockup positions (cw721 tokens) held by the users
+    #[serde(default)]
+    pub num_tokens: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UserInfo {
-    /// Total UST amount deposited by the user across all his lockup positions
-    pub total_ust_locked: Uint128,
-    /// User's maUST share against his total locked UST amount
+    /// Amounts deposited by the user across all his lockup positions, per asset
+    #[serde(default)]
+    pub total_locked: Vec<Coin>,
+    /// User's maToken shares against his locked amounts, weighted by the assets' price weights (i.e. in maUST terms)
     pub total_maust_share: Uint128,
     /// Contains lockup Ids of the User's lockup positions with different durations / deposit amounts
     pub lockup_positions: Vec<String>,
@@ -77,12 +170,27 @@ pub struct UserInfo {
     pub reward_index: Decimal,
     /// Pending rewards to be claimed by the user        
     pub total_xmars_claimed: Uint128,
+    /// xMARS rewards settled upon lockup position transfers, yet to be claimed by the user
+    #[serde(default)]
+    pub unclaimed_xmars: Uint128,
+    /// Ratio used to calculate the forfeited MARS redistributed to the user
+    #[serde(default)]
+    pub forfeited_mars_index: Decimal,
+    /// Forfeited MARS settled upon lockup position transfers, yet to be claimed by the user
+    #[serde(default)]
+    pub unclaimed_forfeited_mars: Uint128,
+    /// Boolean value indicating if the user's xMARS rewards are kept by the contract until his lockup positions are unlocked
+    #[serde(default)]
+    pub is_compounding: bool,
+    /// User's shares of the compounded xMARS
+    #[serde(default)]
+    pub compounding_shares: Uint128,
 }
 
 impl Default for UserInfo {
     fn default() -> Self {
         UserInfo {
-            total_ust_locked: Uint128::zero(),
+            total_locked: vec![],
             total_maust_share: Uint128::zero(),
             lockup_positions: vec![],
             total_mars_incentives: Uint128::zero(),
@@ -90,32 +198,168 @@ impl Default for UserInfo {
             lockdrop_claimed: false,
             reward_index: Decimal::zero(),
             total_xmars_claimed: Uint128::zero(),
+            unclaimed_xmars: Uint128::zero(),
+            forfeited_mars_index: Decimal::zero(),
+            unclaimed_forfeited_mars: Uint128::zero(),
+            is_compounding: false,
+            compounding_shares: Uint128::zero(),
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LockupInfo {
+    /// Owner of the lockup position. Lockup positions are transferable as cw721 tokens
+    pub owner: Addr,
     /// Lockup Duration
     pub duration: u64,
-    /// UST locked as part of this lockup position
-    pub ust_locked: Uint128,
+    /// Denom of the asset locked
+    #[serde(default = "default_lockup_denom")]
+    pub denom: String,
+    /// Amount of the asset locked as part of this lockup position
+    #[serde(alias = "ust_locked")]
+    pub amount_locked: Uint128,
     /// Lockdrop incentive allocated for this position
     pub lockdrop_reward: Uint128,
     /// Timestamp beyond which this position can be unlocked
     pub unlock_timestamp: u64,
     /// Boolean value indicating if the user's has withdrawn funds post the only 1 withdrawal limit cutoff
     pub withdrawal_flag: bool,
+    /// Accounts allowed to transfer the lockup position. Cleared upon transfer
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+    /// Referrer of the deposit, credited with its weight
+    #[serde(default)]
+    pub referrer: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Approval {
+    /// Account that can transfer the lockup position
+    pub spender: Addr,
+    /// When the Approval expires
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ReferrerInfo {
+    /// Total weight of the deposits referred
+    pub referred_weight: Uint128,
+    /// Number of deposits referred
+    pub deposits_referred: u64,
+    /// Amounts referred, per asset (net of withdrawals)
+    pub total_referred: Vec<Coin>,
+    /// Boolean value indicating if the referral incentives have been claimed or not
+    pub mars_claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRedBankDeposit {
+    /// Denom of the asset deposited
+    pub denom: String,
+    /// maToken minted upon the deposit
+    pub ma_token: Addr,
+    /// maToken balance before the deposit. Only used if the minted amount can't be read from the reply
+    pub prev_ma_balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingXmarsClaim {
+    /// xMARS Token address
+    pub xmars_token: Addr,
+    /// xMARS balance before the claim. Only used if the amount claimed can't be read from the reply
+    pub prev_xmars_balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyState {
+    /// Timestamp at which emergency mode was entered
+    pub activated_at: u64,
+    /// Account (owner / guardian) which entered emergency mode
+    pub activated_by: Addr,
+    /// Last user processed by `NukeLockdrop`. The next batch starts after this user
+    pub start_after: Option<Addr>,
+    /// Number of users whose lockup positions have been dissolved (via `NukeLockdrop` or `EmergencyExit`)
+    pub users_processed: u64,
+    /// Number of lockup positions dissolved
+    pub lockups_dissolved: u64,
+    /// Boolean value indicating if `NukeLockdrop` has processed all the users
+    pub is_complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct LegacyMigrationState {
+    /// Last legacy user migrated. The next batch of users starts after this user
+    pub users_start_after: Option<Addr>,
+    /// Boolean value indicating if the UST locked by all the legacy users has been migrated
+    pub are_users_migrated: bool,
+    /// Number of users migrated
+    pub users_migrated: u64,
+    /// Number of lockup positions migrated
+    pub lockups_migrated: u64,
+    /// Boolean value indicating if all the legacy users / lockup positions have been migrated
+    pub is_complete: bool,
 }
 
 impl Default for LockupInfo {
     fn default() -> Self {
         LockupInfo {
+            owner: Addr::unchecked(""),
             duration: 0_u64,
-            ust_locked: Uint128::zero(),
+            denom: default_lockup_denom(),
+            amount_locked: Uint128::zero(),
             lockdrop_reward: Uint128::zero(),
             unlock_timestamp: 0_u64,
             withdrawal_flag: false,
+            approvals: vec![],
+            referrer: None,
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyLockupInfo {
+    /// Owner of the lockup position. Not set for positions opened before they were transferable
+    #[serde(default)]
+    pub owner: Option<Addr>,
+    /// Lockup Duration
+    pub duration: u64,
+    /// UST locked as part of this lockup position
+    pub ust_locked: Uint128,
+    /// Lockdrop incentive allocated for this position
+    pub lockdrop_reward: Uint128,
+    /// Timestamp beyond which this position can be unlocked
+    pub unlock_timestamp: u64,
+    /// Boolean value indicating if the user's has withdrawn funds post the only 1 withdrawal limit cutoff
+    pub withdrawal_flag: bool,
+    /// Accounts allowed to transfer the lockup position
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    ///  maUST token address - Minted upon UST deposits into red bank
+    #[serde(default)]
+    pub ma_ust_token: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    /// Total UST deposited at the end of Lockdrop window
+    #[serde(default)]
+    pub final_ust_locked: Uint128,
+    /// maUST minted at the end of Lockdrop window upon UST deposit in red bank
+    #[serde(default)]
+    pub final_maust_locked: Uint128,
+    /// UST deposited in the contract
+    #[serde(default)]
+    pub total_ust_locked: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyUserInfo {
+    /// Total UST amount deposited by the user across all his lockup positions. Not set once migrated
+    #[serde(default)]
+    pub total_ust_locked: Option<Uint128>,
+}
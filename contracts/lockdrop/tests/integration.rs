@@ -3,10 +3,15 @@ use std::str::FromStr;
 use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
 use cosmwasm_std::{attr, to_binary, Addr, Coin, Decimal, Timestamp, Uint128};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use mars_periphery::cw721::{NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokensResponse};
 use mars_periphery::lockdrop::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupDurationParams,
-    LockupInfoResponse, QueryMsg, StateResponse, UpdateConfigMsg, UserInfoResponse,
+    AssetInfo, AssetParams, AssetState, BoostCurve, ConfigResponse, Cw20HookMsg,
+    EarlyUnlockPenalty, EmergencyStateResponse, ExecuteMsg, InstantiateMsg, LockupDurationParams,
+    LockupInfoResponse, QueryMsg, ReferrerResponse, SimulateDepositResponse, SimulateUserResponse,
+    SimulateWeightResponse, SimulatedLockupPosition, StateResponse, UpdateConfigMsg,
+    UserInfoResponse,
 };
+use mars_periphery::withdrawal_schedule::{WithdrawalSchedule, WithdrawalSegment};
 use terra_multi_test::{App, BankKeeper, ContractWrapper, Executor, TerraMockQuerier};
 
 use mars_core_dev as mars_core;
@@ -329,6 +334,7 @@ fn instantiate_auction_contract(
         ust_deposit_window: 10_000_00,
         mars_deposit_window: 10_000_00,
         withdrawal_window: 5_000_00,
+        withdrawal_schedule: None,
     };
 
     // Init contract
@@ -363,13 +369,15 @@ fn instantiate_lockdrop_contract(
     app: &mut App,
     owner: Addr,
     address_provider: Option<Addr>,
-    ma_ust_token: Option<Addr>,
 ) -> (Addr, InstantiateMsg) {
-    let lockdrop_contract = Box::new(ContractWrapper::new(
-        mars_lockdrop::contract::execute,
-        mars_lockdrop::contract::instantiate,
-        mars_lockdrop::contract::query,
-    ));
+    let lockdrop_contract = Box::new(
+        ContractWrapper::new(
+            mars_lockdrop::contract::execute,
+            mars_lockdrop::contract::instantiate,
+            mars_lockdrop::contract::query,
+        )
+        .with_reply(mars_lockdrop::contract::reply),
+    );
 
     let lockdrop_code_id = app.store_code(lockdrop_contract);
 
@@ -381,40 +389,44 @@ fn instantiate_lockdrop_contract(
     let mut lockdrop_instantiate_msg = mars_periphery::lockdrop::InstantiateMsg {
         owner: owner.clone().to_string(),
         address_provider: None,
-        ma_ust_token: None,
+        assets: vec![AssetParams {
+            denom: "uusd".to_string(),
+            price_weight: Decimal::one(),
+        }],
         init_timestamp: 10_000_01,
         deposit_window: 5_000_00,
         withdrawal_window: 2_000_00,
         lockup_durations: vec![
             LockupDurationParams {
                 duration: 3,
-                boost: Uint128::from(1_u64),
+                boost: Decimal::from_ratio(1_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 6,
-                boost: Uint128::from(2_u64),
+                boost: Decimal::from_ratio(2_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 9,
-                boost: Uint128::from(3_u64),
+                boost: Decimal::from_ratio(3_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 12,
-                boost: Uint128::from(4_u64),
+                boost: Decimal::from_ratio(4_u64, 1_u64),
             },
             LockupDurationParams {
                 duration: 15,
-                boost: Uint128::from(5_u64),
+                boost: Decimal::from_ratio(5_u64, 1_u64),
             },
         ],
+        boost_curve: None,
         seconds_per_duration_unit: 7 * 86400 as u64,
+        early_unlock_penalty: None,
+        referral_incentives_rate: None,
+        withdrawal_schedule: None,
     };
     if address_provider.is_some() {
         lockdrop_instantiate_msg.address_provider = Some(address_provider.unwrap().to_string());
     }
-    if ma_ust_token.is_some() {
-        lockdrop_instantiate_msg.ma_ust_token = Some(ma_ust_token.unwrap().to_string());
-    }
 
     // Init contract
     let lockdrop_instance = app
@@ -470,12 +482,8 @@ fn test_proper_initialization() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
-    let (lockdrop_instance, init_msg) = instantiate_lockdrop_contract(
-        &mut app,
-        owner,
-        Some(Addr::unchecked("address_provider")),
-        Some(Addr::unchecked("ma_ust_token")),
-    );
+    let (lockdrop_instance, init_msg) =
+        instantiate_lockdrop_contract(&mut app, owner, Some(Addr::unchecked("address_provider")));
 
     let resp: ConfigResponse = app
         .wrap()
@@ -489,14 +497,21 @@ fn test_proper_initialization() {
         resp.address_provider
     );
     assert_eq!(
-        Some(Addr::unchecked(init_msg.ma_ust_token.unwrap())),
-        resp.ma_ust_token
+        vec![AssetInfo {
+            denom: "uusd".to_string(),
+            ma_token: None,
+            price_weight: Decimal::one(),
+        }],
+        resp.assets
     );
+    assert_eq!(None, resp.mars_addresses);
     assert_eq!(None, resp.auction_contract_address);
     assert_eq!(init_msg.init_timestamp, resp.init_timestamp);
     assert_eq!(init_msg.deposit_window, resp.deposit_window);
     assert_eq!(init_msg.withdrawal_window, resp.withdrawal_window);
     assert_eq!(init_msg.lockup_durations, resp.lockup_durations);
+    assert_eq!(BoostCurve::Fixed, resp.boost_curve);
+    assert_eq!(WithdrawalSchedule::default(), resp.withdrawal_schedule);
     assert_eq!(
         init_msg.seconds_per_duration_unit,
         resp.seconds_per_duration_unit
@@ -508,9 +523,16 @@ fn test_proper_initialization() {
         .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
 
-    assert_eq!(Uint128::zero(), resp.final_ust_locked);
-    assert_eq!(Uint128::zero(), resp.final_maust_locked);
-    assert_eq!(Uint128::zero(), resp.total_ust_locked);
+    assert_eq!(
+        vec![AssetState {
+            denom: "uusd".to_string(),
+            final_locked: Uint128::zero(),
+            final_ma_locked: Uint128::zero(),
+            total_locked: Uint128::zero(),
+            total_ma_locked: Uint128::zero(),
+        }],
+        resp.assets
+    );
     assert_eq!(Uint128::zero(), resp.total_maust_locked);
     assert_eq!(Uint128::zero(), resp.total_mars_delegated);
     assert_eq!(false, resp.are_claims_allowed);
@@ -527,14 +549,23 @@ fn update_config() {
         &mut app,
         owner.clone(),
         Some(Addr::unchecked("address_provider")),
-        Some(Addr::unchecked("ma_ust_token")),
     );
 
     let update_config = UpdateConfigMsg {
         owner: Some("new_owner".to_string()),
         address_provider: Some("new_address_provider".to_string()),
-        ma_ust_token: Some("new_ma_ust_token".to_string()),
+        assets: Some(vec![AssetParams {
+            denom: "uusd".to_string(),
+            price_weight: Decimal::from_ratio(2u64, 1u64),
+        }]),
         auction_contract_address: Some("new_auction_contract".to_string()),
+        early_unlock_penalty: None,
+        lockup_durations: None,
+        boost_curve: None,
+        guardian: Some("new_guardian".to_string()),
+        nuke_batch_size: Some(20u32),
+        referral_incentives_rate: None,
+        withdrawal_schedule: None,
     };
 
     // ******* Error ::: Only owner can update *******
@@ -578,376 +609,585 @@ fn update_config() {
         Addr::unchecked("new_address_provider".to_string()),
         resp.address_provider.unwrap()
     );
-    assert_eq!(
-        Addr::unchecked("new_ma_ust_token".to_string()),
-        resp.ma_ust_token.unwrap()
-    );
+    assert_eq!(Decimal::from_ratio(2u64, 1u64), resp.assets[0].price_weight);
+    assert_eq!(None, resp.mars_addresses);
     assert_eq!(
         Addr::unchecked("new_auction_contract".to_string()),
         resp.auction_contract_address.unwrap()
     );
+    assert_eq!(
+        Addr::unchecked("new_guardian".to_string()),
+        resp.guardian.unwrap()
+    );
+    assert_eq!(20u32, resp.nuke_batch_size);
 }
 
 #[test]
-fn test_deposit_ust() {
+fn test_boost_curve() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
-
-    let (address_provider_instance, _, _, _, mars_token_instance) =
-        instantiate_red_bank(&mut app, owner.clone());
-
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
-                auction_contract_address: None,
-            },
-        },
-        &[],
-    )
-    .unwrap();
-
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(900_000_0000_000),
-        owner.to_string(),
-    );
-
-    app.execute_contract(
-        owner.clone(),
-        mars_token_instance.clone(),
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::from(1000000000000u64),
-            contract: lockdrop_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
     let user1_address = Addr::unchecked("user1");
-    let user2_address = Addr::unchecked("user2");
-
-    // Set user balances
     app.init_bank_balance(
         &user1_address.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(20000000u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(20000000u128),
-            },
-        ],
-    )
-    .unwrap();
-    app.init_bank_balance(
-        &user2_address.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(5435435u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(20000000u128),
-            },
-        ],
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
     )
     .unwrap();
 
     // ***
-    // *** Test :: Error "Deposit window closed" Reason :: Deposit attempt before deposit window is open ***
+    // *** Test :: Fixed boost curve :: Only the configured durations are supported ***
     // ***
 
-    let err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 3u64 },
-            &[],
+    let resp: SimulateWeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(1000u64),
+                duration: 6u64,
+                denom: None,
+            },
         )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Deposit window closed");
+        .unwrap();
+    assert_eq!(Decimal::from_ratio(2u64, 1u64), resp.boost);
+    assert_eq!(Uint128::from(2000u64), resp.weight);
 
-    // ***
-    // *** Test :: Error "Deposit window closed" Reason :: Deposit attempt after deposit window is closed ***
-    // ***
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(15_000_03)
-    });
     let err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 3u64 },
-            &[],
+        .wrap()
+        .query_wasm_smart::<SimulateWeightResponse, _, _>(
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(1000u64),
+                duration: 7u64,
+                denom: None,
+            },
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Deposit window closed");
+    assert!(err.to_string().contains("Boost not found for duration 7"));
 
     // ***
-    // *** Test :: Error "Trying to deposit several coins" ***
+    // *** Test :: Error "Invalid boost curve duration range" ***
     // ***
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10_000_03)
-    });
 
     let err = app
         .execute_contract(
-            user1_address.clone(),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 3u64 },
-            &[
-                Coin {
-                    denom: "uusd".to_string(),
-                    amount: Uint128::from(432423u128),
-                },
-                Coin {
-                    denom: "uluna".to_string(),
-                    amount: Uint128::from(432423u128),
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: None,
+                    lockup_durations: None,
+                    boost_curve: Some(BoostCurve::Linear {
+                        min_duration: 15u64,
+                        max_duration: 3u64,
+                        min_boost: Decimal::one(),
+                        max_boost: Decimal::from_ratio(5u64, 1u64),
+                    }),
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
                 },
-            ],
+            },
+            &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Trying to deposit several coins"
+        "Generic error: Invalid boost curve duration range"
     );
 
     // ***
-    // *** Test :: Error "Only UST among native tokens accepted" ***
+    // *** Test :: Error "Boost can't decrease as the lockup duration increases" (linear boost curve) ***
     // ***
 
     let err = app
         .execute_contract(
-            user1_address.clone(),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 3u64 },
-            &[Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::from(432423u128),
-            }],
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: None,
+                    lockup_durations: None,
+                    boost_curve: Some(BoostCurve::Linear {
+                        min_duration: 3u64,
+                        max_duration: 15u64,
+                        min_boost: Decimal::from_ratio(5u64, 1u64),
+                        max_boost: Decimal::one(),
+                    }),
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
+                },
+            },
+            &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Only UST among native tokens accepted"
+        "Generic error: Boost can't decrease as the lockup duration increases"
     );
 
     // ***
-    // *** Test :: Error "Amount must be greater than 0" ***
+    // *** Test :: Linear boost curve :: Boost interpolated over the duration range ***
     // ***
 
-    let err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 3u64 },
-            &[Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::from(0u128),
-            }],
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: Some(BoostCurve::Linear {
+                    min_duration: 3u64,
+                    max_duration: 15u64,
+                    min_boost: Decimal::one(),
+                    max_boost: Decimal::from_ratio(5u64, 1u64),
+                }),
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let resp: SimulateWeightResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(1000u64),
+                duration: 7u64,
+                denom: None,
+            },
         )
-        .unwrap_err();
+        .unwrap();
     assert_eq!(
-        err.to_string(),
-        "Generic error: Amount must be greater than 0"
+        Decimal::from_str("2.333333333333333332").unwrap(),
+        resp.boost
     );
+    assert_eq!(Uint128::from(2333u64), resp.weight);
+
+    let err = app
+        .wrap()
+        .query_wasm_smart::<SimulateWeightResponse, _, _>(
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(1000u64),
+                duration: 16u64,
+                denom: None,
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Boost not found for duration 16"));
 
     // ***
-    // *** Test :: Error "{} lockup duration not supported" ***
+    // *** Test :: Error "Piecewise linear boost curve needs at-least 2 lockup durations sorted by increasing duration" ***
     // ***
 
     let err = app
         .execute_contract(
-            user1_address.clone(),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 1u64 },
-            &[Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::from(10u128),
-            }],
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: None,
+                    lockup_durations: Some(vec![
+                        LockupDurationParams {
+                            duration: 3,
+                            boost: Decimal::from_ratio(1_u64, 1_u64),
+                        },
+                        LockupDurationParams {
+                            duration: 12,
+                            boost: Decimal::from_ratio(4_u64, 1_u64),
+                        },
+                        LockupDurationParams {
+                            duration: 6,
+                            boost: Decimal::from_ratio(2_u64, 1_u64),
+                        },
+                    ]),
+                    boost_curve: Some(BoostCurve::PiecewiseLinear),
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
+                },
+            },
+            &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Boost not found for duration 1"
+        "Generic error: Piecewise linear boost curve needs at-least 2 lockup durations sorted by increasing duration"
     );
 
+    // ***
+    // *** Test :: Error "Boost can't decrease as the lockup duration increases" (piecewise linear boost curve) ***
+    // ***
+
     let err = app
         .execute_contract(
-            user1_address.clone(),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUst { duration: 52u64 },
-            &[Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::from(10u128),
-            }],
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: None,
+                    lockup_durations: Some(vec![
+                        LockupDurationParams {
+                            duration: 3,
+                            boost: Decimal::from_ratio(1_u64, 1_u64),
+                        },
+                        LockupDurationParams {
+                            duration: 6,
+                            boost: Decimal::from_ratio(4_u64, 1_u64),
+                        },
+                        LockupDurationParams {
+                            duration: 12,
+                            boost: Decimal::from_ratio(2_u64, 1_u64),
+                        },
+                    ]),
+                    boost_curve: Some(BoostCurve::PiecewiseLinear),
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
+                },
+            },
+            &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Boost not found for duration 52"
+        "Generic error: Boost can't decrease as the lockup duration increases"
     );
 
     // ***
-    // *** Test #1 :: Successfully deposit UST  ***
+    // *** Test :: Piecewise linear boost curve :: Boost interpolated between the lockup durations ***
     // ***
 
     app.execute_contract(
-        user1_address.clone(),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 3u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: Some(vec![
+                    LockupDurationParams {
+                        duration: 3,
+                        boost: Decimal::from_ratio(1_u64, 1_u64),
+                    },
+                    LockupDurationParams {
+                        duration: 6,
+                        boost: Decimal::from_ratio(2_u64, 1_u64),
+                    },
+                    LockupDurationParams {
+                        duration: 15,
+                        boost: Decimal::from_ratio(5_u64, 1_u64),
+                    },
+                ]),
+                boost_curve: Some(BoostCurve::PiecewiseLinear),
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
     )
     .unwrap();
 
-    // let's verify the Lockdrop
-    let mut lockdrop_resp: LockupInfoResponse = app
+    let resp: SimulateWeightResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::LockupInfo {
-                address: user1_address.clone().to_string(),
-                duration: 3u64,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(1000u64),
+                duration: 10u64,
+                denom: None,
             },
         )
         .unwrap();
-
-    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
-    assert_eq!(3u64, lockup_query_data.duration);
-    assert_eq!(Uint128::from(10000u64), lockup_query_data.ust_locked);
-    assert_eq!(Uint128::zero(), lockup_query_data.maust_balance);
     assert_eq!(
-        Uint128::from(1000000000000u64),
-        lockup_query_data.lockdrop_reward
+        Decimal::from_str("3.333333333333333333").unwrap(),
+        resp.boost
     );
-    assert_eq!(3514401u64, lockup_query_data.unlock_timestamp);
-
-    // let's verify the User
-    let mut user_resp: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.clone().to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(10000u64), user_resp.total_ust_locked);
-    assert_eq!(Uint128::zero(), user_resp.total_maust_share);
-    assert_eq!(vec!["user13".to_string()], user_resp.lockup_position_ids);
-    assert_eq!(
-        Uint128::from(1000000000000u64),
-        user_resp.total_mars_incentives
-    );
-    assert_eq!(Uint128::zero(), user_resp.delegated_mars_incentives);
-    assert_eq!(false, user_resp.is_lockdrop_claimed);
-    assert_eq!(Decimal::zero(), user_resp.reward_index);
-    assert_eq!(Uint128::zero(), user_resp.total_xmars_claimed);
-    assert_eq!(Uint128::zero(), user_resp.pending_xmars_to_claim);
-
-    // let's verify the state
-    let mut state_resp: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::zero(), state_resp.final_ust_locked);
-    assert_eq!(Uint128::zero(), state_resp.final_maust_locked);
-    assert_eq!(Uint128::from(10000u64), state_resp.total_ust_locked);
-    assert_eq!(Uint128::zero(), state_resp.total_maust_locked);
-    assert_eq!(Uint128::from(10000u64), state_resp.total_deposits_weight);
-
-    // ***
-    // *** Test #2 :: Successfully deposit UST  ***
-    // ***
+    assert_eq!(Uint128::from(3333u64), resp.weight);
 
+    // Deposits are weighted with the interpolated boost
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_01)
+    });
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 10u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
+            amount: Uint128::from(1000u64),
         }],
     )
     .unwrap();
 
-    // let's verify the Lockdrop
-    lockdrop_resp = app
+    let resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::LockupInfo {
-                address: user1_address.clone().to_string(),
-                duration: 15u64,
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(3333u64), resp.total_deposits_weight);
+
+    // ***
+    // *** Test :: Error "Lockup durations / boost curve can only be updated before the deposit window opens" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: None,
+                    lockup_durations: None,
+                    boost_curve: Some(BoostCurve::Fixed),
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
+                },
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockup durations / boost curve can only be updated before the deposit window opens"
+    );
+}
 
-    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
-    assert_eq!(15u64, lockup_query_data.duration);
-    assert_eq!(Uint128::from(10000u64), lockup_query_data.ust_locked);
-    assert_eq!(Uint128::zero(), lockup_query_data.maust_balance);
+#[test]
+fn test_withdrawal_schedule() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let update_schedule_msg = |withdrawal_schedule: WithdrawalSchedule| ExecuteMsg::UpdateConfig {
+        new_config: UpdateConfigMsg {
+            owner: None,
+            address_provider: None,
+            assets: None,
+            auction_contract_address: None,
+            early_unlock_penalty: None,
+            lockup_durations: None,
+            boost_curve: None,
+            guardian: None,
+            nuke_batch_size: None,
+            referral_incentives_rate: None,
+            withdrawal_schedule: Some(withdrawal_schedule),
+        },
+    };
+    let query_percent = |app: &App, timestamp: u64| -> Decimal {
+        app.wrap()
+            .query_wasm_smart(
+                &lockdrop_instance,
+                &QueryMsg::WithdrawalPercentAllowed {
+                    timestamp: Some(timestamp),
+                },
+            )
+            .unwrap()
+    };
+
+    // ***
+    // *** Test :: Default schedule :: 100% during the deposit window, 50% during the 1st half of the withdrawal window ***
+    // *** then decreasing linearly to 0% ***
+    // ***
+
+    assert_eq!(Decimal::one(), query_percent(&app, 15_000_00));
+    assert_eq!(Decimal::percent(50), query_percent(&app, 16_000_01));
+    assert_eq!(Decimal::percent(25), query_percent(&app, 16_500_01));
+    assert_eq!(Decimal::zero(), query_percent(&app, 17_000_01));
+
+    // ***
+    // *** Test :: Error "Withdrawal schedule percents can't exceed 100%" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_schedule_msg(WithdrawalSchedule {
+                deposit_window_max_percent: Decimal::percent(101),
+                segments: WithdrawalSchedule::default().segments,
+            }),
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(833333333333u64),
-        lockup_query_data.lockdrop_reward
+        err.to_string(),
+        "Generic error: Withdrawal schedule percents can't exceed 100%"
     );
-    assert_eq!(10772001u64, lockup_query_data.unlock_timestamp);
 
-    // let's verify the User
-    user_resp = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.clone().to_string(),
-            },
+    // ***
+    // *** Test :: Error "Withdrawal schedule segments need to be sorted by increasing end" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_schedule_msg(WithdrawalSchedule {
+                deposit_window_max_percent: Decimal::one(),
+                segments: vec![
+                    WithdrawalSegment {
+                        end: Decimal::one(),
+                        start_percent: Decimal::percent(50),
+                        end_percent: Decimal::zero(),
+                    },
+                    WithdrawalSegment {
+                        end: Decimal::percent(50),
+                        start_percent: Decimal::percent(50),
+                        end_percent: Decimal::percent(50),
+                    },
+                ],
+            }),
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(20000u64), user_resp.total_ust_locked);
-    assert_eq!(Uint128::zero(), user_resp.total_maust_share);
+        .unwrap_err();
     assert_eq!(
-        vec!["user13".to_string(), "user115".to_string()],
-        user_resp.lockup_position_ids
+        err.to_string(),
+        "Generic error: Withdrawal schedule segments need to be sorted by increasing end"
     );
+
+    // ***
+    // *** Test :: Error "Withdrawal schedule segments need to end with the withdrawal window" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_schedule_msg(WithdrawalSchedule {
+                deposit_window_max_percent: Decimal::one(),
+                segments: vec![WithdrawalSegment {
+                    end: Decimal::percent(50),
+                    start_percent: Decimal::percent(50),
+                    end_percent: Decimal::percent(50),
+                }],
+            }),
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(999999999999u64),
-        user_resp.total_mars_incentives
+        err.to_string(),
+        "Generic error: Withdrawal schedule segments need to end with the withdrawal window"
     );
 
-    // let's verify the state
-    state_resp = app
+    // ***
+    // *** Test :: Custom schedule :: Max % interpolated linearly over each segment ***
+    // ***
+
+    let withdrawal_schedule = WithdrawalSchedule {
+        deposit_window_max_percent: Decimal::one(),
+        segments: vec![
+            WithdrawalSegment {
+                end: Decimal::percent(50),
+                start_percent: Decimal::percent(80),
+                end_percent: Decimal::percent(20),
+            },
+            WithdrawalSegment {
+                end: Decimal::one(),
+                start_percent: Decimal::percent(10),
+                end_percent: Decimal::percent(10),
+            },
+        ],
+    };
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &update_schedule_msg(withdrawal_schedule.clone()),
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
         .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::Config {})
         .unwrap();
-    assert_eq!(Uint128::zero(), state_resp.final_ust_locked);
-    assert_eq!(Uint128::zero(), state_resp.final_maust_locked);
-    assert_eq!(Uint128::from(20000u64), state_resp.total_ust_locked);
-    assert_eq!(Uint128::zero(), state_resp.total_maust_locked);
-    assert_eq!(Uint128::from(60000u64), state_resp.total_deposits_weight);
+    assert_eq!(withdrawal_schedule, resp.withdrawal_schedule);
+
+    assert_eq!(Decimal::one(), query_percent(&app, 15_000_00));
+    assert_eq!(Decimal::percent(80), query_percent(&app, 15_000_01));
+    assert_eq!(Decimal::percent(50), query_percent(&app, 15_500_01));
+    assert_eq!(Decimal::percent(20), query_percent(&app, 16_000_01));
+    assert_eq!(Decimal::percent(10), query_percent(&app, 16_500_01));
+    assert_eq!(Decimal::zero(), query_percent(&app, 17_000_01));
+
+    // ***
+    // *** Test :: Error "Withdrawal schedule can only be updated before the deposit window opens" ***
+    // ***
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_01)
+    });
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_schedule_msg(WithdrawalSchedule::default()),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Withdrawal schedule can only be updated before the deposit window opens"
+    );
 }
 
 #[test]
-fn test_withdraw_ust() {
+fn test_deposit_ust() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
     let (address_provider_instance, _, _, _, mars_token_instance) =
         instantiate_red_bank(&mut app, owner.clone());
@@ -959,14 +1199,29 @@ fn test_withdraw_ust() {
             new_config: UpdateConfigMsg {
                 owner: None,
                 address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
     mint_some_mars(
         &mut app,
         owner.clone(),
@@ -988,6 +1243,7 @@ fn test_withdraw_ust() {
     .unwrap();
 
     let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
 
     // Set user balances
     app.init_bank_balance(
@@ -1004,352 +1260,344 @@ fn test_withdraw_ust() {
         ],
     )
     .unwrap();
-
-    // for successful deposit
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10_000_03)
-    });
-
-    // ######    SUCCESS :: UST Successfully deposited     ######
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(5000u128),
-        }],
+    app.init_bank_balance(
+        &user2_address.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(5435435u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+        ],
     )
     .unwrap();
 
-    // Check user response
-    let mut user_resp: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+    // ***
+    // *** Test :: Error "Deposit window closed" Reason :: Deposit attempt before deposit window is open ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(5000u128), user_resp.total_ust_locked);
-    assert_eq!(vec!["user16"], user_resp.lockup_position_ids);
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Deposit window closed");
 
-    // ######    SUCCESS :: All UST Successfully withdrawn and lockup position removed from state     ######
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::WithdrawUst {
-            duration: 6u64,
-            amount: Uint128::from(5000u128),
-        },
-        &[],
-    )
-    .unwrap();
-    user_resp = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+    // ***
+    // *** Test :: Error "Deposit window closed" Reason :: Deposit attempt after deposit window is closed ***
+    // ***
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(15_000_03)
+    });
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(0u128), user_resp.total_ust_locked);
-    assert_eq!(true, user_resp.lockup_position_ids.is_empty());
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Deposit window closed");
 
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
-    )
-    .unwrap();
-
-    // ######    SUCCESS :: UST Successfully withdrawn (when withdrawals allowed)     ######
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::WithdrawUst {
-            duration: 6u64,
-            amount: Uint128::from(100u128),
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Check state response
-    let mut state_resp: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(19900u64), state_resp.total_ust_locked);
-
-    // Check user response
-    let mut user_resp: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(19900u64), user_resp.total_ust_locked);
-
-    // let's verify the Lockdrop
-    let mut lockdrop_resp: LockupInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::LockupInfo {
-                address: user1_address.clone().to_string(),
-                duration: 6u64,
-            },
-        )
-        .unwrap();
-
-    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
-    assert_eq!(6u64, lockup_query_data.duration);
-    assert_eq!(Uint128::from(9900u64), lockup_query_data.ust_locked);
-
-    // close deposit window. Max 50% withdrawals allowed now
+    // ***
+    // *** Test :: Error "Trying to deposit several coins" ***
+    // ***
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(15_000_01)
+        b.time = Timestamp::from_seconds(10_000_03)
     });
 
-    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}   ######
-
-    let mut err = app
+    let err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawUst {
-                amount: Uint128::from(9000u64),
-                duration: 6u64,
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
-            &[],
+            &[
+                Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(432423u128),
+                },
+                Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(432423u128),
+                },
+            ],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4950 "
+        "Generic error: Trying to deposit several coins"
     );
 
-    // ######    SUCCESS :: Withdraw 50% successfully   ######
-
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::WithdrawUst {
-            amount: Uint128::from(4950u64),
-            duration: 6u64,
-        },
-        &[],
-    )
-    .unwrap();
-    // Check state response
-    state_resp = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(14950u64), state_resp.total_ust_locked);
+    // ***
+    // *** Test :: Error "uluna not accepted for deposits" ***
+    // ***
 
-    // Check user response
-    user_resp = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
+            &[Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::from(432423u128),
+            }],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(14950u64), user_resp.total_ust_locked);
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: uluna not accepted for deposits"
+    );
 
-    // let's verify the Lockdrop
-    lockdrop_resp = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::LockupInfo {
-                address: user1_address.clone().to_string(),
-                duration: 6u64,
+    // ***
+    // *** Test :: Error "Amount must be greater than 0" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(0u128),
+            }],
         )
-        .unwrap();
-
-    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
-    assert_eq!(6u64, lockup_query_data.duration);
-    assert_eq!(Uint128::from(4950u64), lockup_query_data.ust_locked);
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Amount must be greater than 0"
+    );
 
-    // ######    ERROR :: Max 1 withdrawal allowed during current window   ######
+    // ***
+    // *** Test :: Error "{} lockup duration not supported" ***
+    // ***
 
-    err = app
+    let err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawUst {
-                amount: Uint128::from(10u64),
-                duration: 6u64,
+            &ExecuteMsg::DepositUst {
+                duration: 1u64,
+                recipient: None,
+                referrer: None,
             },
-            &[],
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+            }],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Max 1 withdrawal allowed");
-
-    // 50% of withdrawal window over. Max withdrawal % decreasing linearly now
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(16_050_01)
-    });
-
-    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}   ######
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Boost not found for duration 1"
+    );
 
-    err = app
+    let err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawUst {
-                amount: Uint128::from(7000u64),
-                duration: 15u64,
+            &ExecuteMsg::DepositUst {
+                duration: 52u64,
+                recipient: None,
+                referrer: None,
             },
-            &[],
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(10u128),
+            }],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4750 "
+        "Generic error: Boost not found for duration 52"
     );
 
-    // ######    SUCCESS :: Withdraw some UST successfully   ######
+    // ***
+    // *** Test #1 :: Successfully deposit UST  ***
+    // ***
 
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::WithdrawUst {
-            amount: Uint128::from(750u64),
-            duration: 15u64,
+        &ExecuteMsg::DepositUst {
+            duration: 3u64,
+            recipient: None,
+            referrer: None,
         },
-        &[],
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
     )
     .unwrap();
 
-    // Check state response
-    state_resp = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(14200u64), state_resp.total_ust_locked);
-
-    // Check user response
-    user_resp = app
+    // let's verify the Lockdrop
+    let mut lockdrop_resp: LockupInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+            &QueryMsg::LockupInfo {
+                address: user1_address.clone().to_string(),
+                duration: 3u64,
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(14200u64), user_resp.total_ust_locked);
 
-    // let's verify the Lockdrop
-    lockdrop_resp = app
+    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(3u64, lockup_query_data.duration);
+    assert_eq!(Uint128::from(10000u64), lockup_query_data.amount_locked);
+    assert_eq!(Uint128::zero(), lockup_query_data.ma_token_balance);
+    assert_eq!(
+        Uint128::from(1000000000000u64),
+        lockup_query_data.lockdrop_reward
+    );
+    assert_eq!(3514401u64, lockup_query_data.unlock_timestamp);
+
+    // let's verify the User
+    let mut user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::LockupInfo {
+            &QueryMsg::UserInfo {
                 address: user1_address.clone().to_string(),
-                duration: 15u64,
             },
         )
         .unwrap();
+    assert_eq!(vec![Coin::new(10000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(Uint128::zero(), user_resp.total_maust_share);
+    assert_eq!(vec!["user1-3-0".to_string()], user_resp.lockup_position_ids);
     assert_eq!(
-        Uint128::from(9250u64),
-        lockdrop_resp.lockup_info.unwrap().ust_locked
+        Uint128::from(1000000000000u64),
+        user_resp.total_mars_incentives
     );
+    assert_eq!(Uint128::zero(), user_resp.delegated_mars_incentives);
+    assert_eq!(false, user_resp.is_lockdrop_claimed);
+    assert_eq!(Decimal::zero(), user_resp.reward_index);
+    assert_eq!(Uint128::zero(), user_resp.total_xmars_claimed);
+    assert_eq!(Uint128::zero(), user_resp.pending_xmars_to_claim);
 
-    // // ######    ERROR :: Max 1 withdrawal allowed during current window   ######
+    // let's verify the state
+    let mut state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::zero(), state_resp.assets[0].final_locked);
+    assert_eq!(Uint128::zero(), state_resp.assets[0].final_ma_locked);
+    assert_eq!(Uint128::from(10000u64), state_resp.assets[0].total_locked);
+    assert_eq!(Uint128::zero(), state_resp.total_maust_locked);
+    assert_eq!(Uint128::from(10000u64), state_resp.total_deposits_weight);
 
-    err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawUst {
-                amount: Uint128::from(50u64),
+    // ***
+    // *** Test #2 :: Successfully deposit UST  ***
+    // ***
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // let's verify the Lockdrop
+    lockdrop_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.clone().to_string(),
                 duration: 15u64,
             },
-            &[],
         )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Max 1 withdrawal allowed");
-
-    // finish withdraw period for deposit failure
+        .unwrap();
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_41)
-    });
+    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(15u64, lockup_query_data.duration);
+    assert_eq!(Uint128::from(10000u64), lockup_query_data.amount_locked);
+    assert_eq!(Uint128::zero(), lockup_query_data.ma_token_balance);
+    assert_eq!(
+        Uint128::from(833333333333u64),
+        lockup_query_data.lockdrop_reward
+    );
+    assert_eq!(10772001u64, lockup_query_data.unlock_timestamp);
 
-    err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawUst {
-                amount: Uint128::from(50u64),
-                duration: 15u64,
+    // let's verify the User
+    user_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.clone().to_string(),
             },
-            &[],
         )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Withdrawals not allowed");
+        .unwrap();
+    assert_eq!(vec![Coin::new(20000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(Uint128::zero(), user_resp.total_maust_share);
+    assert_eq!(
+        vec!["user1-3-0".to_string(), "user1-15-0".to_string()],
+        user_resp.lockup_position_ids
+    );
+    assert_eq!(
+        Uint128::from(999999999999u64),
+        user_resp.total_mars_incentives
+    );
+
+    // let's verify the state
+    state_resp = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::zero(), state_resp.assets[0].final_locked);
+    assert_eq!(Uint128::zero(), state_resp.assets[0].final_ma_locked);
+    assert_eq!(Uint128::from(20000u64), state_resp.assets[0].total_locked);
+    assert_eq!(Uint128::zero(), state_resp.total_maust_locked);
+    assert_eq!(Uint128::from(60000u64), state_resp.total_deposits_weight);
 }
 
 #[test]
-fn test_deposit_mars_to_auction() {
+fn test_withdraw_ust() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
-
-    // ******* Initialize Address Provider & Auction  *******
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
     let (address_provider_instance, _, _, _, mars_token_instance) =
         instantiate_red_bank(&mut app, owner.clone());
 
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(900_000_0000_000),
-        owner.to_string(),
-    );
-
-    let (auction_instance, _) = instantiate_auction_contract(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Addr::unchecked("airdrop_instance"),
-        lockdrop_instance.clone(),
-    );
-
     app.execute_contract(
         owner.clone(),
         lockdrop_instance.clone(),
@@ -1357,14 +1605,37 @@ fn test_deposit_mars_to_auction() {
             new_config: UpdateConfigMsg {
                 owner: None,
                 address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
     app.execute_contract(
         owner.clone(),
         mars_token_instance.clone(),
@@ -1382,10 +1653,16 @@ fn test_deposit_mars_to_auction() {
     // Set user balances
     app.init_bank_balance(
         &user1_address.clone(),
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(20000000u128),
-        }],
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+        ],
     )
     .unwrap();
 
@@ -1399,7 +1676,63 @@ fn test_deposit_mars_to_auction() {
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(5000u128),
+        }],
+    )
+    .unwrap();
+
+    // Check user response
+    let mut user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vec![Coin::new(5000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(vec!["user1-6-0"], user_resp.lockup_position_ids);
+
+    // ######    SUCCESS :: All UST Successfully withdrawn and lockup position removed from state     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            duration: 6u64,
+            amount: Uint128::from(5000u128),
+            lockup_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+    user_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(user_resp.total_locked.is_empty());
+    assert_eq!(true, user_resp.lockup_position_ids.is_empty());
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
             amount: Uint128::from(10000u128),
@@ -1410,7 +1743,11 @@ fn test_deposit_mars_to_auction() {
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
             amount: Uint128::from(10000u128),
@@ -1418,147 +1755,192 @@ fn test_deposit_mars_to_auction() {
     )
     .unwrap();
 
-    // ######    ERROR :: Deposit / withdraw windows not closed yet   ######
+    // ######    SUCCESS :: UST Successfully withdrawn (when withdrawals allowed)     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            duration: 6u64,
+            amount: Uint128::from(100u128),
+            lockup_id: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    let mut err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositMarsToAuction {
-                amount: Uint128::from(9000u64),
+    // Check state response
+    let mut state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(19900u64), state_resp.assets[0].total_locked);
+
+    // Check user response
+    let mut user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
             },
-            &[],
         )
-        .unwrap_err();
-    assert_eq!(
-        err.to_string(),
-        "Generic error: Deposit / withdraw windows not closed yet"
-    );
+        .unwrap();
+    assert_eq!(vec![Coin::new(19900u128, "uusd")], user_resp.total_locked);
 
-    // ######    ERROR :: Auction contract address not set   ######
+    // let's verify the Lockdrop
+    let mut lockdrop_resp: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.clone().to_string(),
+                duration: 6u64,
+            },
+        )
+        .unwrap();
+
+    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(6u64, lockup_query_data.duration);
+    assert_eq!(Uint128::from(9900u64), lockup_query_data.amount_locked);
+
+    // close deposit window. Max 50% withdrawals allowed now
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_03)
+        b.time = Timestamp::from_seconds(15_000_01)
     });
 
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.clone().to_string()),
-                ma_ust_token: None,
-                auction_contract_address: None,
-            },
-        },
-        &[],
-    )
-    .unwrap();
+    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}   ######
 
-    err = app
+    let mut err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositMarsToAuction {
+            &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(9000u64),
+                duration: 6u64,
+                lockup_id: None,
             },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Auction contract address not set"
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4950 "
     );
 
-    // ######    ERROR :: "No valid lockup positions   ######
+    // ######    SUCCESS :: Withdraw 50% successfully   ######
+
     app.execute_contract(
-        owner.clone(),
+        user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: None,
-                ma_ust_token: None,
-                auction_contract_address: Some(auction_instance.clone().to_string()),
-            },
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(4950u64),
+            duration: 6u64,
+            lockup_id: None,
         },
         &[],
     )
     .unwrap();
+    // Check state response
+    state_resp = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(14950u64), state_resp.assets[0].total_locked);
+
+    // Check user response
+    user_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vec![Coin::new(14950u128, "uusd")], user_resp.total_locked);
+
+    // let's verify the Lockdrop
+    lockdrop_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.clone().to_string(),
+                duration: 6u64,
+            },
+        )
+        .unwrap();
+
+    let lockup_query_data = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(6u64, lockup_query_data.duration);
+    assert_eq!(Uint128::from(4950u64), lockup_query_data.amount_locked);
+
+    // ######    ERROR :: Max 1 withdrawal allowed during current window   ######
 
     err = app
         .execute_contract(
-            Addr::unchecked("not_user".to_string()),
+            user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositMarsToAuction {
-                amount: Uint128::from(9000u64),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(10u64),
+                duration: 6u64,
+                lockup_id: None,
             },
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: No valid lockup positions");
+    assert_eq!(err.to_string(), "Generic error: Max 1 withdrawal allowed");
 
-    // ######    SUCCESSFULLY DELEGATE TO AUCTION   ######
+    // 50% of withdrawal window over. Max withdrawal % decreasing linearly now
 
-    // Update Lockdrop Config :: Set auction contract address
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: None,
-                ma_ust_token: None,
-                auction_contract_address: Some(auction_instance.clone().to_string()),
-            },
-        },
-        &[],
-    )
-    .unwrap();
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(16_050_01)
+    });
 
-    // Lockdrop's MARS balance (before delegation)
-    let mars_balance_lockdrop_before: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance,
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
-            },
-        )
-        .unwrap();
+    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}   ######
 
-    // Auction's MARS balance (before delegation)
-    let mars_balance_auction_before: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance,
-            &Cw20QueryMsg::Balance {
-                address: auction_instance.clone().to_string(),
+    err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(7000u64),
+                duration: 15u64,
+                lockup_id: None,
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4750 "
+    );
+
+    // ######    SUCCESS :: Withdraw some UST successfully   ######
 
-    // Delegate MARS to auction
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositMarsToAuction {
-            amount: Uint128::from(9000u64),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(750u64),
+            duration: 15u64,
+            lockup_id: None,
         },
         &[],
     )
     .unwrap();
 
     // Check state response
-    let state_resp: StateResponse = app
+    state_resp = app
         .wrap()
         .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
-    assert_eq!(Uint128::from(9000u64), state_resp.total_mars_delegated);
+    assert_eq!(Uint128::from(14200u64), state_resp.assets[0].total_locked);
 
     // Check user response
-    let user_resp: UserInfoResponse = app
+    user_resp = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
@@ -1567,88 +1949,71 @@ fn test_deposit_mars_to_auction() {
             },
         )
         .unwrap();
-    assert_eq!(
-        Uint128::from(999999999999u128),
-        user_resp.total_mars_incentives
-    );
-    assert_eq!(Uint128::from(9000u64), user_resp.delegated_mars_incentives);
+    assert_eq!(vec![Coin::new(14200u128, "uusd")], user_resp.total_locked);
 
-    // Lockdrop's MARS balance (after delegation)
-    let mars_balance_lockdrop_after: BalanceResponse = app
+    // let's verify the Lockdrop
+    lockdrop_resp = app
         .wrap()
         .query_wasm_smart(
-            &mars_token_instance,
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.clone().to_string(),
+                duration: 15u64,
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(9250u64),
+        lockdrop_resp.lockup_info.unwrap().amount_locked
+    );
 
-    // Auction's MARS balance (after delegation)
-    let mars_balance_auction_after: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance,
-            &Cw20QueryMsg::Balance {
-                address: auction_instance.clone().to_string(),
+    // // ######    ERROR :: Max 1 withdrawal allowed during current window   ######
+
+    err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(50u64),
+                duration: 15u64,
+                lockup_id: None,
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Max 1 withdrawal allowed");
 
-    // Check MARS tokens were transferred correctly from Lockdrop to Auction contract
-    assert_eq!(
-        mars_balance_lockdrop_before.balance - mars_balance_lockdrop_after.balance,
-        mars_balance_auction_after.balance - mars_balance_auction_before.balance
-    );
-    assert_eq!(
-        mars_balance_lockdrop_before.balance - mars_balance_lockdrop_after.balance,
-        Uint128::from(9000u64)
-    );
-    assert_eq!(Uint128::from(9000u64), user_resp.delegated_mars_incentives);
+    // finish withdraw period for deposit failure
 
-    // ######    ERROR :: Amount cannot exceed user's unclaimed MARS balance   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_41)
+    });
 
-    // Delegate MARS to auction
     err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositMarsToAuction {
-                amount: Uint128::from(900000_0000000000u64),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(50u64),
+                duration: 15u64,
+                lockup_id: None,
             },
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Amount cannot exceed user's unclaimed MARS balance. MARS to delegate = 9000000000000000, Max delegatable MARS = 999999990999 ");
+    assert_eq!(err.to_string(), "Generic error: Withdrawals not allowed");
 }
 
 #[test]
-fn test_deposit_ust_in_red_bank() {
+fn test_multiple_lockup_positions_per_duration() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
-
-    // ******* Initialize Address Provider & Auction  *******
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
-    let (address_provider_instance, red_bank_instance, _, _, mars_token_instance) =
+    let (address_provider_instance, _, _, _, mars_token_instance) =
         instantiate_red_bank(&mut app, owner.clone());
 
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(900_000_0000_000),
-        owner.to_string(),
-    );
-
-    let (_, _) = instantiate_auction_contract(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Addr::unchecked("airdrop_instance"),
-        lockdrop_instance.clone(),
-    );
-
     app.execute_contract(
         owner.clone(),
         lockdrop_instance.clone(),
@@ -1656,14 +2021,37 @@ fn test_deposit_ust_in_red_bank() {
             new_config: UpdateConfigMsg {
                 owner: None,
                 address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
     app.execute_contract(
         owner.clone(),
         mars_token_instance.clone(),
@@ -1681,10 +2069,16 @@ fn test_deposit_ust_in_red_bank() {
     // Set user balances
     app.init_bank_balance(
         &user1_address.clone(),
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(20000000u128),
-        }],
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+        ],
     )
     .unwrap();
 
@@ -1694,14 +2088,18 @@ fn test_deposit_ust_in_red_bank() {
         b.time = Timestamp::from_seconds(10_000_03)
     });
 
-    // ######    SUCCESS :: UST Successfully deposited     ######
+    // ######    SUCCESS :: Each deposit opens a new lockup position     ######
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
+            amount: Uint128::from(5000u128),
         }],
     )
     .unwrap();
@@ -1709,166 +2107,125 @@ fn test_deposit_ust_in_red_bank() {
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
+            amount: Uint128::from(3000u128),
         }],
     )
     .unwrap();
 
-    // ***
-    // *** Test :: Error " Only Owner can call this function" ***
-    // ***
-
-    let err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUstInRedBank {},
-            &[],
+    let mut user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
         )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Unauthorized");
-
-    // ***
-    // *** Test :: Error " maUST address should be set" ***
-    // ***
-
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.clone().to_string()),
-                ma_ust_token: None,
-                auction_contract_address: None,
-            },
-        },
-        &[],
-    )
-    .unwrap();
-
-    let err = app
-        .execute_contract(
-            owner.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUstInRedBank {},
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: maUST not set");
-
-    // ***
-    // *** Test :: Error " maUST address should be set" ***
-    // ***
+        .unwrap();
+    assert_eq!(vec![Coin::new(8000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(
+        vec!["user1-6-0", "user1-6-1"],
+        user_resp.lockup_position_ids
+    );
 
-    // Query maUST Money-market info
-    let ma_ust_market: mars_core::red_bank::Market = app
+    let lockdrop_resp: LockupInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &red_bank_instance,
-            &mars_core::red_bank::msg::QueryMsg::Market {
-                asset: mars_core::asset::Asset::Native {
-                    denom: "uusd".to_string(),
-                },
+            &lockdrop_instance,
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user1-6-1".to_string(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(3000u64),
+        lockdrop_resp.lockup_info.unwrap().amount_locked
+    );
 
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: None,
-                ma_ust_token: Some(ma_ust_market.ma_token_address.clone().to_string()),
-                auction_contract_address: None,
-            },
-        },
-        &[],
-    )
-    .unwrap();
+    // ######    ERROR :: Lockup id not provided     ######
 
     let err = app
         .execute_contract(
-            owner.clone(),
+            user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUstInRedBank {},
+            &ExecuteMsg::WithdrawUst {
+                duration: 6u64,
+                amount: Uint128::from(3000u128),
+                lockup_id: None,
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Lockdrop withdrawals haven't concluded yet"
+        "Generic error: Multiple lockup positions with duration 6, lockup id needs to be provided"
     );
 
-    // ######    SUCCESSFULLY DEPOSITED IN RED BANK ######
+    // ######    ERROR :: Lockup id doesn't match the duration     ######
 
-    // Check state response
-    let state_resp_before: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                duration: 15u64,
+                amount: Uint128::from(3000u128),
+                lockup_id: Some("user1-6-1".to_string()),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Lockup doesn't exist");
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_03)
-    });
+    // ######    SUCCESS :: Lockup position withdrawn via its id     ######
 
     app.execute_contract(
-        owner.clone(),
+        user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUstInRedBank {},
+        &ExecuteMsg::WithdrawUst {
+            duration: 6u64,
+            amount: Uint128::from(3000u128),
+            lockup_id: Some("user1-6-1".to_string()),
+        },
         &[],
     )
     .unwrap();
 
-    // Check state response
-    let state_resp_after: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(
-        state_resp_before.total_ust_locked,
-        state_resp_after.final_ust_locked
-    );
-    assert_eq!(
-        Uint128::from(20000000000u64),
-        state_resp_after.final_maust_locked
-    );
-    assert_eq!(
-        state_resp_after.final_ust_locked,
-        state_resp_before.total_ust_locked
-    );
-    assert_eq!(
-        state_resp_after.final_maust_locked,
-        state_resp_after.total_maust_locked
-    );
-    assert_eq!(Uint128::from(0u64), state_resp_after.total_mars_delegated);
-    assert_eq!(false, state_resp_after.are_claims_allowed);
-    assert_eq!(
-        Uint128::from(70000u64),
-        state_resp_after.total_deposits_weight
-    );
-    assert_eq!(Decimal::zero(), state_resp_after.xmars_rewards_index);
-
-    // maUST balance
-    let ma_ust_balance: BalanceResponse = app
+    user_resp = app
         .wrap()
         .query_wasm_smart(
-            &ma_ust_market.ma_token_address.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(state_resp_after.final_maust_locked, ma_ust_balance.balance);
+    assert_eq!(vec![Coin::new(5000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(vec!["user1-6-0"], user_resp.lockup_position_ids);
 
-    // Check user response
-    let user_resp: UserInfoResponse = app
+    // ######    SUCCESS :: Lockup ids are never reused     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(2000u128),
+        }],
+    )
+    .unwrap();
+
+    user_resp = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
@@ -1877,81 +2234,18 @@ fn test_deposit_ust_in_red_bank() {
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(20000000000u64), user_resp.total_maust_share);
-    assert_eq!(Uint128::from(20000u64), user_resp.total_ust_locked);
+    assert_eq!(vec![Coin::new(7000u128, "uusd")], user_resp.total_locked);
     assert_eq!(
-        Uint128::from(999999999999u128),
-        user_resp.total_mars_incentives
+        vec!["user1-6-0", "user1-6-2"],
+        user_resp.lockup_position_ids
     );
-
-    // ***
-    // *** Test :: Error " Already deposited" ***
-    // ***
-
-    let err = app
-        .execute_contract(
-            owner.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::DepositUstInRedBank {},
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Already deposited");
 }
 
 #[test]
-fn test_enable_claims() {
+fn test_extend_lockup_and_partial_unlock() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
-
-    // ******* Initialize Address Provider & Auction  *******
-
-    let (address_provider_instance, red_bank_instance, _, _, mars_token_instance) =
-        instantiate_red_bank(&mut app, owner.clone());
-
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(900_000_0000_000),
-        owner.to_string(),
-    );
-
-    let (auction_instance, _) = instantiate_auction_contract(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Addr::unchecked("airdrop_instance"),
-        lockdrop_instance.clone(),
-    );
-
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
-                auction_contract_address: None,
-            },
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        owner.clone(),
-        mars_token_instance.clone(),
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::from(1000000000000u64),
-            contract: lockdrop_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
     let user1_address = Addr::unchecked("user1");
 
@@ -1971,165 +2265,258 @@ fn test_enable_claims() {
         b.time = Timestamp::from_seconds(10_000_03)
     });
 
-    // ######    SUCCESS :: UST Successfully deposited     ######
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
+            amount: Uint128::from(5000u128),
         }],
     )
     .unwrap();
 
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
-    )
-    .unwrap();
+    // ######    ERROR :: Lockup duration can only be extended     ######
 
-    // Query maUST Money-market info
-    let ma_ust_market: mars_core::red_bank::Market = app
-        .wrap()
-        .query_wasm_smart(
-            &red_bank_instance,
-            &mars_core::red_bank::msg::QueryMsg::Market {
-                asset: mars_core::asset::Asset::Native {
-                    denom: "uusd".to_string(),
-                },
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ExtendLockup {
+                from_duration: 6u64,
+                to_duration: 3u64,
+                lockup_id: None,
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockup duration can only be extended"
+    );
 
-    // ***
-    // *** Test :: Error "Auction address in lockdrop not set" ***
-    // ***
+    // ######    ERROR :: Duration not supported     ######
 
     let err = app
         .execute_contract(
-            Addr::unchecked("not_auction".to_string()),
+            user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::EnableClaims {},
+            &ExecuteMsg::ExtendLockup {
+                from_duration: 6u64,
+                to_duration: 7u64,
+                lockup_id: None,
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: Auction address in lockdrop not set"
+        "Generic error: Boost not found for duration 7"
     );
 
-    // Set address provider and other addresses
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.clone().to_string()),
-                ma_ust_token: Some(ma_ust_market.ma_token_address.clone().to_string()),
-                auction_contract_address: Some(auction_instance.clone().to_string()),
-            },
-        },
-        &[],
-    )
-    .unwrap();
-
-    // ***
-    // *** Test :: Error " Only Auction contract can call this function" ***
-    // ***
+    // ######    ERROR :: Lockup doesn't exist     ######
 
     let err = app
         .execute_contract(
-            Addr::unchecked("not_auction".to_string()),
+            user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::EnableClaims {},
+            &ExecuteMsg::ExtendLockup {
+                from_duration: 3u64,
+                to_duration: 9u64,
+                lockup_id: None,
+            },
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+    assert_eq!(err.to_string(), "Generic error: Lockup doesn't exist");
 
-    // ######    SUCCESSFULLY ENABLED CLAIMS ######
+    let owner_of_resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::OwnerOf {
+                token_id: "user1-6-0".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(user1_address.to_string(), owner_of_resp.owner);
 
-    // Check state response
-    let state_resp_before: StateResponse = app
+    let nft_info_resp: NftInfoResponse = app
         .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::NftInfo {
+                token_id: "user1-6-0".to_string(),
+            },
+        )
         .unwrap();
-    assert_eq!(false, state_resp_before.are_claims_allowed);
+    assert_eq!("Mars Lockdrop Position #user1-6-0", nft_info_resp.name);
+    assert_eq!(
+        "5000 uusd locked for 6 duration units, unlocks at timestamp 5328801",
+        nft_info_resp.description
+    );
 
-    // for successful deposit
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_03)
-    });
+    // ######    SUCCESS :: Lockup position moved to the longer duration     ######
 
     app.execute_contract(
-        auction_instance.clone(),
+        user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::EnableClaims {},
+        &ExecuteMsg::ExtendLockup {
+            from_duration: 6u64,
+            to_duration: 9u64,
+            lockup_id: None,
+        },
         &[],
     )
     .unwrap();
 
-    // Check state response
-    let state_resp_after: StateResponse = app
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(vec![Coin::new(5000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(vec!["user1-6-0"], user_resp.lockup_position_ids);
+
+    // Lockup position keeps its id, and hence its token id
+    let lockdrop_resp: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user1-6-0".to_string(),
+            },
+        )
+        .unwrap();
+    let lockup_info = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(9u64, lockup_info.duration);
+    assert_eq!(Uint128::from(5000u64), lockup_info.amount_locked);
+    assert_eq!(7143201u64, lockup_info.unlock_timestamp);
+
+    let lockdrop_resp: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.to_string(),
+                duration: 9u64,
+            },
+        )
+        .unwrap();
+    assert_eq!(9u64, lockdrop_resp.lockup_info.unwrap().duration);
+
+    let owner_of_resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::OwnerOf {
+                token_id: "user1-6-0".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(user1_address.to_string(), owner_of_resp.owner);
+
+    let nft_info_resp: NftInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::NftInfo {
+                token_id: "user1-6-0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!("Mars Lockdrop Position #user1-6-0", nft_info_resp.name);
+    assert_eq!(
+        "5000 uusd locked for 9 duration units, unlocks at timestamp 7143201",
+        nft_info_resp.description
+    );
+
+    let num_tokens_resp: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::NumTokens {})
+        .unwrap();
+    assert_eq!(1u64, num_tokens_resp.count);
+
+    // Weight re-computed with the boost of the new duration
+    let state_resp: StateResponse = app
         .wrap()
         .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
-    assert_eq!(true, state_resp_after.are_claims_allowed);
+    assert_eq!(Uint128::from(15000u64), state_resp.total_deposits_weight);
 
-    // ***
-    // *** Test :: Error " Already allowed" ***
-    // ***
+    // ######    ERROR :: Lockup position can't be unlocked before its unlock timestamp     ######
 
     let err = app
         .execute_contract(
-            auction_instance.clone(),
+            user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::EnableClaims {},
+            &ExecuteMsg::PartialUnlock {
+                duration: 9u64,
+                amount: Uint128::from(1000u128),
+                lockup_id: None,
+            },
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Already allowed");
-}
+    assert_eq!(err.to_string(), "Generic error: 6143198 seconds to Unlock");
 
-#[test]
-fn test_claim_rewards_and_unlock() {
-    let mut app = mock_app();
-    let owner = Addr::unchecked("contract_owner");
-    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None, None);
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::PartialUnlock {
+                duration: 6u64,
+                amount: Uint128::from(1000u128),
+                lockup_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Invalid lockup");
 
-    // ******* Initialize Address Provider & Auction  *******
+    // ######    ERROR :: Lockup positions can't be extended once the withdrawal window is closed     ######
 
-    let (
-        address_provider_instance,
-        red_bank_instance,
-        incentives_instance,
-        xmars_token_instance,
-        mars_token_instance,
-    ) = instantiate_red_bank(&mut app, owner.clone());
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_02)
+    });
 
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(900_000_0000_000),
-        owner.to_string(),
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ExtendLockup {
+                from_duration: 9u64,
+                to_duration: 12u64,
+                lockup_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockup positions can only be extended until the withdrawal window closes"
     );
+}
 
-    let (auction_instance, _) = instantiate_auction_contract(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Addr::unchecked("airdrop_instance"),
-        lockdrop_instance.clone(),
-    );
+#[test]
+fn test_simulate_user_and_deposit() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let (address_provider_instance, _, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
 
     app.execute_contract(
         owner.clone(),
@@ -2138,14 +2525,37 @@ fn test_claim_rewards_and_unlock() {
             new_config: UpdateConfigMsg {
                 owner: None,
                 address_provider: Some(address_provider_instance.to_string()),
-                ma_ust_token: None,
+                assets: None,
                 auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
             },
         },
         &[],
     )
     .unwrap();
 
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
     app.execute_contract(
         owner.clone(),
         mars_token_instance.clone(),
@@ -2159,31 +2569,13 @@ fn test_claim_rewards_and_unlock() {
     .unwrap();
 
     let user1_address = Addr::unchecked("user1");
-    let user2_address = Addr::unchecked("user2");
-    let user3_address = Addr::unchecked("user3");
 
     // Set user balances
     app.init_bank_balance(
         &user1_address.clone(),
         vec![Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::new(51000000000u128),
-        }],
-    )
-    .unwrap();
-    app.init_bank_balance(
-        &user2_address.clone(),
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(51000000000u128),
-        }],
-    )
-    .unwrap();
-    app.init_bank_balance(
-        &user3_address.clone(),
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(51000000000u128),
+            amount: Uint128::new(20000000u128),
         }],
     )
     .unwrap();
@@ -2194,191 +2586,311 @@ fn test_claim_rewards_and_unlock() {
         b.time = Timestamp::from_seconds(10_000_03)
     });
 
-    // ######    SUCCESS :: UST Successfully deposited     ######
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
-    )
-    .unwrap();
-
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 15u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000u128),
-        }],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        user2_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 12u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(1000000u128),
-        }],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        user2_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 9u64 },
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::from(1000000u128),
+            amount: Uint128::from(5000u128),
         }],
     )
     .unwrap();
 
-    app.execute_contract(
-        user3_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 6u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(1000000u128),
-        }],
-    )
-    .unwrap();
+    // ######    SUCCESS :: Deposit simulated at the current block     ######
 
-    app.execute_contract(
-        user3_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUst { duration: 12u64 },
-        &[Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::from(10000000u128),
-        }],
-    )
-    .unwrap();
+    let deposit_resp: SimulateDepositResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::SimulateDeposit {
+                amount: Uint128::from(5000u128),
+                duration: 12u64,
+                timestamp: None,
+                denom: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(10_000_03u64, deposit_resp.timestamp);
+    assert_eq!(Decimal::from_ratio(4u64, 1u64), deposit_resp.boost);
+    assert_eq!(Uint128::from(20000u64), deposit_resp.weight);
+    // Lockdrop reward as per the total weight including the deposit's weight
+    assert_eq!(Uint128::from(666666666666u64), deposit_resp.mars_incentives);
+    assert_eq!(Uint128::from(5000u64), deposit_resp.max_withdrawable);
+    assert_eq!(8957601u64, deposit_resp.unlock_timestamp);
 
-    // *** Update Configuration ***
+    // ######    SUCCESS :: Withdrawal limit projected during the withdrawal window     ######
 
-    // Query maUST Money-market info
-    let ma_ust_market: mars_core::red_bank::Market = app
+    let deposit_resp: SimulateDepositResponse = app
         .wrap()
         .query_wasm_smart(
-            &red_bank_instance,
-            &mars_core::red_bank::msg::QueryMsg::Market {
-                asset: mars_core::asset::Asset::Native {
-                    denom: "uusd".to_string(),
-                },
+            &lockdrop_instance,
+            &QueryMsg::SimulateDeposit {
+                amount: Uint128::from(5000u128),
+                duration: 12u64,
+                timestamp: Some(16_500_01u64),
+                denom: None,
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(1250u64), deposit_resp.max_withdrawable);
 
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: UpdateConfigMsg {
-                owner: None,
-                address_provider: Some(address_provider_instance.clone().to_string()),
-                ma_ust_token: Some(ma_ust_market.ma_token_address.clone().to_string()),
-                auction_contract_address: Some(auction_instance.clone().to_string()),
-            },
-        },
-        &[],
-    )
-    .unwrap();
-
-    // ***
-    // *** Test :: Error "Invalid lockup" ***
-    // ***
+    // ######    ERROR :: Duration not supported     ######
 
     let err = app
-        .execute_contract(
-            user1_address.clone(),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::ClaimRewardsAndUnlock {
-                lockup_to_unlock_duration: Some(3u64),
+        .wrap()
+        .query_wasm_smart::<SimulateDepositResponse, _, _>(
+            &lockdrop_instance,
+            &QueryMsg::SimulateDeposit {
+                amount: Uint128::from(5000u128),
+                duration: 7u64,
+                timestamp: None,
+                denom: None,
             },
-            &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Invalid lockup");
+    assert!(err.to_string().contains("Boost not found for duration 7"));
 
-    // ***
-    // *** Test :: Error "Invalid lockup" ***
-    // ***
+    // ######    SUCCESS :: User's lockup positions projected during the withdrawal window     ######
+
+    let user_resp: SimulateUserResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::SimulateUser {
+                address: user1_address.to_string(),
+                timestamp: Some(16_500_01u64),
+            },
+        )
+        .unwrap();
+    assert_eq!(16_500_01u64, user_resp.timestamp);
+    assert_eq!(
+        Uint128::from(1000000000000u64),
+        user_resp.total_mars_incentives
+    );
+    assert_eq!(
+        Decimal::from_ratio(25u64, 100u64),
+        user_resp.withdrawal_percent_allowed
+    );
+    assert_eq!(Uint128::zero(), user_resp.pending_xmars_to_claim);
+    assert_eq!(Uint128::zero(), user_resp.estimated_mars_emissions);
+    assert_eq!(
+        vec![SimulatedLockupPosition {
+            lockup_id: "user1-6-0".to_string(),
+            denom: "uusd".to_string(),
+            duration: 6u64,
+            amount_locked: Uint128::from(5000u64),
+            weight: Uint128::from(10000u64),
+            mars_incentives: Uint128::from(1000000000000u64),
+            max_withdrawable: Uint128::from(1250u64),
+            unlock_timestamp: 5328801u64,
+            is_unlockable: false,
+        }],
+        user_resp.lockup_positions
+    );
+
+    // ######    SUCCESS :: Lockup positions unlockable once their unlock timestamp has passed     ######
+
+    let user_resp: SimulateUserResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::SimulateUser {
+                address: user1_address.to_string(),
+                timestamp: Some(5328801u64),
+            },
+        )
+        .unwrap();
+    assert_eq!(Decimal::zero(), user_resp.withdrawal_percent_allowed);
+    assert_eq!(
+        Uint128::zero(),
+        user_resp.lockup_positions[0].max_withdrawable
+    );
+    assert!(user_resp.lockup_positions[0].is_unlockable);
+}
+
+#[test]
+fn test_multiple_deposit_assets() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let update_assets_msg = |assets: Vec<AssetParams>| ExecuteMsg::UpdateConfig {
+        new_config: UpdateConfigMsg {
+            owner: None,
+            address_provider: None,
+            assets: Some(assets),
+            auction_contract_address: None,
+            early_unlock_penalty: None,
+            lockup_durations: None,
+            boost_curve: None,
+            guardian: None,
+            nuke_batch_size: None,
+            referral_incentives_rate: None,
+            withdrawal_schedule: None,
+        },
+    };
 
+    // ######    ERROR :: Invalid price weight     ######
     let err = app
         .execute_contract(
-            user1_address.clone(),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::ClaimRewardsAndUnlock {
-                lockup_to_unlock_duration: Some(6u64),
-            },
+            &update_assets_msg(vec![AssetParams {
+                denom: "uluna".to_string(),
+                price_weight: Decimal::zero(),
+            }]),
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: 4328798 seconds to Unlock");
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Invalid price weight for uluna"
+    );
 
-    // ***
-    // *** Test :: Error "No lockup to claim rewards for" ***
-    // ***
+    // ######    SUCCESS :: LUNA accepted for deposits, weighted 50 times UST     ######
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &update_assets_msg(vec![AssetParams {
+            denom: "uluna".to_string(),
+            price_weight: Decimal::from_ratio(50u64, 1u64),
+        }]),
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        vec![
+            AssetInfo {
+                denom: "uusd".to_string(),
+                ma_token: None,
+                price_weight: Decimal::one(),
+            },
+            AssetInfo {
+                denom: "uluna".to_string(),
+                ma_token: None,
+                price_weight: Decimal::from_ratio(50u64, 1u64),
+            },
+        ],
+        resp.assets
+    );
+
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(20000000u128),
+            },
+        ],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
 
+    // ######    ERROR :: Assets can't be re-weighted once the deposit window is open     ######
     let err = app
         .execute_contract(
-            Addr::unchecked("not_user".to_string()),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::ClaimRewardsAndUnlock {
-                lockup_to_unlock_duration: None,
-            },
+            &update_assets_msg(vec![AssetParams {
+                denom: "uluna".to_string(),
+                price_weight: Decimal::from_ratio(40u64, 1u64),
+            }]),
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.to_string(),
-        "Generic error: No lockup to claim rewards for"
+        "Generic error: Assets can only be added / re-weighted before the deposit window opens"
     );
 
-    // ***
-    // *** Test :: Error "Claim not allowed" ***
-    // ***
-
+    // ######    ERROR :: Denom not accepted     ######
     let err = app
         .execute_contract(
             user1_address.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::ClaimRewardsAndUnlock {
-                lockup_to_unlock_duration: None,
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
-            &[],
+            &[Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::from(1000u128),
+            }],
         )
         .unwrap_err();
-    assert_eq!(err.to_string(), "Generic error: Claim not allowed");
-
-
-    // for successful deposit
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_03)
-    });
+    assert_eq!(
+        err.to_string(),
+        "Generic error: ukrw not accepted for deposits"
+    );
 
+    // ######    SUCCESS :: Deposits in UST and LUNA     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 3u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    )
+    .unwrap();
 
-    // Enable claims
     app.execute_contract(
-        auction_instance.clone(),
+        user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::EnableClaims {},
-        &[],
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(20u128),
+        }],
     )
     .unwrap();
 
-    // ######    SUCCESS :: MARS Lockdrop rewards successfully claimed      ######
+    // 1000 UST * 1 + 20 LUNA * 50 * 2
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(3000u64), state_resp.total_deposits_weight);
+    assert_eq!(Uint128::from(1000u64), state_resp.assets[0].total_locked);
+    assert_eq!(Uint128::from(20u64), state_resp.assets[1].total_locked);
 
-    // Check user response
-    let user_resp_before: UserInfoResponse = app
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
@@ -2388,525 +2900,3350 @@ fn test_claim_rewards_and_unlock() {
         )
         .unwrap();
     assert_eq!(
-        Uint128::from(1426533522u64),
-        user_resp_before.total_mars_incentives
+        vec![Coin::new(1000u128, "uusd"), Coin::new(20u128, "uluna")],
+        user_resp.total_locked
     );
 
-    app.execute_contract(
-        user1_address.clone(),
-        lockdrop_instance.clone(),
-        &ExecuteMsg::ClaimRewardsAndUnlock {
-            lockup_to_unlock_duration: None,
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Check user response
-    let user_resp_after: UserInfoResponse = app
+    let lockdrop_resp: LockupInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user1-6-0".to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        user_resp_after.total_mars_incentives,
-        user_resp_before.total_mars_incentives
-    );
-    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
-    assert_eq!(
-        user_resp_before.pending_xmars_to_claim,
-        user_resp_after.pending_xmars_to_claim
-    );
-    assert_eq!(Uint128::zero(), user_resp_after.pending_xmars_to_claim);
+    let lockup_info = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!("uluna".to_string(), lockup_info.denom);
+    assert_eq!(Uint128::from(20u64), lockup_info.amount_locked);
 
-    let user1_mars_balance: BalanceResponse = app
+    let simulate_resp: SimulateWeightResponse = app
         .wrap()
         .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user1_address.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(20u64),
+                duration: 6u64,
+                denom: Some("uluna".to_string()),
             },
         )
         .unwrap();
-    assert_eq!(
-        user_resp_before.total_mars_incentives,
-        user1_mars_balance.balance
-    );
-
-    // ######    SUCCESS :: MARS Lockdrop rewards (+ xMARS) successfully claimed (After UST is deposited in Red Bank)     ######
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_03)
-    });
-
-    // mint MARS  to Incentives Contract
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(351199080000000),
-        incentives_instance.clone().to_string(),
-    );
-
-    // Set Incentives
-    app.execute_contract(
-        owner.clone(),
-        incentives_instance.clone(),
-        &mars_core::incentives::msg::ExecuteMsg::SetAssetIncentive {
-            ma_token_address: ma_ust_market.ma_token_address.clone().to_string(),
-            emission_per_second: Uint128::from(10000000u64),
-        },
-        &[],
-    )
-    .unwrap();
+    assert_eq!(Uint128::from(2000u64), simulate_resp.weight);
 
-    // Deposit UST in Red Bank
+    // ######    SUCCESS :: LUNA withdrawn from its lockup position     ######
     app.execute_contract(
-        owner.clone(),
+        user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::DepositUstInRedBank {},
+        &ExecuteMsg::WithdrawUst {
+            duration: 6u64,
+            amount: Uint128::from(5u128),
+            lockup_id: None,
+        },
         &[],
     )
     .unwrap();
 
-    // Check incentives
-    let asset_incentive_response: mars_core::incentives::AssetIncentiveResponse = app
+    let state_resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &incentives_instance,
-            &mars_core::incentives::msg::QueryMsg::AssetIncentive {
-                ma_token_address: ma_ust_market.ma_token_address.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
+    assert_eq!(Uint128::from(2500u64), state_resp.total_deposits_weight);
+    assert_eq!(Uint128::from(15u64), state_resp.assets[1].total_locked);
+}
 
-    assert_eq!(
-        Uint128::from(10000000u64),
-        asset_incentive_response
-            .asset_incentive
-            .clone()
-            .unwrap()
-            .emission_per_second
-    );
-    assert_eq!(
-        mars_core_dev::math::decimal::Decimal::zero(),
-        asset_incentive_response
-            .asset_incentive
-            .clone()
-            .unwrap()
-            .index
-    );
-    assert_eq!(
-        1700003u64,
-        asset_incentive_response
-            .asset_incentive
-            .clone()
-            .unwrap()
-            .last_updated
-    );
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(17_000_93)
-    });
+#[test]
+fn test_deposit_on_behalf_and_referral() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let update_referral_msg = |referral_incentives_rate: Decimal| ExecuteMsg::UpdateConfig {
+        new_config: UpdateConfigMsg {
+            owner: None,
+            address_provider: None,
+            assets: None,
+            auction_contract_address: None,
+            early_unlock_penalty: None,
+            lockup_durations: None,
+            boost_curve: None,
+            guardian: None,
+            nuke_batch_size: None,
+            referral_incentives_rate: Some(referral_incentives_rate),
+            withdrawal_schedule: None,
+        },
+    };
 
-    // Check user response
-    let user_resp_before: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
+    // ######    ERROR :: Invalid referral incentives rate     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_referral_msg(Decimal::one()),
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(1382488u64),
-        user_resp_before.pending_xmars_to_claim
+        err.to_string(),
+        "Generic error: Invalid referral incentives rate"
     );
 
-    // xMars to be claimed (Lockdrop)
-    let xmars_pending: Uint128 = app
+    // ######    SUCCESS :: 10% of the lockdrop incentives allocated to the referrers     ######
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &update_referral_msg(Decimal::percent(10)),
+        &[],
+    )
+    .unwrap();
+
+    let resp: ConfigResponse = app
         .wrap()
-        .query_wasm_smart(
-            &incentives_instance.clone().to_string(),
-            &mars_core::incentives::msg::QueryMsg::UserUnclaimedRewards {
-                user_address: lockdrop_instance.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::Config {})
         .unwrap();
-    assert_eq!(Uint128::from(899999999u64), xmars_pending);
+    assert_eq!(Decimal::percent(10), resp.referral_incentives_rate);
 
-    // Check user response : before
-    let user_resp_before: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let partner_address = Addr::unchecked("partner");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+    app.init_bank_balance(
+        &partner_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    ERROR :: Referral incentives rate can't be updated once the deposit window is open     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &update_referral_msg(Decimal::percent(5)),
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(1382488u64),
-        user_resp_before.pending_xmars_to_claim
+        err.to_string(),
+        "Generic error: Referral incentives rate can only be updated before the deposit window opens"
     );
 
-    // Check user's MARS Balance
-    let user1_mars_balance_before: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user1_address.clone().to_string(),
+    // ######    ERROR :: Depositors can't refer themselves     ######
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: Some(user1_address.to_string()),
             },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000u128),
+            }],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Cannot refer yourself");
 
-    // claim rewards : xMars (Mars already claimed)
+    // ######    SUCCESS :: Deposit referred by the partner     ######
     app.execute_contract(
         user1_address.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::ClaimRewardsAndUnlock {
-            lockup_to_unlock_duration: None,
+        &ExecuteMsg::DepositUst {
+            duration: 3u64,
+            recipient: None,
+            referrer: Some(partner_address.to_string()),
         },
-        &[],
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
     )
     .unwrap();
 
-    // Check user response : after
-    let user_resp_after: UserInfoResponse = app
+    // ######    SUCCESS :: Partner deposits on behalf of user2     ######
+    app.execute_contract(
+        partner_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: Some(user2_address.to_string()),
+            referrer: Some(partner_address.to_string()),
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(2000u128),
+        }],
+    )
+    .unwrap();
+
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
             &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+                address: user2_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
+    assert_eq!(vec![Coin::new(2000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(vec!["user2-6-0"], user_resp.lockup_position_ids);
 
-    // Check user's xMARS Balance
-    let user1_xmars_balance: BalanceResponse = app
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &xmars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user1_address.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: partner_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        user_resp_before.pending_xmars_to_claim,
-        user1_xmars_balance.balance
-    );
+    assert!(user_resp.lockup_position_ids.is_empty());
 
-    // Check lockdrop contract's xMARS Balance
-    let lockdrop_xmars_balance: BalanceResponse = app
+    let lockdrop_resp: LockupInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &xmars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user2-6-0".to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        xmars_pending,
-        lockdrop_xmars_balance.balance + user_resp_before.pending_xmars_to_claim
-    );
+    let lockup_info = lockdrop_resp.lockup_info.unwrap();
+    assert_eq!(user2_address, lockup_info.owner);
+    assert_eq!(Some(partner_address.clone()), lockup_info.referrer);
 
-    // Check user's MARS Balance
-    let user1_mars_balance_after: BalanceResponse = app
+    // 1000 UST * 1 + 2000 UST * 2
+    let referrer_resp: ReferrerResponse = app
         .wrap()
         .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user1_address.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::Referrer {
+                address: partner_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(5000u64), referrer_resp.referred_weight);
+    assert_eq!(2u64, referrer_resp.deposits_referred);
     assert_eq!(
-        user1_mars_balance_before.balance,
-        user1_mars_balance_after.balance
+        vec![Coin::new(3000u128, "uusd")],
+        referrer_resp.total_referred
     );
+    assert_eq!(false, referrer_resp.is_claimed);
 
-    // ######    SUCCESS :: Unlock Position : Claim MARS Lockdrop rewards (+ xMARS) successfully   ######
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(16820001)
-    });
+    // ######    SUCCESS :: Withdrawn weight is no longer credited to the referrer     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            duration: 3u64,
+            amount: Uint128::from(400u128),
+            lockup_id: None,
+        },
+        &[],
+    )
+    .unwrap();
 
-    // Check user response : before
-    let user_resp_before: UserInfoResponse = app
+    let referrer_resp: ReferrerResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user2_address.to_string(),
+            &QueryMsg::Referrer {
+                address: partner_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(4600u64), referrer_resp.referred_weight);
     assert_eq!(
-        Uint128::from(23225803379404u64),
-        user_resp_before.pending_xmars_to_claim
-    );
-    assert_eq!(
-        Uint128::from(142653352353u64),
-        user_resp_before.total_mars_incentives
+        vec![Coin::new(2600u128, "uusd")],
+        referrer_resp.total_referred
     );
-    assert_eq!(false, user_resp_before.is_lockdrop_claimed);
 
-    // Check user's MARS Balance
-    let user2_mars_balance_before: BalanceResponse = app
+    let state_resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
+    assert_eq!(Uint128::from(4600u64), state_resp.total_deposits_weight);
+    assert_eq!(Uint128::from(4600u64), state_resp.total_referred_weight);
 
-    // Check user's xMARS Balance
-    let user2_xmars_balance_before: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone().to_string(),
-            },
+    // ######    ERROR :: Claims not allowed     ######
+    let err = app
+        .execute_contract(
+            partner_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimReferralRewards {},
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Claim not allowed");
+}
 
-    // Check lockup
-    let lockup_response_before: LockupInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance.clone().to_string(),
-            &QueryMsg::LockupInfo {
-                address: user2_address.clone().to_string(),
-                duration: 9u64,
-            },
-        )
-        .unwrap();
+#[test]
+fn test_deposit_mars_to_auction() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
 
-    let lockup_before = lockup_response_before.lockup_info.unwrap();
+    // ******* Initialize Address Provider & Auction  *******
+
+    let (address_provider_instance, _, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    let (auction_instance, _) = instantiate_auction_contract(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Addr::unchecked("airdrop_instance"),
+        lockdrop_instance.clone(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Deposit / withdraw windows not closed yet   ######
+
+    let mut err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositMarsToAuction {
+                amount: Uint128::from(9000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Deposit / withdraw windows not closed yet"
+    );
+
+    // ######    ERROR :: Auction contract address not set   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.clone().to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositMarsToAuction {
+                amount: Uint128::from(9000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Auction contract address not set"
+    );
+
+    // ######    ERROR :: "No valid lockup positions   ######
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    err = app
+        .execute_contract(
+            Addr::unchecked("not_user".to_string()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositMarsToAuction {
+                amount: Uint128::from(9000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: No valid lockup positions");
+
+    // ######    SUCCESSFULLY DELEGATE TO AUCTION   ######
+
+    // Update Lockdrop Config :: Set auction contract address
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Lockdrop's MARS balance (before delegation)
+    let mars_balance_lockdrop_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction's MARS balance (before delegation)
+    let mars_balance_auction_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: auction_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Delegate MARS to auction
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositMarsToAuction {
+            amount: Uint128::from(9000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check state response
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(9000u64), state_resp.total_mars_delegated);
+
+    // Check user response
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(999999999999u128),
+        user_resp.total_mars_incentives
+    );
+    assert_eq!(Uint128::from(9000u64), user_resp.delegated_mars_incentives);
+
+    // Lockdrop's MARS balance (after delegation)
+    let mars_balance_lockdrop_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction's MARS balance (after delegation)
+    let mars_balance_auction_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: auction_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check MARS tokens were transferred correctly from Lockdrop to Auction contract
+    assert_eq!(
+        mars_balance_lockdrop_before.balance - mars_balance_lockdrop_after.balance,
+        mars_balance_auction_after.balance - mars_balance_auction_before.balance
+    );
+    assert_eq!(
+        mars_balance_lockdrop_before.balance - mars_balance_lockdrop_after.balance,
+        Uint128::from(9000u64)
+    );
+    assert_eq!(Uint128::from(9000u64), user_resp.delegated_mars_incentives);
+
+    // ######    ERROR :: Amount cannot exceed user's unclaimed MARS balance   ######
+
+    // Delegate MARS to auction
+    err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositMarsToAuction {
+                amount: Uint128::from(900000_0000000000u64),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Amount cannot exceed user's unclaimed MARS balance. MARS to delegate = 9000000000000000, Max delegatable MARS = 999999990999 ");
+}
+
+#[test]
+fn test_deposit_ust_in_red_bank() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    // ******* Initialize Address Provider & Auction  *******
+
+    let (address_provider_instance, red_bank_instance, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    let (_, _) = instantiate_auction_contract(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Addr::unchecked("airdrop_instance"),
+        lockdrop_instance.clone(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // ***
+    // *** Test :: Error " Only Owner can call this function" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUstInRedBank {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+
+    // ***
+    // *** Test :: Error " Mars addresses should be resolved" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUstInRedBank {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Mars addresses not set");
+
+    // Mars addresses are resolved once the address provider is set
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.clone().to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config_resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        red_bank_instance,
+        config_resp.mars_addresses.unwrap().red_bank
+    );
+
+    // Query maUST Money-market info
+    let ma_ust_market: mars_core::red_bank::Market = app
+        .wrap()
+        .query_wasm_smart(
+            &red_bank_instance,
+            &mars_core::red_bank::msg::QueryMsg::Market {
+                asset: mars_core::asset::Asset::Native {
+                    denom: "uusd".to_string(),
+                },
+            },
+        )
+        .unwrap();
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::RefreshAddresses {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUstInRedBank {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockdrop withdrawals haven't concluded yet"
+    );
+
+    // ######    SUCCESSFULLY DEPOSITED IN RED BANK ######
+
+    // Check state response
+    let state_resp_before: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUstInRedBank {},
+        &[],
+    )
+    .unwrap();
+
+    // Check state response
+    let state_resp_after: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        state_resp_before.assets[0].total_locked,
+        state_resp_after.assets[0].final_locked
+    );
+    assert_eq!(
+        Uint128::from(20000000000u64),
+        state_resp_after.assets[0].final_ma_locked
+    );
+    assert_eq!(
+        state_resp_after.assets[0].final_locked,
+        state_resp_before.assets[0].total_locked
+    );
+    assert_eq!(
+        state_resp_after.assets[0].final_ma_locked,
+        state_resp_after.total_maust_locked
+    );
+    assert_eq!(Uint128::from(0u64), state_resp_after.total_mars_delegated);
+    assert_eq!(false, state_resp_after.are_claims_allowed);
+    assert_eq!(
+        Uint128::from(70000u64),
+        state_resp_after.total_deposits_weight
+    );
+    assert_eq!(Decimal::zero(), state_resp_after.xmars_rewards_index);
+
+    // maUST balance
+    let ma_ust_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &ma_ust_market.ma_token_address.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        state_resp_after.assets[0].final_ma_locked,
+        ma_ust_balance.balance
+    );
+
+    // maUST address resolved from the Red Bank market
+    let config_resp: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        Some(ma_ust_market.ma_token_address.clone()),
+        config_resp.assets[0].ma_token
+    );
+    assert_eq!(
+        red_bank_instance,
+        config_resp.mars_addresses.unwrap().red_bank
+    );
+
+    // Check user response
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(20000000000u64), user_resp.total_maust_share);
+    assert_eq!(vec![Coin::new(20000u128, "uusd")], user_resp.total_locked);
+    assert_eq!(
+        Uint128::from(999999999999u128),
+        user_resp.total_mars_incentives
+    );
+
+    // ***
+    // *** Test :: Error " Already deposited" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUstInRedBank {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Already deposited");
+}
+
+#[test]
+fn test_enable_claims() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    // ******* Initialize Address Provider & Auction  *******
+
+    let (address_provider_instance, red_bank_instance, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    let (auction_instance, _) = instantiate_auction_contract(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Addr::unchecked("airdrop_instance"),
+        lockdrop_instance.clone(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    // Query maUST Money-market info
+    let ma_ust_market: mars_core::red_bank::Market = app
+        .wrap()
+        .query_wasm_smart(
+            &red_bank_instance,
+            &mars_core::red_bank::msg::QueryMsg::Market {
+                asset: mars_core::asset::Asset::Native {
+                    denom: "uusd".to_string(),
+                },
+            },
+        )
+        .unwrap();
+
+    // ***
+    // *** Test :: Error "Auction address in lockdrop not set" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_auction".to_string()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EnableClaims {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Auction address in lockdrop not set"
+    );
+
+    // Set address provider and other addresses
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.clone().to_string()),
+                assets: None,
+                auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    // ***
+    // *** Test :: Error " Only Auction contract can call this function" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_auction".to_string()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EnableClaims {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESSFULLY ENABLED CLAIMS ######
+
+    // Check state response
+    let state_resp_before: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(false, state_resp_before.are_claims_allowed);
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    app.execute_contract(
+        auction_instance.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EnableClaims {},
+        &[],
+    )
+    .unwrap();
+
+    // Check state response
+    let state_resp_after: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(true, state_resp_after.are_claims_allowed);
+
+    // ***
+    // *** Test :: Error " Already allowed" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            auction_instance.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EnableClaims {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Already allowed");
+}
+
+#[test]
+fn test_claim_rewards_and_unlock() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    // ******* Initialize Address Provider & Auction  *******
+
+    let (
+        address_provider_instance,
+        red_bank_instance,
+        incentives_instance,
+        xmars_token_instance,
+        mars_token_instance,
+    ) = instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    let (auction_instance, _) = instantiate_auction_contract(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Addr::unchecked("airdrop_instance"),
+        lockdrop_instance.clone(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+    app.init_bank_balance(
+        &user2_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+    app.init_bank_balance(
+        &user3_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 12u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 9u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user3_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user3_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 12u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000000u128),
+        }],
+    )
+    .unwrap();
+
+    // *** Update Configuration ***
+
+    // Query maUST Money-market info
+    let ma_ust_market: mars_core::red_bank::Market = app
+        .wrap()
+        .query_wasm_smart(
+            &red_bank_instance,
+            &mars_core::red_bank::msg::QueryMsg::Market {
+                asset: mars_core::asset::Asset::Native {
+                    denom: "uusd".to_string(),
+                },
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.clone().to_string()),
+                assets: None,
+                auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    // ***
+    // *** Test :: Error "Invalid lockup" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndUnlock {
+                lockup_to_unlock_duration: Some(3u64),
+                lockup_to_unlock_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Invalid lockup");
+
+    // ***
+    // *** Test :: Error "Invalid lockup" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndUnlock {
+                lockup_to_unlock_duration: Some(6u64),
+                lockup_to_unlock_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: 4328798 seconds to Unlock");
+
+    // ***
+    // *** Test :: Error "No lockup to claim rewards for" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_user".to_string()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndUnlock {
+                lockup_to_unlock_duration: None,
+                lockup_to_unlock_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: No lockup to claim rewards for"
+    );
+
+    // ***
+    // *** Test :: Error "Claim not allowed" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndUnlock {
+                lockup_to_unlock_duration: None,
+                lockup_to_unlock_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Claim not allowed");
+
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+
+    // Enable claims
+    app.execute_contract(
+        auction_instance.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EnableClaims {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    SUCCESS :: MARS Lockdrop rewards successfully claimed      ######
+
+    // Check user response
+    let user_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(1426533522u64),
+        user_resp_before.total_mars_incentives
+    );
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            lockup_to_unlock_duration: None,
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check user response
+    let user_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user_resp_after.total_mars_incentives,
+        user_resp_before.total_mars_incentives
+    );
+    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
+    assert_eq!(
+        user_resp_before.pending_xmars_to_claim,
+        user_resp_after.pending_xmars_to_claim
+    );
+    assert_eq!(Uint128::zero(), user_resp_after.pending_xmars_to_claim);
+
+    let user1_mars_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user1_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user_resp_before.total_mars_incentives,
+        user1_mars_balance.balance
+    );
+
+    // ######    SUCCESS :: MARS Lockdrop rewards (+ xMARS) successfully claimed (After UST is deposited in Red Bank)     ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    // mint MARS  to Incentives Contract
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(351199080000000),
+        incentives_instance.clone().to_string(),
+    );
+
+    // Set Incentives
+    app.execute_contract(
+        owner.clone(),
+        incentives_instance.clone(),
+        &mars_core::incentives::msg::ExecuteMsg::SetAssetIncentive {
+            ma_token_address: ma_ust_market.ma_token_address.clone().to_string(),
+            emission_per_second: Uint128::from(10000000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Deposit UST in Red Bank
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUstInRedBank {},
+        &[],
+    )
+    .unwrap();
+
+    // Check incentives
+    let asset_incentive_response: mars_core::incentives::AssetIncentiveResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &incentives_instance,
+            &mars_core::incentives::msg::QueryMsg::AssetIncentive {
+                ma_token_address: ma_ust_market.ma_token_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        Uint128::from(10000000u64),
+        asset_incentive_response
+            .asset_incentive
+            .clone()
+            .unwrap()
+            .emission_per_second
+    );
+    assert_eq!(
+        mars_core_dev::math::decimal::Decimal::zero(),
+        asset_incentive_response
+            .asset_incentive
+            .clone()
+            .unwrap()
+            .index
+    );
+    assert_eq!(
+        1700003u64,
+        asset_incentive_response
+            .asset_incentive
+            .clone()
+            .unwrap()
+            .last_updated
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_93)
+    });
+
+    // Check user response
+    let user_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(1382488u64),
+        user_resp_before.pending_xmars_to_claim
+    );
+
+    // xMars to be claimed (Lockdrop)
+    let xmars_pending: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &incentives_instance.clone().to_string(),
+            &mars_core::incentives::msg::QueryMsg::UserUnclaimedRewards {
+                user_address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(899999999u64), xmars_pending);
+
+    // Check user response : before
+    let user_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(1382488u64),
+        user_resp_before.pending_xmars_to_claim
+    );
+
+    // Check user's MARS Balance
+    let user1_mars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user1_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // claim rewards : xMars (Mars already claimed)
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            lockup_to_unlock_duration: None,
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check user response : after
+    let user_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
+
+    // Check user's xMARS Balance
+    let user1_xmars_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xmars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user1_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user_resp_before.pending_xmars_to_claim,
+        user1_xmars_balance.balance
+    );
+
+    // Check lockdrop contract's xMARS Balance
+    let lockdrop_xmars_balance: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xmars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        xmars_pending,
+        lockdrop_xmars_balance.balance + user_resp_before.pending_xmars_to_claim
+    );
+
+    // Check user's MARS Balance
+    let user1_mars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user1_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1_mars_balance_before.balance,
+        user1_mars_balance_after.balance
+    );
+
+    // ######    SUCCESS :: Unlock Position : Claim MARS Lockdrop rewards (+ xMARS) successfully   ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(16820001)
+    });
+
+    // Check user response : before
+    let user_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(23225803379404u64),
+        user_resp_before.pending_xmars_to_claim
+    );
+    assert_eq!(
+        Uint128::from(142653352353u64),
+        user_resp_before.total_mars_incentives
+    );
+    assert_eq!(false, user_resp_before.is_lockdrop_claimed);
+
+    // Check user's MARS Balance
+    let user2_mars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check user's xMARS Balance
+    let user2_xmars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check lockup
+    let lockup_response_before: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance.clone().to_string(),
+            &QueryMsg::LockupInfo {
+                address: user2_address.clone().to_string(),
+                duration: 9u64,
+            },
+        )
+        .unwrap();
+
+    let lockup_before = lockup_response_before.lockup_info.unwrap();
+
+    // claim rewards & Unlock Position : xMars
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            lockup_to_unlock_duration: Some(9u64),
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check user response : after
+    let user_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
+    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
+    assert_eq!(
+        vec!["user2-12-0".to_string()],
+        user_resp_after.lockup_position_ids
+    );
+
+    // Check user's xMARS Balance
+    let user2_xmars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xmars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    let user2_mars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user2_xmars_balance_before.balance + user_resp_before.pending_xmars_to_claim,
+        user2_xmars_balance_after.balance
+    );
+    assert_eq!(
+        user2_mars_balance_before.balance + user_resp_before.total_mars_incentives,
+        user2_mars_balance_after.balance
+    );
+
+    // Check lockup
+    let lockup_after_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance.clone().to_string(),
+            &QueryMsg::LockupInfo {
+                address: user2_address.clone().to_string(),
+                duration: 9u64,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(lockup_after_response.lockup_info, None);
+
+    let user2_ma_token_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &ma_ust_market.ma_token_address.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        lockup_before.ma_token_balance,
+        user2_ma_token_balance_after.balance
+    );
+
+    // ######    SUCCESS :: Forcefully Unlock Position : Claim MARS Lockdrop rewards (+ xMARS) successfully   ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(16820003)
+    });
+
+    // mint MARS for to User-3 Contract
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        user3_address.clone().to_string(),
+    );
+    // Check user response : before
+    let user_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(127741935483858u64),
+        user_resp_before.pending_xmars_to_claim
+    );
+    assert_eq!(
+        Uint128::from(855920114122u64),
+        user_resp_before.total_mars_incentives
+    );
+    assert_eq!(false, user_resp_before.is_lockdrop_claimed);
+
+    // Check user's MARS Balance
+    let user3_mars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user3_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Check user's xMARS Balance
+    let user3_xmars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xmars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user3_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Increase allowance
+    app.execute_contract(
+        user3_address.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: lockdrop_instance.clone().to_string(),
+            amount: Uint128::from(1000000000000000000u64),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // claim rewards & Forcefully Unlock Position
+    app.execute_contract(
+        user3_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            lockup_to_unlock_duration: Some(12u64),
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check user response : after
+    let user_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
+    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
+    assert_eq!(
+        vec!["user3-6-0".to_string()],
+        user_resp_after.lockup_position_ids
+    );
+
+    // Check user's xMARS Balance
+    let user3_xmars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &xmars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user3_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    let user3_mars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user3_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user3_xmars_balance_before.balance + user_resp_before.pending_xmars_to_claim,
+        user3_xmars_balance_after.balance
+    );
+    assert_eq!(
+        user3_mars_balance_before.balance + user_resp_before.total_mars_incentives,
+        user3_mars_balance_after.balance
+    );
+}
+
+#[test]
+fn test_force_unlock_lockup_position() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    // ******* Initialize Address Provider & Auction  *******
+
+    let (address_provider_instance, red_bank_instance, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    let (auction_instance, _) = instantiate_auction_contract(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Addr::unchecked("airdrop_instance"),
+        lockdrop_instance.clone(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+    app.init_bank_balance(
+        &user2_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+    app.init_bank_balance(
+        &user3_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(51000000000u128),
+        }],
+    )
+    .unwrap();
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 15u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 12u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 9u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user3_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        user3_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 12u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000000u128),
+        }],
+    )
+    .unwrap();
+
+    // *** Update Configuration ***
+
+    // Query maUST Money-market info
+    let ma_ust_market: mars_core::red_bank::Market = app
+        .wrap()
+        .query_wasm_smart(
+            &red_bank_instance,
+            &mars_core::red_bank::msg::QueryMsg::Market {
+                asset: mars_core::asset::Asset::Native {
+                    denom: "uusd".to_string(),
+                },
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.clone().to_string()),
+                assets: None,
+                auction_contract_address: Some(auction_instance.clone().to_string()),
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    // Half of the position's lockdrop reward needs to be returned to force unlock it
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: Some(EarlyUnlockPenalty {
+                    penalty_rate: Decimal::from_ratio(1u64, 2u64),
+                    treasury_address: None,
+                }),
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    // Enable claims
+    app.execute_contract(
+        auction_instance.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EnableClaims {},
+        &[],
+    )
+    .unwrap();
+
+    // Deposit UST in Red Bank
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUstInRedBank {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Penalty can't be updated once claims are enabled     ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    address_provider: None,
+                    assets: None,
+                    auction_contract_address: None,
+                    early_unlock_penalty: Some(EarlyUnlockPenalty {
+                        penalty_rate: Decimal::one(),
+                        treasury_address: None,
+                    }),
+                    lockup_durations: None,
+                    boost_curve: None,
+                    guardian: None,
+                    nuke_batch_size: None,
+                    referral_incentives_rate: None,
+                    withdrawal_schedule: None,
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Early unlock penalty can only be updated before claims are enabled"
+    );
+
+    // ######    ERROR :: Lockup position can't be unlocked before its unlock timestamp without penalty     ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndUnlock {
+                unlock_for_addr: None,
+                lockup_to_unlock_duration: Some(6u64),
+                lockup_to_unlock_id: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: 3628798 seconds to Unlock");
+
+    // User-1 claims the lockdrop rewards, used to force unlock the position
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            unlock_for_addr: None,
+            lockup_to_unlock_duration: None,
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let lockup_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user1-6-0".to_string(),
+            },
+        )
+        .unwrap();
+    let mars_to_return =
+        lockup_response.lockup_info.unwrap().lockdrop_reward * Decimal::from_ratio(1u64, 2u64);
+
+    // ######    ERROR :: MARS returned doesn't match the penalty     ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            mars_token_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                amount: mars_to_return - Uint128::from(1u64),
+                contract: lockdrop_instance.to_string(),
+                msg: to_binary(&Cw20HookMsg::ForceUnlock {
+                    lockup_id: "user1-6-0".to_string(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "Generic error: {} MARS needs to be returned to force unlock the position",
+            mars_to_return
+        )
+    );
+
+    // ######    ERROR :: Only the owner can force unlock the position     ######
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        mars_to_return,
+        user2_address.to_string(),
+    );
+
+    let err = app
+        .execute_contract(
+            user2_address.clone(),
+            mars_token_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                amount: mars_to_return,
+                contract: lockdrop_instance.to_string(),
+                msg: to_binary(&Cw20HookMsg::ForceUnlock {
+                    lockup_id: "user1-6-0".to_string(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Lockup position forcefully unlocked, forfeited MARS redistributed     ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: mars_to_return,
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::ForceUnlock {
+                lockup_id: "user1-6-0".to_string(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let lockup_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfoWithId {
+                lockup_id: "user1-6-0".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lockup_response.lockup_info, None);
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(mars_to_return, state_resp.total_mars_forfeited);
+    assert_eq!(
+        Decimal::from_ratio(mars_to_return, state_resp.total_maust_locked),
+        state_resp.forfeited_mars_index
+    );
+
+    // User-2 claims its share of the forfeited MARS along-with the lockdrop rewards
+    let user2_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user2_resp_before.total_maust_share * state_resp.forfeited_mars_index,
+        user2_resp_before.pending_forfeited_mars_to_claim
+    );
+
+    let user2_mars_balance_before: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndUnlock {
+            unlock_for_addr: None,
+            lockup_to_unlock_duration: None,
+            lockup_to_unlock_id: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user2_mars_balance_after: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &mars_token_instance.clone().to_string(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user2_mars_balance_before.balance
+            + user2_resp_before.total_mars_incentives
+            + user2_resp_before.pending_forfeited_mars_to_claim,
+        user2_mars_balance_after.balance
+    );
+
+    let user2_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::zero(),
+        user2_resp_after.pending_forfeited_mars_to_claim
+    );
+}
+
+#[test]
+fn test_transfer_lockup_position() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    // ******* Initialize Address Provider *******
+
+    let (address_provider_instance, _, _, _, mars_token_instance) =
+        instantiate_red_bank(&mut app, owner.clone());
+
+    mint_some_mars(
+        &mut app,
+        owner.clone(),
+        mars_token_instance.clone(),
+        Uint128::new(900_000_0000_000),
+        owner.to_string(),
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: Some(address_provider_instance.to_string()),
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: None,
+                nuke_batch_size: None,
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::RefreshAddresses {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        mars_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::from(1000000000000u64),
+            contract: lockdrop_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseMarsIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
+
+    // Set user balances
+    for user_address in [&user1_address, &user2_address, &user3_address] {
+        app.init_bank_balance(
+            user_address,
+            vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(51000000000u128),
+            }],
+        )
+        .unwrap();
+    }
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    for (user_address, duration, amount) in [
+        (&user1_address, 6u64, 10000u128),
+        (&user1_address, 15u64, 10000u128),
+        (&user2_address, 9u64, 1000000u128),
+        (&user3_address, 6u64, 1000000u128),
+    ] {
+        app.execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration,
+                recipient: None,
+                referrer: None,
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(amount),
+            }],
+        )
+        .unwrap();
+    }
+
+    // ######    ERROR :: Deposit / withdrawal windows not closed     ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: user2_address.to_string(),
+                token_id: "user1-6-0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockup positions can only be transferred once the deposit / withdrawal windows are closed"
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(17_000_03)
+    });
+
+    // ######    ERROR :: Sender is neither the owner nor approved     ######
+
+    let err = app
+        .execute_contract(
+            user3_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: user2_address.to_string(),
+                token_id: "user1-6-0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+
+    let lockup_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.to_string(),
+                duration: 6u64,
+            },
+        )
+        .unwrap();
+    let lockup_info = lockup_response.lockup_info.unwrap();
+    assert_eq!(user1_address, lockup_info.owner);
+
+    let user1_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    let user2_resp_before: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // ######    SUCCESS :: Lockup position transferred by an approved spender     ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::Approve {
+            spender: user3_address.to_string(),
+            token_id: "user1-6-0".to_string(),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res = app
+        .execute_contract(
+            user3_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: user2_address.to_string(),
+                token_id: "user1-6-0".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        res.events[1].attributes[1],
+        attr("action", "lockdrop::ExecuteMsg::TransferNft")
+    );
+    assert_eq!(res.events[1].attributes[2], attr("sender", "user3"));
+    assert_eq!(res.events[1].attributes[3], attr("recipient", "user2"));
+    assert_eq!(res.events[1].attributes[4], attr("token_id", "user1-6-0"));
+
+    // Check ownership, approvals are cleared
+    let owner_of_response: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::OwnerOf {
+                token_id: "user1-6-0".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_of_response.owner, user2_address.to_string());
+    assert!(owner_of_response.approvals.is_empty());
+
+    // Position is now returned for the recipient
+    let lockup_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user1_address.to_string(),
+                duration: 6u64,
+            },
+        )
+        .unwrap();
+    assert_eq!(lockup_response.lockup_info, None);
+
+    let lockup_response: LockupInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockupInfo {
+                address: user2_address.to_string(),
+                duration: 6u64,
+            },
+        )
+        .unwrap();
+    let transferred_lockup_info = lockup_response.lockup_info.unwrap();
+    assert_eq!(user2_address, transferred_lockup_info.owner);
+    assert_eq!(
+        lockup_info.amount_locked,
+        transferred_lockup_info.amount_locked
+    );
+    assert_eq!(
+        lockup_info.lockdrop_reward,
+        transferred_lockup_info.lockdrop_reward
+    );
+
+    // UST and MARS incentives of the position are moved to the recipient
+    let user1_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![Coin::new(10000u128, "uusd")],
+        user1_resp_after.total_locked
+    );
+    assert_eq!(
+        user1_resp_before.total_mars_incentives - lockup_info.lockdrop_reward,
+        user1_resp_after.total_mars_incentives
+    );
+    assert_eq!(
+        vec!["user1-15-0".to_string()],
+        user1_resp_after.lockup_position_ids
+    );
+
+    let user2_resp_after: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![Coin::new(1010000u128, "uusd")],
+        user2_resp_after.total_locked
+    );
+    assert_eq!(
+        user2_resp_before.total_mars_incentives + lockup_info.lockdrop_reward,
+        user2_resp_after.total_mars_incentives
+    );
+    assert_eq!(
+        vec!["user2-9-0".to_string(), "user1-6-0".to_string()],
+        user2_resp_after.lockup_position_ids
+    );
+
+    let tokens_response: TokensResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Tokens {
+                owner: user2_address.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec!["user1-6-0".to_string(), "user2-9-0".to_string()],
+        tokens_response.tokens
+    );
+
+    let num_tokens_response: NumTokensResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::NumTokens {})
+        .unwrap();
+    assert_eq!(4u64, num_tokens_response.count);
+
+    // ######    ERROR :: Previous owner can no longer transfer the position     ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: user1_address.to_string(),
+                token_id: "user1-6-0".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
+}
+
+#[test]
+fn test_set_compounding() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let user1_address = Addr::unchecked("user1");
+
+    // Set user balances
+    app.init_bank_balance(
+        &user1_address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20000000u128),
+        }],
+    )
+    .unwrap();
+
+    // ######    ERROR :: No lockup positions     ######
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::SetCompounding { enabled: true },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: No lockup positions");
+
+    // for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_03)
+    });
+
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            duration: 6u64,
+            recipient: None,
+            referrer: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(5000u128),
+        }],
+    )
+    .unwrap();
+
+    let mut user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(false, user_resp.is_compounding);
+
+    // ######    SUCCESS :: Compounding enabled     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::SetCompounding { enabled: true },
+        &[],
+    )
+    .unwrap();
+
+    user_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(true, user_resp.is_compounding);
+    assert_eq!(Uint128::zero(), user_resp.compounding_shares);
+    assert_eq!(Uint128::zero(), user_resp.compounded_xmars);
+
+    // ######    SUCCESS :: Compounding disabled     ######
+    app.execute_contract(
+        user1_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::SetCompounding { enabled: false },
+        &[],
+    )
+    .unwrap();
+
+    user_resp = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(false, user_resp.is_compounding);
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::zero(), state_resp.total_compounded_xmars);
+    assert_eq!(Uint128::zero(), state_resp.total_compounding_shares);
+}
+
+#[test]
+fn test_emergency_mode() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+    let guardian = Addr::unchecked("guardian");
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone(), None);
+
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
+
+    // Set user balances and open lockup positions
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10_000_01)
+    });
+    for (user_address, duration, amount) in vec![
+        (user1_address.clone(), 3u64, 1000u128),
+        (user2_address.clone(), 6u64, 2000u128),
+        (user3_address.clone(), 3u64, 3000u128),
+    ] {
+        app.init_bank_balance(
+            &user_address.clone(),
+            vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(20000000u128),
+            }],
+        )
+        .unwrap();
+        app.execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration,
+                recipient: None,
+                referrer: None,
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(amount),
+            }],
+        )
+        .unwrap();
+    }
+
+    // ***
+    // *** Test :: Error "Lockdrop is not in emergency mode" ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::NukeLockdrop {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockdrop is not in emergency mode"
+    );
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EmergencyExit {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Lockdrop is not in emergency mode"
+    );
 
-    // claim rewards & Unlock Position : xMars
+    // Set guardian and nuke batch size
     app.execute_contract(
-        user2_address.clone(),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::ClaimRewardsAndUnlock {
-            lockup_to_unlock_duration: Some(9u64),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                address_provider: None,
+                assets: None,
+                auction_contract_address: None,
+                early_unlock_penalty: None,
+                lockup_durations: None,
+                boost_curve: None,
+                guardian: Some(guardian.to_string()),
+                nuke_batch_size: Some(1u32),
+                referral_incentives_rate: None,
+                withdrawal_schedule: None,
+            },
         },
         &[],
     )
     .unwrap();
 
-    // Check user response : after
-    let user_resp_after: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user2_address.to_string(),
-            },
+    // ***
+    // *** Test :: Error "Unauthorized" Reason :: Only owner / guardian can enter emergency mode ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EnterEmergencyMode {},
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
-    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
-    assert_eq!(
-        vec!["user212".to_string()],
-        user_resp_after.lockup_position_ids
-    );
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
 
-    // Check user's xMARS Balance
-    let user2_xmars_balance_after: BalanceResponse = app
+    // ***
+    // *** Test :: Guardian enters emergency mode ***
+    // ***
+
+    app.execute_contract(
+        guardian.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EnterEmergencyMode {},
+        &[],
+    )
+    .unwrap();
+
+    let resp: EmergencyStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &xmars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::EmergencyState {})
         .unwrap();
-    let user2_mars_balance_after: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone().to_string(),
+    assert_eq!(true, resp.is_emergency_mode);
+    assert_eq!(Some(10_000_01u64), resp.activated_at);
+    assert_eq!(None, resp.start_after);
+    assert_eq!(0u64, resp.users_processed);
+    assert_eq!(false, resp.is_complete);
+
+    // ***
+    // *** Test :: Error "Lockdrop is in emergency mode" Reason :: Deposits are disabled ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                duration: 3u64,
+                recipient: None,
+                referrer: None,
             },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000u64),
+            }],
         )
-        .unwrap();
-    assert_eq!(
-        user2_xmars_balance_before.balance + user_resp_before.pending_xmars_to_claim,
-        user2_xmars_balance_after.balance
-    );
+        .unwrap_err();
     assert_eq!(
-        user2_mars_balance_before.balance + user_resp_before.total_mars_incentives,
-        user2_mars_balance_after.balance
+        err.to_string(),
+        "Generic error: Lockdrop is in emergency mode"
     );
 
-    // Check lockup
-    let lockup_after_response: LockupInfoResponse = app
+    // ***
+    // *** Test :: User exits on its own, its deposit no longer weighted ***
+    // ***
+
+    let state_before: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance.clone().to_string(),
-            &QueryMsg::LockupInfo {
-                address: user2_address.clone().to_string(),
-                duration: 9u64,
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
-
-    assert_eq!(lockup_after_response.lockup_info, None);
-
-    let user2_ma_token_balance_after: BalanceResponse = app
+    let user2_weight: SimulateWeightResponse = app
         .wrap()
         .query_wasm_smart(
-            &ma_ust_market.ma_token_address.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::SimulateWeight {
+                amount: Uint128::from(2000u64),
+                duration: 6u64,
+                denom: None,
             },
         )
         .unwrap();
 
-    assert_eq!(
-        lockup_before.maust_balance,
-        user2_ma_token_balance_after.balance
-    );
-
-    // ######    SUCCESS :: Forcefully Unlock Position : Claim MARS Lockdrop rewards (+ xMARS) successfully   ######
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(16820003)
-    });
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EmergencyExit {},
+        &[],
+    )
+    .unwrap();
 
-    // mint MARS for to User-3 Contract
-    mint_some_mars(
-        &mut app,
-        owner.clone(),
-        mars_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        user3_address.clone().to_string(),
-    );
-    // Check user response : before
-    let user_resp_before: UserInfoResponse = app
+    let resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user3_address.to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
+    assert_eq!(Uint128::from(4000u64), resp.assets[0].total_locked);
     assert_eq!(
-        Uint128::from(127741935483858u64),
-        user_resp_before.pending_xmars_to_claim
+        state_before.total_deposits_weight - user2_weight.weight,
+        resp.total_deposits_weight
     );
+
+    let err = app
+        .execute_contract(
+            user2_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::EmergencyExit {},
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(855920114122u64),
-        user_resp_before.total_mars_incentives
+        err.to_string(),
+        "Generic error: No lockup positions to dissolve"
     );
-    assert_eq!(false, user_resp_before.is_lockdrop_claimed);
 
-    // Check user's MARS Balance
-    let user3_mars_balance_before: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user3_address.clone().to_string(),
-            },
+    // ***
+    // *** Test :: Error "Unauthorized" Reason :: Only owner / guardian can nuke the lockdrop ***
+    // ***
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::NukeLockdrop {},
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
 
-    // Check user's xMARS Balance
-    let user3_xmars_balance_before: BalanceResponse = app
+    // ***
+    // *** Test :: Lockdrop is nuked in batches, resuming after the last user processed ***
+    // ***
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::NukeLockdrop {},
+        &[],
+    )
+    .unwrap();
+
+    let resp: EmergencyStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &xmars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user3_address.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::EmergencyState {})
         .unwrap();
+    assert_eq!(Some(user1_address.to_string()), resp.start_after);
+    assert_eq!(2u64, resp.users_processed);
+    assert_eq!(false, resp.is_complete);
 
-    // Increase allowance
     app.execute_contract(
-        user3_address.clone(),
-        mars_token_instance.clone(),
-        &Cw20ExecuteMsg::IncreaseAllowance {
-            spender: lockdrop_instance.clone().to_string(),
-            amount: Uint128::from(1000000000000000000u64),
-            expires: None,
-        },
+        guardian.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::NukeLockdrop {},
         &[],
     )
     .unwrap();
-
-    // claim rewards & Forcefully Unlock Position
     app.execute_contract(
-        user3_address.clone(),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::ClaimRewardsAndUnlock {
-            lockup_to_unlock_duration: Some(12u64),
-        },
+        &ExecuteMsg::NukeLockdrop {},
         &[],
     )
     .unwrap();
 
-    // Check user response : after
-    let user_resp_after: UserInfoResponse = app
+    let resp: EmergencyStateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user3_address.to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::EmergencyState {})
         .unwrap();
-    assert_eq!(Uint128::from(0u64), user_resp_after.pending_xmars_to_claim);
-    assert_eq!(true, user_resp_after.is_lockdrop_claimed);
-    assert_eq!(
-        vec!["user36".to_string()],
-        user_resp_after.lockup_position_ids
-    );
+    assert_eq!(Some(user3_address.to_string()), resp.start_after);
+    assert_eq!(3u64, resp.users_processed);
+    assert_eq!(3u64, resp.lockups_dissolved);
+    assert_eq!(true, resp.is_complete);
 
-    // Check user's xMARS Balance
-    let user3_xmars_balance_after: BalanceResponse = app
+    let resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &xmars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user3_address.clone().to_string(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
-    let user3_mars_balance_after: BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &mars_token_instance.clone().to_string(),
-            &Cw20QueryMsg::Balance {
-                address: user3_address.clone().to_string(),
-            },
+    assert_eq!(Uint128::zero(), resp.assets[0].total_locked);
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::NukeLockdrop {},
+            &[],
         )
-        .unwrap();
-    assert_eq!(
-        user3_xmars_balance_before.balance + user_resp_before.pending_xmars_to_claim,
-        user3_xmars_balance_after.balance
-    );
+        .unwrap_err();
     assert_eq!(
-        user3_mars_balance_before.balance + user_resp_before.total_mars_incentives,
-        user3_mars_balance_after.balance
+        err.to_string(),
+        "Generic error: All lockup positions have already been dissolved"
     );
 }
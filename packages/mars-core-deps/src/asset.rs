@@ -0,0 +1,11 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Represents either a native asset or a cw20. Meant to be used as part of a msg
+/// in a contract call and not to be used internally
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Asset {
+    Cw20 { contract_addr: String },
+    Native { denom: String },
+}
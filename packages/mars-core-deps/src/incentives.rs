@@ -1,7 +1,24 @@
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Incentive Metadata for a given incentive
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetIncentive {
+    /// How much MARS per second is emitted to be then distributed to all maToken holders
+    pub emission_per_second: Uint128,
+    /// Total MARS assigned for distribution since the start of the incentive
+    pub index: Decimal,
+    /// Last time (in seconds) index was updated
+    pub last_updated: u64,
+}
+
 pub mod msg {
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
 
+    use super::AssetIncentive;
+
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
     #[serde(rename_all = "snake_case")]
     pub enum QueryMsg {
@@ -22,4 +39,10 @@ pub mod msg {
         /// being sent.
         ClaimRewards {},
     }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    pub struct AssetIncentiveResponse {
+        /// Incentive for the maToken, none if it isn't incentivized
+        pub asset_incentive: Option<AssetIncentive>,
+    }
 }
@@ -1,3 +1,5 @@
+pub mod asset;
+
 // Contracts
 pub mod address_provider;
 pub mod incentives;
@@ -1,7 +1,22 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Asset markets. Only the fields used by the periphery contracts are mirrored
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Market {
+    /// Market index (Bit position on data)
+    pub index: u32,
+    /// maToken contract address
+    pub ma_token_address: Addr,
+}
+
 pub mod msg {
     use schemars::JsonSchema;
     use serde::{Deserialize, Serialize};
 
+    use crate::asset::Asset;
+
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
     #[serde(rename_all = "snake_case")]
     pub enum ExecuteMsg {
@@ -12,4 +27,11 @@ pub mod msg {
             denom: String,
         },
     }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub enum QueryMsg {
+        /// Get asset market
+        Market { asset: Asset },
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -8,8 +8,56 @@ pub struct InstantiateMsg {
     pub owner: Option<String>,
     pub mars_token_address: String,
     pub merkle_roots: Option<Vec<String>>,
+    /// Hashing scheme the merkle trees of `merkle_roots` are built with (default: Legacy)
+    pub hashing_scheme: Option<HashingScheme>,
     pub from_timestamp: Option<u64>,
     pub to_timestamp: u64,
+    pub vesting: Option<VestingSchedule>,
+    pub early_exit_penalty: Option<EarlyExitPenalty>,
+    /// Number of seconds after which a proposed sweep of the unclaimed MARS can be executed (default: 7 days)
+    pub sweep_delay: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    /// Number of seconds post claims enablement before which no MARS is vested
+    pub cliff: u64,
+    /// Number of seconds post claims enablement over which MARS vests linearly
+    pub duration: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyExitPenalty {
+    /// Number of seconds post claims enablement during which withdrawals are penalized
+    pub window: u64,
+    /// Share of the withdrawn MARS forfeited by the user
+    pub penalty_rate: Decimal,
+    /// Address receiving the forfeited MARS. Forfeited MARS is added back to the unclaimed tokens if not set
+    pub treasury_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SweepRecipient {
+    /// Address receiving the unclaimed MARS
+    pub address: String,
+    /// Number of unclaimed MARS tokens transferred to the address
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HashingScheme {
+    /// Leaves are hashed as keccak256(account + amount), nodes as keccak256(a || b)
+    Legacy,
+    /// Leaves are domain separated and include the chain id and the contract address. Fields are
+    /// length prefixed, leaves and nodes are tagged with distinct prefixes
+    V1,
+}
+
+impl Default for HashingScheme {
+    fn default() -> Self {
+        HashingScheme::V1
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,18 +68,54 @@ pub enum ExecuteMsg {
     UpdateConfig {
         owner: Option<String>,
         auction_contract_address: Option<String>,
-        merkle_roots: Option<Vec<String>>,
         from_timestamp: Option<u64>,
         to_timestamp: Option<u64>,
+        early_exit_penalty: Option<EarlyExitPenalty>,
+        auction_deadline: Option<u64>,
+    },
+    /// Admin function to add a merkle root with its own MARS budget and optional expiry
+    AddMerkleRoot {
+        merkle_root: String,
+        budget: Uint128,
+        expiry_timestamp: Option<u64>,
+        /// Hashing scheme used to build the merkle tree (default: V1)
+        hashing_scheme: Option<HashingScheme>,
+        is_indexed: Option<bool>,
+    },
+    /// Admin function to disable a merkle root. No claims are allowed against a disabled merkle root
+    DisableMerkleRoot {
+        root_index: u32,
     },
     // Called by the bootstrap auction contract when liquidity is added to the
     // MARS-UST Pool to enable MARS withdrawals by users
     EnableClaims {},
-    /// Allows Terra users to claim their MARS Airdrop
+    /// Admin function to mark the bootstrap auction as failed once `auction_deadline` has passed without claims
+    /// being enabled. The auction contract returns the delegated MARS and users can withdraw their full airdrop
+    MarkAuctionFailed {},
+    /// Allows Terra users to claim their MARS Airdrop. `leaf_index` is required by indexed merkle roots
     Claim {
         claim_amount: Uint128,
         merkle_proof: Vec<String>,
         root_index: u32,
+        leaf_index: Option<u64>,
+    },
+    /// Claims the MARS Airdrop and delegates `delegate_amount` of it to the bootstrap auction contract
+    ClaimAndDelegate {
+        claim_amount: Uint128,
+        merkle_proof: Vec<String>,
+        root_index: u32,
+        leaf_index: Option<u64>,
+        delegate_amount: Uint128,
+    },
+    /// Allows users to claim the MARS Airdrop of an EVM address by providing a signature
+    /// of their terra address generated by that EVM address
+    EvmClaim {
+        eth_address: String,
+        claim_amount: Uint128,
+        merkle_proof: Vec<String>,
+        root_index: u32,
+        signature: String,
+        signed_msg_hash: String,
     },
     /// Allows users to delegate their MARS tokens to the LP Bootstrap auction contract
     DelegateMarsToBootstrapAuction {
@@ -39,17 +123,25 @@ pub enum ExecuteMsg {
     },
     /// Allows users to withdraw their MARS tokens
     WithdrawAirdropReward {},
-    /// Admin function to facilitate transfer of the unclaimed MARS Tokens
-    TransferUnclaimedTokens {
-        recipient: String,
-        amount: Uint128,
+    /// Allows users to withdraw their vested MARS tokens
+    WithdrawVested {},
+    /// Admin function to propose a sweep of the unclaimed MARS tokens to the recipients once the claim window is
+    /// over. The sweep can be executed once the sweep delay has passed
+    ProposeSweep {
+        recipients: Vec<SweepRecipient>,
     },
+    /// Admin function to cancel the pending sweep
+    CancelSweep {},
+    /// Executes the pending sweep once the sweep delay has passed. Callable by anyone
+    ExecuteSweep {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     IncreaseMarsIncentives {},
+    /// Sent by the bootstrap auction contract along-with the delegated MARS once the auction is marked as failed
+    ReturnDelegatedMars {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -57,19 +149,60 @@ pub enum Cw20HookMsg {
 pub enum QueryMsg {
     Config {},
     State {},
-    UserInfo { address: String },
-    HasUserClaimed { address: String },
+    UserInfo {
+        address: String,
+    },
+    /// Either `address` (claims against any merkle root, or against `root_index` if provided) or `root_index` along-with
+    /// `leaf_index` (indexed merkle roots) is to be provided
+    HasUserClaimed {
+        address: Option<String>,
+        root_index: Option<u32>,
+        leaf_index: Option<u64>,
+    },
+    MerkleRoots {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
+    ClaimedBitmap {
+        root_index: u32,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the airdrop positions of the users, ordered by their address
+    AllUsers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the airdrop positions of the users who delegated MARS to the bootstrap auction, among the next `limit`
+    /// users ordered by their address
+    Delegators {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the airdrop positions of the users who are yet to withdraw their full airdrop, among the next `limit`
+    /// users ordered by their address
+    UnwithdrawnUsers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the pending sweep of the unclaimed MARS tokens, if any
+    PendingSweep {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub owner: String,
     pub mars_token_address: String,
-    pub merkle_roots: Vec<String>,
     pub from_timestamp: u64,
     pub to_timestamp: u64,
     pub auction_contract_address: Option<Addr>,
     pub are_claims_allowed: bool,
+    pub claims_enabled_timestamp: u64,
+    pub vesting: Option<VestingSchedule>,
+    pub early_exit_penalty: Option<EarlyExitPenalty>,
+    pub auction_deadline: Option<u64>,
+    pub is_auction_failed: bool,
+    pub sweep_delay: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -77,6 +210,14 @@ pub struct StateResponse {
     pub total_airdrop_size: Uint128,
     pub total_delegated_amount: Uint128,
     pub unclaimed_tokens: Uint128,
+    /// Total MARS forfeited by users withdrawing within the early exit window
+    pub total_penalty_amount: Uint128,
+    /// MARS forfeited via early exits which has been added back to the unclaimed tokens
+    pub recycled_penalty_amount: Uint128,
+    /// Number of users with an airdrop position
+    pub claimants_count: u64,
+    /// Number of users who withdrew MARS from their airdrop position
+    pub withdrawers_count: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -84,6 +225,23 @@ pub struct UserInfoResponse {
     pub airdrop_amount: Uint128,
     pub delegated_amount: Uint128,
     pub tokens_withdrawn: bool,
+    pub withdrawn_amount: Uint128,
+    pub vested_amount: Uint128,
+    pub locked_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserResponse {
+    pub address: String,
+    pub user_info: UserInfoResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UsersResponse {
+    pub users: Vec<UserResponse>,
+    /// Address of the last user scanned, to be provided as `start_after` to query the next users. None once all
+    /// the users have been scanned
+    pub start_after: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -91,5 +249,45 @@ pub struct ClaimResponse {
     pub is_claimed: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRootResponse {
+    pub root_index: u32,
+    pub merkle_root: String,
+    pub budget: Option<Uint128>,
+    pub claimed_amount: Uint128,
+    pub expiry_timestamp: Option<u64>,
+    pub is_disabled: bool,
+    pub hashing_scheme: HashingScheme,
+    pub is_indexed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRootsResponse {
+    pub merkle_roots: Vec<MerkleRootResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimedBitmapWordResponse {
+    /// Index of the word, i.e. `leaf_index / 256`
+    pub word_index: u64,
+    /// 32 bytes bitmap. Claim status of `leaf_index` is the bit `leaf_index % 8` of the byte `(leaf_index % 256) / 8`
+    pub bitmap: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimedBitmapResponse {
+    pub root_index: u32,
+    pub words: Vec<ClaimedBitmapWordResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingSweepResponse {
+    pub recipients: Vec<SweepRecipient>,
+    /// Timestamp at which the sweep was proposed
+    pub proposed_timestamp: u64,
+    /// Timestamp since which the sweep can be executed
+    pub executable_timestamp: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
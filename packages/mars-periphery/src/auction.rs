@@ -1,3 +1,4 @@
+use crate::withdrawal_schedule::WithdrawalSchedule;
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
@@ -17,6 +18,9 @@ pub struct InstantiateMsg {
     pub mars_deposit_window: u64,
     pub ust_deposit_window: u64,
     pub withdrawal_window: u64,
+    /// Max share of their UST deposits which users can withdraw during the deposit / withdrawal windows. Defaults to 100%
+    /// during the UST deposit window, 50% during the 1st half of the withdrawal window, then decreasing linearly to 0%
+    pub withdrawal_schedule: Option<WithdrawalSchedule>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -52,6 +56,9 @@ pub enum ExecuteMsg {
         unlock_for_addr: Option<Addr>,
         withdraw_unlocked_shares: bool,
     },
+    /// Returns the MARS delegated via the airdrop contract if liquidity hasn't been added to the pool.
+    /// Callable only by the airdrop contract once the bootstrap auction is marked as failed
+    ReturnAirdropDelegations {},
     Callback(CallbackMsg),
 }
 
@@ -119,6 +126,7 @@ pub struct ConfigResponse {
     pub mars_deposit_window: u64,
     pub ust_deposit_window: u64,
     pub withdrawal_window: u64,
+    pub withdrawal_schedule: WithdrawalSchedule,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -132,6 +140,8 @@ pub struct StateResponse {
     pub pool_init_timestamp: u64,
     pub global_mars_reward_index: Decimal,
     pub global_astro_reward_index: Decimal,
+    pub airdrop_mars_deposited: Uint128,
+    pub are_airdrop_delegations_returned: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -2,7 +2,7 @@ use crate::tax::deduct_tax;
 use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::{
     to_binary, Addr, Api, BalanceResponse, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps,
-    QuerierWrapper, QueryRequest, StdResult, Uint128, WasmMsg, WasmQuery,
+    Event, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw20::BalanceResponse as CW20BalanceResponse;
 use cw20_base::msg::{ExecuteMsg as CW20ExecuteMsg, QueryMsg as Cw20QueryMsg};
@@ -126,6 +126,74 @@ pub fn cw20_get_balance(
     Ok(query.balance)
 }
 
+/// Event types and attribute keys under which the executed contract is reported in wasm events
+const WASM_EVENT_TYPES: [&str; 2] = ["wasm", "from_contract"];
+const CONTRACT_ADDRESS_KEYS: [&str; 3] =
+    ["contract_address", "_contract_address", "_contract_addr"];
+
+/// @dev Helper function which returns the amount of cw20 tokens minted / transferred to an account, as reported by the
+/// events of a sub-message execution. Returns None if no such mint / transfer is found
+/// @param events : Events of the sub-message execution
+/// @param token_address : Contract address of the cw20 token
+/// @param account_addr : Address of the account receiving the tokens
+pub fn cw20_get_amount_received_from_events(
+    events: &[Event],
+    token_address: &Addr,
+    account_addr: &Addr,
+) -> Option<Uint128> {
+    let mut received: Option<Uint128> = None;
+
+    for event in events
+        .iter()
+        .filter(|event| WASM_EVENT_TYPES.contains(&event.ty.as_str()))
+    {
+        // Attributes of several contract executions can be merged into one event, each starting with the contract address
+        let mut contract: Option<&str> = None;
+        let mut action: Option<&str> = None;
+        let mut to: Option<&str> = None;
+        let mut amount: Option<Uint128> = None;
+
+        for attr in event
+            .attributes
+            .iter()
+            .map(Some)
+            .chain(std::iter::once(None))
+        {
+            let is_new_execution = attr.map_or(true, |attr| {
+                CONTRACT_ADDRESS_KEYS.contains(&attr.key.as_str())
+            });
+
+            if is_new_execution {
+                if let (Some(contract), Some(action), Some(to), Some(amount)) =
+                    (contract, action, to, amount)
+                {
+                    if contract == token_address.as_str()
+                        && (action == "mint" || action == "transfer")
+                        && to == account_addr.as_str()
+                    {
+                        received = Some(received.unwrap_or_default() + amount);
+                    }
+                }
+                contract = attr.map(|attr| attr.value.as_str());
+                action = None;
+                to = None;
+                amount = None;
+                continue;
+            }
+
+            let attr = attr.unwrap();
+            match attr.key.as_str() {
+                "action" => action = Some(attr.value.as_str()),
+                "to" | "recipient" => to = Some(attr.value.as_str()),
+                "amount" => amount = attr.value.parse::<u128>().ok().map(Uint128::from),
+                _ => {}
+            }
+        }
+    }
+
+    received
+}
+
 /// @dev Helper function which returns a cosmos wasm msg to approve held cs20 tokens to be transferrable by beneficiary address
 /// @param token_contract_address : Token contract address
 /// @param spender_address : Address to which allowance is being provided to, to allow it to transfer the tokens held by the contract
@@ -1,6 +1,8 @@
 pub mod airdrop;
 pub mod auction;
+pub mod cw721;
 pub mod helpers;
 pub mod lockdrop;
 pub mod lp_staking;
 pub mod tax;
+pub mod withdrawal_schedule;
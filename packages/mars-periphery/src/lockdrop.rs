@@ -1,5 +1,8 @@
-use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
-use cw20::Cw20ReceiveMsg;
+use crate::withdrawal_schedule::WithdrawalSchedule;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ReceiveMsg, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,8 +12,8 @@ pub struct InstantiateMsg {
     pub owner: String,
     /// Contract used to query addresses related to red-bank (MARS Token)
     pub address_provider: Option<String>,
-    ///  maUST token address - Minted upon UST deposits into red bank
-    pub ma_ust_token: Option<String>,
+    /// Assets accepted for deposits, along-with their maToken and price weight
+    pub assets: Vec<AssetParams>,
     /// Timestamp till when deposits can be made
     pub init_timestamp: u64,
     /// Number of seconds for which lockup deposits will be accepted
@@ -19,8 +22,33 @@ pub struct InstantiateMsg {
     pub withdrawal_window: u64,
     /// Durations and boosties params
     pub lockup_durations: Vec<LockupDurationParams>,
+    /// Boost curve used to compute the boost of a lockup duration. Defaults to `BoostCurve::Fixed`
+    pub boost_curve: Option<BoostCurve>,
     /// Number of seconds per week
     pub seconds_per_duration_unit: u64,
+    /// Penalty for unlocking a lockup position before its unlock timestamp. Defaults to returning the full lockdrop reward
+    pub early_unlock_penalty: Option<EarlyUnlockPenalty>,
+    /// Share of the lockdrop incentives (MARS) allocated to the referrers, pro-rata to their referred weight. Defaults to 0
+    pub referral_incentives_rate: Option<Decimal>,
+    /// Max share of their deposits which users can withdraw during the deposit / withdrawal windows. Defaults to 100% during
+    /// the deposit window, 50% during the 1st half of the withdrawal window, then decreasing linearly to 0%
+    pub withdrawal_schedule: Option<WithdrawalSchedule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyUnlockPenalty {
+    /// Share of the position's lockdrop reward (MARS) to be returned to force unlock it
+    pub penalty_rate: Decimal,
+    /// Address receiving the forfeited MARS. Forfeited MARS is redistributed to the remaining lockers if not set
+    pub treasury_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetParams {
+    /// Denom of the native asset accepted for deposits
+    pub denom: String,
+    /// Value of one unit of the asset relative to one uusd. Weights the deposits (lockdrop reward) and maToken shares (xMARS rewards) of the asset
+    pub price_weight: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -29,10 +57,24 @@ pub struct UpdateConfigMsg {
     pub owner: Option<String>,
     /// Contract used to query addresses related to red-bank (MARS Token)
     pub address_provider: Option<String>,
-    ///  maUST token address - Minted upon UST deposits into red bank
-    pub ma_ust_token: Option<String>,
+    /// Assets to be added / re-weighted, matched by denom. Can only be updated before the deposit window opens
+    pub assets: Option<Vec<AssetParams>>,
     /// Bootstrap Auction contract address
     pub auction_contract_address: Option<String>,
+    /// Penalty for unlocking a lockup position before its unlock timestamp. Can only be updated before claims are enabled
+    pub early_unlock_penalty: Option<EarlyUnlockPenalty>,
+    /// Durations and boosties params. Can only be updated before the deposit window opens
+    pub lockup_durations: Option<Vec<LockupDurationParams>>,
+    /// Boost curve used to compute the boost of a lockup duration. Can only be updated before the deposit window opens
+    pub boost_curve: Option<BoostCurve>,
+    /// Account which can enter emergency mode along-with the owner
+    pub guardian: Option<String>,
+    /// Number of users whose lockup positions are dissolved per `NukeLockdrop` call
+    pub nuke_batch_size: Option<u32>,
+    /// Share of the lockdrop incentives (MARS) allocated to the referrers. Can only be updated before the deposit window opens
+    pub referral_incentives_rate: Option<Decimal>,
+    /// Max share of their deposits which users can withdraw. Can only be updated before the deposit window opens
+    pub withdrawal_schedule: Option<WithdrawalSchedule>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -43,30 +85,112 @@ pub enum ExecuteMsg {
     UpdateConfig {
         new_config: UpdateConfigMsg,
     },
-    /// Function to deposit UST in the contract locked for `duration` number of weeks, starting once the deposits/withdrawals are disabled
+    /// Function to deposit any of the accepted assets (UST, LUNA, ...) in the contract locked for `duration` number of weeks, starting once
+    /// the deposits/withdrawals are disabled. Each deposit opens a new lockup position, owned by `recipient` (the sender by default).
+    /// The deposit's weight is attributed to `referrer` if provided
     DepositUst {
         duration: u64,
+        recipient: Option<String>,
+        referrer: Option<String>,
     },
-    /// Function to withdraw UST from the lockup position which is locked for `duration` number of weeks
+    /// Function to withdraw the asset locked in the lockup position which is locked for `duration` number of weeks.
+    /// `lockup_id` is required if the user holds several lockup positions with the same duration
     WithdrawUst {
         duration: u64,
         amount: Uint128,
+        lockup_id: Option<String>,
     },
-    /// ADMIN Function :: Deposits all UST into the Red Bank
+    /// Moves the sender's lockup position locked for `from_duration` number of weeks to the longer `to_duration`, re-computing
+    /// its weight and boost. The position keeps its lockup id. Allowed until the deposit / withdrawal windows close.
+    /// `lockup_id` is required if the user holds several lockup positions with `from_duration`
+    ExtendLockup {
+        from_duration: u64,
+        to_duration: u64,
+        lockup_id: Option<String>,
+    },
+    /// ADMIN Function :: Resolves the Red Bank, incentives contract, MARS and xMARS token addresses via the address provider.
+    /// The addresses are cached until the next refresh
+    RefreshAddresses {},
+    /// ADMIN Function :: Deposits all the locked assets into the Red Bank, each into its own market. The maToken of each asset
+    /// is resolved via its Red Bank market
     DepositUstInRedBank {},
     /// Deposit MARS to auction contract
     DepositMarsToAuction {
         amount: Uint128,
     },
-    /// Facilitates MARS reward claim and optionally unlocking any lockup position once the lockup duration is over
+    /// Facilitates MARS reward claim and optionally unlocking any lockup position once the lockup duration is over.
+    /// Lockup positions can be unlocked before via `Cw20HookMsg::ForceUnlock`.
+    /// `lockup_to_unlock_id` is required if the user holds several lockup positions with the duration to unlock
     ClaimRewardsAndUnlock {
         unlock_for_addr: Option<Addr>,
         lockup_to_unlock_duration: Option<u64>,
+        lockup_to_unlock_id: Option<String>,
+    },
+    /// Unlocks `amount` of the asset locked in the sender's lockup position locked for `duration` number of weeks, once the
+    /// lockup duration is over, and transfers the corresponding maTokens. The rest of the position stays locked and keeps
+    /// accruing xMARS rewards. Also claims the sender's rewards.
+    /// `lockup_id` is required if the user holds several lockup positions with the same duration
+    PartialUnlock {
+        duration: u64,
+        amount: Uint128,
+        lockup_id: Option<String>,
     },
     /// Called by the bootstrap auction contract when liquidity is added to the MARS-UST Pool to enable MARS withdrawals by users
     EnableClaims {},
-    /// Loops over provided user addresses, unlocks each of their lockup position. Removes the list of user positions from state
+    /// Transfers the sender's share of the referral incentives (MARS), pro-rata to his referred weight. Allowed once claims are enabled
+    ClaimReferralRewards {},
+    /// Enables / disables compounding of the sender's xMARS rewards. When enabled, the xMARS rewards claimed are kept by
+    /// the contract and paid out once the sender's lockup positions are unlocked, pro-rata to their maUST share
+    SetCompounding {
+        enabled: bool,
+    },
+    /// Owner / guardian Function :: Enters emergency mode. Deposits are disabled and lockup positions can be dissolved
+    /// via `NukeLockdrop` / `EmergencyExit`
+    EnterEmergencyMode {},
+    /// Owner / guardian Function :: Loops over the next batch of users (`nuke_batch_size`, resuming after the last user processed)
+    /// and dissolves each of their lockup positions, refunding the assets (or maTokens once deposited in the Red Bank) locked.
+    /// Only allowed in emergency mode
     NukeLockdrop {},
+    /// Dissolves all the sender's lockup positions, refunding the assets (or maTokens once deposited in the Red Bank) locked.
+    /// Only allowed in emergency mode
+    EmergencyExit {},
+    /// Admin function :: Migrates the next `limit` legacy users / lockup positions stored by earlier versions of the
+    /// contract, resuming after the last one migrated. Other functions are disabled until all of them are migrated
+    MigrateLegacyPositions {
+        limit: Option<u32>,
+    },
+    /// cw721 :: Transfers the lockup position `token_id` (lockup id) along-with its MARS incentives and pending xMARS rewards to `recipient`.
+    /// Allowed once the deposit / withdrawal windows are closed
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    /// cw721 :: Transfers the lockup position `token_id` to `contract` and triggers an action on the receiving contract
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// cw721 :: Allows `spender` to transfer / send the lockup position `token_id`
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// cw721 :: Removes a previously granted approval
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    /// cw721 :: Allows `operator` to transfer / send any of the sender's lockup positions
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    /// cw721 :: Removes a previously granted ApproveAll permission
+    RevokeAll {
+        operator: String,
+    },
     /// Callbacks; only callable by the contract itself.
     Callback(CallbackMsg),
 }
@@ -74,16 +198,18 @@ pub enum ExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CallbackMsg {
-    UpdateStateOnRedBankDeposit {
-        prev_ma_ust_balance: Uint128,
-    },
     UpdateStateOnClaim {
         user: Addr,
-        prev_xmars_balance: Uint128,
     },
     DissolvePosition {
         user: Addr,
-        duration: u64,
+        lockup_id: String,
+        mars_to_redistribute: Uint128,
+    },
+    PartialUnlockPosition {
+        user: Addr,
+        lockup_id: String,
+        amount: Uint128,
     },
 }
 
@@ -91,6 +217,11 @@ pub enum CallbackMsg {
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     IncreaseMarsIncentives {},
+    /// Unlocks the lockup position `lockup_id` before its unlock timestamp. The MARS sent needs to match the
+    /// position's lockdrop reward times the early unlock penalty rate. Also claims the owner's pending rewards
+    ForceUnlock {
+        lockup_id: String,
+    },
 }
 
 // Modified from
@@ -110,10 +241,83 @@ impl CallbackMsg {
 pub enum QueryMsg {
     Config {},
     State {},
-    UserInfo { address: String },
-    LockupInfo { address: String, duration: u64 },
-    LockupInfoWithId { lockup_id: String },
-    WithdrawalPercentAllowed { timestamp: Option<u64> },
+    UserInfo {
+        address: String,
+    },
+    /// Returns the user's lockup position with the given duration. Fails if the user holds several of them
+    LockupInfo {
+        address: String,
+        duration: u64,
+    },
+    LockupInfoWithId {
+        lockup_id: String,
+    },
+    WithdrawalPercentAllowed {
+        timestamp: Option<u64>,
+    },
+    /// Returns the emergency mode status along-with the `NukeLockdrop` progress
+    EmergencyState {},
+    /// Returns the boost and weight of `amount` of `denom` (uusd by default) locked for `duration` number of weeks.
+    /// Fails if the asset or the duration isn't supported
+    SimulateWeight {
+        amount: Uint128,
+        duration: u64,
+        denom: Option<String>,
+    },
+    /// Projects the user's lockdrop reward (as per the current total weight), withdrawal limits, unlock times, pending
+    /// xMARS rewards and MARS emissions (as per the incentives contract's emission rates) at `timestamp` (the current block time by default)
+    SimulateUser {
+        address: String,
+        timestamp: Option<u64>,
+    },
+    /// Projects the lockdrop reward (as per the current total weight), withdrawal limit at `timestamp` (the current block
+    /// time by default) and unlock time of a deposit of `amount` of `denom` (uusd by default) locked for `duration` number of weeks
+    SimulateDeposit {
+        amount: Uint128,
+        duration: u64,
+        timestamp: Option<u64>,
+        denom: Option<String>,
+    },
+    /// Returns the deposits referred by `address` along-with its share of the referral incentives
+    Referrer {
+        address: String,
+    },
+    /// cw721 :: Returns the owner of the lockup position along-with its approvals
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// cw721 :: Returns the operators allowed to transfer all of the owner's lockup positions
+    ApprovedForAll {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// cw721 :: Returns the number of lockup positions
+    NumTokens {},
+    /// cw721 :: Returns the name and symbol of the lockup positions
+    ContractInfo {},
+    /// cw721 :: Returns the metadata of the lockup position
+    NftInfo {
+        token_id: String,
+    },
+    /// cw721 :: Returns the metadata and owner of the lockup position
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// cw721 :: Returns the ids of the lockup positions owned by `owner`
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// cw721 :: Returns the ids of all the lockup positions
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -122,8 +326,10 @@ pub struct ConfigResponse {
     pub owner: String,
     /// Contract used to query addresses related to red-bank (MARS Token)
     pub address_provider: Option<Addr>,
-    ///  maUST token address - Minted upon UST deposits into red bank
-    pub ma_ust_token: Option<Addr>,
+    /// Mars protocol addresses resolved via the address provider upon `RefreshAddresses`
+    pub mars_addresses: Option<MarsAddresses>,
+    /// Assets accepted for deposits, along-with their maToken and price weight
+    pub assets: Vec<AssetInfo>,
     /// Auction Contract address to which MARS tokens can be delegated to for bootstrapping MARS-UST Pool
     pub auction_contract_address: Option<Addr>,
     /// Timestamp till when deposits can be made
@@ -134,21 +340,31 @@ pub struct ConfigResponse {
     pub withdrawal_window: u64,
     /// Durations and boosties params
     pub lockup_durations: Vec<LockupDurationParams>,
+    /// Boost curve used to compute the boost of a lockup duration
+    pub boost_curve: BoostCurve,
     /// Number of seconds per week
     pub seconds_per_duration_unit: u64,
     /// Total MARS lockdrop incentives to be distributed among the users
     pub lockdrop_incentives: Uint128,
+    /// Share of the position's lockdrop reward to be returned to force unlock it
+    pub early_unlock_penalty_rate: Decimal,
+    /// Address receiving the forfeited MARS. Forfeited MARS is redistributed to the remaining lockers if not set
+    pub treasury_address: Option<Addr>,
+    /// Account which can enter emergency mode along-with the owner
+    pub guardian: Option<Addr>,
+    /// Number of users whose lockup positions are dissolved per `NukeLockdrop` call
+    pub nuke_batch_size: u32,
+    /// Share of the lockdrop incentives (MARS) allocated to the referrers
+    pub referral_incentives_rate: Decimal,
+    /// Max share of their deposits which users can withdraw during the deposit / withdrawal windows
+    pub withdrawal_schedule: WithdrawalSchedule,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StateResponse {
-    /// Total UST deposited at the end of Lockdrop window. This value remains unchanged post the lockdrop window
-    pub final_ust_locked: Uint128,
-    /// maUST minted at the end of Lockdrop window upon UST deposit in red bank. This value remains unchanged post the lockdrop window
-    pub final_maust_locked: Uint128,
-    /// UST deposited in the contract. This value is updated real-time upon each UST deposit / unlock
-    pub total_ust_locked: Uint128,
-    /// maUST held by the contract. This value is updated real-time upon each maUST withdrawal from red bank
+    /// Amounts locked / maTokens held per asset
+    pub assets: Vec<AssetState>,
+    /// maTokens held by the contract, weighted by the assets' price weights (i.e. in maUST terms). This value is updated real-time upon each maToken withdrawal
     pub total_maust_locked: Uint128,
     /// MARS Tokens deposited into the bootstrap auction contract
     pub total_mars_delegated: Uint128,
@@ -158,11 +374,21 @@ pub struct StateResponse {
     pub total_deposits_weight: Uint128,
     /// Ratio of MARS rewards accured to total_maust_locked. Used to calculate MARS incentives accured by each user
     pub xmars_rewards_index: Decimal,
+    /// MARS returned upon forced unlocks of lockup positions
+    pub total_mars_forfeited: Uint128,
+    /// Ratio of forfeited MARS redistributed to total_maust_locked. Used to calculate forfeited MARS accrued by each user
+    pub forfeited_mars_index: Decimal,
+    /// xMARS rewards kept by the contract on behalf of the users compounding their rewards
+    pub total_compounded_xmars: Uint128,
+    /// Total shares of the compounded xMARS
+    pub total_compounding_shares: Uint128,
+    /// Total weight of the deposits made with a referrer
+    pub total_referred_weight: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct UserInfoResponse {
-    pub total_ust_locked: Uint128,
+    pub total_locked: Vec<Coin>,
     pub total_maust_share: Uint128,
     pub lockup_position_ids: Vec<String>,
     pub total_mars_incentives: Uint128,
@@ -171,6 +397,10 @@ pub struct UserInfoResponse {
     pub reward_index: Decimal,
     pub total_xmars_claimed: Uint128,
     pub pending_xmars_to_claim: Uint128,
+    pub pending_forfeited_mars_to_claim: Uint128,
+    pub is_compounding: bool,
+    pub compounding_shares: Uint128,
+    pub compounded_xmars: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -181,25 +411,209 @@ pub struct LockupInfoResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LockupInfoQueryData {
+    /// Owner of the lockup position
+    pub owner: Addr,
     /// Lockup Duration
     pub duration: u64,
-    /// UST locked as part of this lockup position
-    pub ust_locked: Uint128,
-    /// MA-UST share
-    pub maust_balance: Uint128,
+    /// Denom of the asset locked
+    pub denom: String,
+    /// Amount of the asset locked as part of this lockup position
+    pub amount_locked: Uint128,
+    /// maToken share
+    pub ma_token_balance: Uint128,
     /// Lockdrop incentive distributed to this position
     pub lockdrop_reward: Uint128,
     /// Timestamp beyond which this position can be unlocked
     pub unlock_timestamp: u64,
     /// Boolean value indicating if the user's has withdrawn funds post the only 1 withdrawal limit cutoff
     pub withdrawal_flag: bool,
+    /// Referrer of the deposit, if any
+    pub referrer: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferrerResponse {
+    /// Address of the referrer
+    pub address: String,
+    /// Total weight of the deposits referred
+    pub referred_weight: Uint128,
+    /// Number of deposits referred
+    pub deposits_referred: u64,
+    /// Amounts referred, per asset (net of withdrawals)
+    pub total_referred: Vec<Coin>,
+    /// Referrer's share of the referral incentives (MARS)
+    pub mars_incentives: Uint128,
+    /// Boolean value indicating if the referral incentives have been claimed or not
+    pub is_claimed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmergencyStateResponse {
+    /// Boolean value indicating if the lockdrop is in emergency mode
+    pub is_emergency_mode: bool,
+    /// Timestamp at which emergency mode was entered
+    pub activated_at: Option<u64>,
+    /// Last user processed by `NukeLockdrop`. The next batch starts after this user
+    pub start_after: Option<String>,
+    /// Number of users whose lockup positions have been dissolved (via `NukeLockdrop` or `EmergencyExit`)
+    pub users_processed: u64,
+    /// Number of lockup positions dissolved
+    pub lockups_dissolved: u64,
+    /// Boolean value indicating if `NukeLockdrop` has processed all the users
+    pub is_complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateWeightResponse {
+    /// Boost of the lockup duration
+    pub boost: Decimal,
+    /// Weight of the amount locked, used to compute the lockdrop reward
+    pub weight: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateUserResponse {
+    /// Timestamp at which the values are projected
+    pub timestamp: u64,
+    /// MARS incentives allocated to the user, as per the current total weight until finalized
+    pub total_mars_incentives: Uint128,
+    /// Share of the amounts locked which can be withdrawn at `timestamp`
+    pub withdrawal_percent_allowed: Decimal,
+    /// xMARS rewards already accrued by the user, claimable at `timestamp`
+    pub pending_xmars_to_claim: Uint128,
+    /// MARS emitted to the user's maToken shares from the current block until `timestamp`, estimated with the incentives
+    /// contract's current emission rates. Claimed as xMARS, at the staking exchange rate of the claim
+    pub estimated_mars_emissions: Uint128,
+    /// User's lockup positions
+    pub lockup_positions: Vec<SimulatedLockupPosition>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulatedLockupPosition {
+    pub lockup_id: String,
+    pub denom: String,
+    pub duration: u64,
+    pub amount_locked: Uint128,
+    /// Weight of the amount locked, used to compute the lockdrop reward
+    pub weight: Uint128,
+    /// Lockdrop reward (MARS) of the position, as per the current total weight until finalized
+    pub mars_incentives: Uint128,
+    /// Amount of the asset which can be withdrawn from the position at `timestamp`
+    pub max_withdrawable: Uint128,
+    pub unlock_timestamp: u64,
+    /// Boolean value indicating if the position can be unlocked at `timestamp`
+    pub is_unlockable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateDepositResponse {
+    /// Timestamp at which the withdrawal limit is projected
+    pub timestamp: u64,
+    /// Boost of the lockup duration
+    pub boost: Decimal,
+    /// Weight of the amount locked, used to compute the lockdrop reward
+    pub weight: Uint128,
+    /// Lockdrop reward (MARS) of the deposit, as per the current total weight (including the deposit's weight)
+    pub mars_incentives: Uint128,
+    /// Amount of the asset which could be withdrawn at `timestamp`
+    pub max_withdrawable: Uint128,
+    /// Timestamp beyond which the deposit could be unlocked
+    pub unlock_timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetInfo {
+    /// Denom of the native asset accepted for deposits
+    pub denom: String,
+    /// maToken address - Minted upon deposits of the asset into the Red Bank. Resolved via the asset's Red Bank market upon the deposit
+    pub ma_token: Option<Addr>,
+    /// Value of one unit of the asset relative to one uusd
+    pub price_weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarsAddresses {
+    /// Red Bank, into which the locked assets are deposited
+    pub red_bank: Addr,
+    /// Incentives contract, from which the xMARS rewards are claimed
+    pub incentives: Addr,
+    /// MARS token address
+    pub mars_token: Addr,
+    /// xMARS token address
+    pub xmars_token: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetState {
+    /// Denom of the asset
+    pub denom: String,
+    /// Amount deposited at the end of Lockdrop window. This value remains unchanged post the lockdrop window
+    pub final_locked: Uint128,
+    /// maTokens minted at the end of Lockdrop window upon the deposit in red bank. This value remains unchanged post the lockdrop window
+    pub final_ma_locked: Uint128,
+    /// Amount deposited in the contract. This value is updated real-time upon each deposit / unlock
+    pub total_locked: Uint128,
+    /// maTokens held by the contract. This value is updated real-time upon each maToken withdrawal
+    pub total_ma_locked: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LockupDurationParams {
     pub duration: u64,
-    pub boost: Uint128,
+    pub boost: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoostCurve {
+    /// Boost of the durations listed in `lockup_durations`. Other durations are not supported
+    Fixed,
+    /// Boost increasing linearly from `min_boost` to `max_boost` over the `[min_duration, max_duration]` range
+    Linear {
+        min_duration: u64,
+        max_duration: u64,
+        min_boost: Decimal,
+        max_boost: Decimal,
+    },
+    /// Boost interpolated linearly between the consecutive points of `lockup_durations` (sorted by increasing duration),
+    /// over the range from the first to the last duration
+    PiecewiseLinear,
+}
+
+impl Default for BoostCurve {
+    fn default() -> Self {
+        BoostCurve::Fixed
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
+
+/// @dev Returns the id of a lockup position, formatted as `<depositor>-<duration>-<seq>`
+/// @param depositor : Address which opened the lockup position
+/// @param duration : Duration the lockup position is opened with
+/// @param seq : Sequence number of the position among the depositor's positions with the same duration
+pub fn format_lockup_id(depositor: &str, duration: u64, seq: u64) -> String {
+    format!("{}-{}-{}", depositor, duration, seq)
+}
+
+/// @dev Parses the id of a lockup position into its depositor, duration and sequence number
+/// @param lockup_id : Id of the lockup position
+pub fn parse_lockup_id(lockup_id: &str) -> StdResult<(String, u64, u64)> {
+    let invalid_id = || StdError::generic_err(format!("Invalid lockup id {}", lockup_id));
+
+    let mut parts = lockup_id.rsplitn(3, '-');
+    let seq = parts.next().ok_or_else(invalid_id)?;
+    let duration = parts.next().ok_or_else(invalid_id)?;
+    let depositor = parts.next().ok_or_else(invalid_id)?;
+
+    let seq: u64 = seq.parse().map_err(|_| invalid_id())?;
+    let duration: u64 = duration.parse().map_err(|_| invalid_id())?;
+
+    // CHECK :: Ids are canonical (no leading zeros / sign) so that each position has a single id
+    if depositor.is_empty() || format_lockup_id(depositor, duration, seq) != lockup_id {
+        return Err(invalid_id());
+    }
+
+    Ok((depositor.to_string(), duration, seq))
+}
@@ -0,0 +1,142 @@
+use cosmwasm_std::{Decimal, Fraction, StdError, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Max share of their deposits which users can withdraw, as a function of the time elapsed in the withdrawal window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalSchedule {
+    /// Max share of the deposits which can be withdrawn until the deposit window closes
+    pub deposit_window_max_percent: Decimal,
+    /// Segments of the withdrawal window, sorted by increasing `end`. Each segment starts where the previous one ends (the
+    /// start of the withdrawal window for the first one), and the last one ends with the withdrawal window
+    pub segments: Vec<WithdrawalSegment>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalSegment {
+    /// Fraction of the withdrawal window elapsed at the end of the segment
+    pub end: Decimal,
+    /// Max share of the deposits which can be withdrawn at the start of the segment
+    pub start_percent: Decimal,
+    /// Max share of the deposits which can be withdrawn at the end of the segment. The max share is interpolated linearly
+    /// between `start_percent` and `end_percent` over the segment
+    pub end_percent: Decimal,
+}
+
+impl Default for WithdrawalSchedule {
+    /// 100% withdrawals allowed during the deposit window, 50% during the 1st half of the withdrawal window, then decreasing
+    /// linearly from 50% to 0% during the 2nd half
+    fn default() -> Self {
+        WithdrawalSchedule {
+            deposit_window_max_percent: Decimal::one(),
+            segments: vec![
+                WithdrawalSegment {
+                    end: Decimal::percent(50),
+                    start_percent: Decimal::percent(50),
+                    end_percent: Decimal::percent(50),
+                },
+                WithdrawalSegment {
+                    end: Decimal::one(),
+                    start_percent: Decimal::percent(50),
+                    end_percent: Decimal::zero(),
+                },
+            ],
+        }
+    }
+}
+
+impl WithdrawalSchedule {
+    /// @dev Validates the withdrawal schedule
+    pub fn validate(&self) -> StdResult<()> {
+        // CHECK :: Shares can't exceed 100%
+        if self.deposit_window_max_percent > Decimal::one()
+            || self.segments.iter().any(|segment| {
+                segment.start_percent > Decimal::one() || segment.end_percent > Decimal::one()
+            })
+        {
+            return Err(StdError::generic_err(
+                "Withdrawal schedule percents can't exceed 100%",
+            ));
+        }
+
+        // CHECK :: Segments need to be sorted by increasing end and cover the whole withdrawal window
+        let mut start = Decimal::zero();
+        for segment in self.segments.iter() {
+            if segment.end <= start {
+                return Err(StdError::generic_err(
+                    "Withdrawal schedule segments need to be sorted by increasing end",
+                ));
+            }
+            start = segment.end;
+        }
+        if start != Decimal::one() {
+            return Err(StdError::generic_err(
+                "Withdrawal schedule segments need to end with the withdrawal window",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// @dev Returns the max share of their deposits which users can withdraw at the given timestamp. The withdrawal window
+    /// limits apply from `withdrawal_window_start` included
+    /// @params withdrawal_window_start : Timestamp when the deposit window closes and the withdrawal window opens
+    /// @params withdrawal_window : Number of seconds of the withdrawal window
+    /// @params timestamp : Timestamp of the withdrawal
+    pub fn max_withdrawal_percent(
+        &self,
+        withdrawal_window_start: u64,
+        withdrawal_window: u64,
+        timestamp: u64,
+    ) -> Decimal {
+        // Deposit window
+        if timestamp < withdrawal_window_start {
+            return self.deposit_window_max_percent;
+        }
+
+        // Withdrawals not allowed once the withdrawal window is closed
+        let elapsed = timestamp - withdrawal_window_start;
+        if elapsed >= withdrawal_window {
+            return Decimal::zero();
+        }
+
+        let mut segment_start = 0u64;
+        for segment in self.segments.iter() {
+            let segment_end = (Uint128::from(withdrawal_window) * segment.end).u128() as u64;
+            if elapsed <= segment_end {
+                return interpolate_percent(segment, segment_start, segment_end, elapsed);
+            }
+            segment_start = segment_end;
+        }
+
+        Decimal::zero()
+    }
+}
+
+/// @dev Helper function. Returns the max share interpolated linearly over the segment, at `elapsed` seconds in the withdrawal window
+fn interpolate_percent(
+    segment: &WithdrawalSegment,
+    segment_start: u64,
+    segment_end: u64,
+    elapsed: u64,
+) -> Decimal {
+    if segment_end <= segment_start {
+        return segment.end_percent;
+    }
+    let segment_length = segment_end - segment_start;
+
+    if segment.start_percent >= segment.end_percent {
+        // Decreasing :: end_percent + (start_percent - end_percent) * time_left / segment_length
+        let range = segment.start_percent - segment.end_percent;
+        let percent_atomics = Uint128::new(segment.end_percent.numerator())
+            + Uint128::new(range.numerator()).multiply_ratio(segment_end - elapsed, segment_length);
+        Decimal::from_ratio(percent_atomics, segment.end_percent.denominator())
+    } else {
+        // Increasing :: start_percent + (end_percent - start_percent) * time_elapsed / segment_length
+        let range = segment.end_percent - segment.start_percent;
+        let percent_atomics = Uint128::new(segment.start_percent.numerator())
+            + Uint128::new(range.numerator())
+                .multiply_ratio(elapsed - segment_start, segment_length);
+        Decimal::from_ratio(percent_atomics, segment.start_percent.denominator())
+    }
+}
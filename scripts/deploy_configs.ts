@@ -5,7 +5,7 @@ export const mainnet: Config = {
     config: {
       owner: undefined,
       address_provider: undefined,
-      ma_ust_token: undefined,
+      assets: [{ denom: "uusd", price_weight: "1" }],
       init_timestamp: mainnet_init_timestamp,
       deposit_window: 86400 * 5,
       withdrawal_window: 86400 * 2,
@@ -56,7 +56,7 @@ export const bombay_testnet: Config = {
     config: {
       owner: undefined,
       address_provider: undefined,
-      ma_ust_token: undefined,
+      assets: [{ denom: "uusd", price_weight: "1" }],
       init_timestamp: bombay_init_timestamp,
       deposit_window: 3600 * 5,
       withdrawal_window: 3600 * 2,
@@ -121,7 +121,7 @@ interface LockdropInitMsg {
   config: {
     owner?: string;
     address_provider?: string;
-    ma_ust_token?: string;
+    assets: { denom: string; price_weight: string }[];
     init_timestamp: number;
     deposit_window: number;
     withdrawal_window: number;
@@ -135,6 +135,7 @@ interface AirdropInitMsg {
     owner?: string;
     mars_token_address: string;
     merkle_roots?: string[];
+    hashing_scheme?: string;
     from_timestamp?: number;
     to_timestamp: number;
   };
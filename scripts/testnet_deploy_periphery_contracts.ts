@@ -95,7 +95,6 @@ async function main() {
     CONFIGURATION.lockdrop_InitMsg.config.owner = wallet.key.accAddress;
     CONFIGURATION.lockdrop_InitMsg.config.address_provider =
       network.address_provider;
-    CONFIGURATION.lockdrop_InitMsg.config.ma_ust_token = network.ma_ust_token;
     console.log(CONFIGURATION.lockdrop_InitMsg);
     network.lockdrop_address = await deployContract(
       terra,
@@ -110,6 +109,22 @@ async function main() {
     );
   }
 
+  // RefreshAddresses :: Resolve the Mars addresses (MARS token, xMARS token, Red Bank, incentives) in the lockdrop
+  if (!network.lockdrop_addresses_refreshed) {
+    let tx = await executeContract(
+      terra,
+      wallet,
+      network.lockdrop_address,
+      { refresh_addresses: {} },
+      []
+    );
+    console.log(
+      `Lockdrop :: Mars addresses refreshed successfully ${tx.txhash}\n`
+    );
+    network.lockdrop_addresses_refreshed = true;
+    writeArtifact(network, terra.config.chainID);
+  }
+
   /*************************************** DEPLOYMENT :: AIRDROP CONTRACT  *****************************************/
   /*************************************** DEPLOYMENT :: AIRDROP CONTRACT  *****************************************/
   /*************************************** DEPLOYMENT :: AIRDROP CONTRACT  *****************************************/
@@ -183,7 +198,7 @@ async function main() {
           new_config: {
             owner: undefined,
             address_provider: undefined,
-            ma_ust_token: undefined,
+            assets: undefined,
             auction_contract_address: network.auction_address,
           },
         },